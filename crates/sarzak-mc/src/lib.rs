@@ -4,7 +4,8 @@ use clap::Args;
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use nut::domain::{generate_macros, generate_store, generate_types};
+use nut::codegen::{ContextGroup, Formatter};
+use nut::domain::{macros_context, store_context, types_context, GenerateOptions};
 use nut::sarzak::mc::{ModelCompilerError, ModelCompilerOptions, SarzakModelCompiler};
 
 const TYPES: &str = "types";
@@ -18,6 +19,11 @@ const DEFAULT_DOC_TESTS: bool = true;
 const DEFAULT_NEW: bool = true;
 const DEFAULT_EXTRUDE: bool = false;
 const DEFAULT_IGNORE_IGNORE: bool = false;
+const DEFAULT_CHECK: bool = false;
+const DEFAULT_FAIL_ON_DISPLACED: bool = false;
+const DEFAULT_ALLOW_NEWER: bool = false;
+const DEFAULT_SOURCE_MAP: bool = false;
+const DEFAULT_FORMATTER: &str = "rustfmt";
 
 macro_rules! extract_options {
     ($options:ident; $(($option:ident, $default:expr)),+ ) => {
@@ -60,13 +66,49 @@ pub struct SarzakCompilerOptions {
     pub extrude: Option<bool>,
     /// Force Output Ignored Blocks
     ///
-    /// Ignored blocks are used to keep random variable names and values from
-    /// triggering differences with git. However, when the model changes, most
-    /// likely these will need to be regenerated as well. This is how you do it.
-    ///
-    /// Maybe it would have been better to call this force_docs, or something?
+    /// Ignored blocks used to keep random variable names and values from
+    /// triggering differences with git. Doc tests are seeded from the model now,
+    /// so they don't need them anymore. But if you have ignore blocks of your
+    /// own, this is how you regenerate them.
     #[arg(long, short)]
     pub ignore_ignore: Option<bool>,
+    /// Check Generated Code
+    ///
+    /// Do everything but write the files. If any of them would change, say which,
+    /// show the diff, and fail. This is for CI, to catch generated code that's out
+    /// of date with the model.
+    #[arg(long, short)]
+    pub check: Option<bool>,
+    /// Fail Rather Than Displace User Code
+    ///
+    /// Normally, lines in a critical block that don't match what's being generated
+    /// are commented out, and critical blocks that aren't generated any more are
+    /// deleted. With this, generation fails instead, and says where.
+    #[arg(long, short)]
+    pub fail_on_displaced: Option<bool>,
+    /// Overwrite Files From a Newer nut
+    ///
+    /// Generated files say which version of nut wrote them. If that's newer than
+    /// this one, generation fails, rather than clobbering who knows what. With
+    /// this it's just a warning.
+    #[arg(long, short)]
+    pub allow_newer: Option<bool>,
+    /// Write Source Maps
+    ///
+    /// Write a JSON file next to each generated file, e.g., `types.map.json`, that
+    /// says which lines came from which object, attribute, or relationship in the
+    /// model. That way a compiler error in generated code can be traced back to
+    /// the model.
+    #[arg(long, short)]
+    pub source_map: Option<bool>,
+    /// Formatter for Generated Code
+    ///
+    /// `builtin` is a simple pretty-printer that doesn't need anything installed.
+    /// `none` leaves the code as it's generated. Anything else is a command, with
+    /// arguments, that reads Rust on stdin and writes it to stdout. The default
+    /// is `rustfmt`.
+    #[arg(long)]
+    pub formatter: Option<String>,
 }
 
 impl Default for SarzakCompilerOptions {
@@ -77,6 +119,11 @@ impl Default for SarzakCompilerOptions {
             new: Some(DEFAULT_NEW),
             extrude: Some(DEFAULT_EXTRUDE),
             ignore_ignore: Some(DEFAULT_IGNORE_IGNORE),
+            check: Some(DEFAULT_CHECK),
+            fail_on_displaced: Some(DEFAULT_FAIL_ON_DISPLACED),
+            allow_newer: Some(DEFAULT_ALLOW_NEWER),
+            source_map: Some(DEFAULT_SOURCE_MAP),
+            formatter: Some(DEFAULT_FORMATTER.to_owned()),
         }
     }
 }
@@ -109,26 +156,49 @@ impl SarzakModelCompiler for ModelCompiler {
                 (meta, DEFAULT_META),
                 (doc_tests, DEFAULT_DOC_TESTS),
                 (new, DEFAULT_NEW),
-                (extrude, DEFAULT_NEW),
-                (ignore_ignore, DEFAULT_IGNORE_IGNORE));
+                (extrude, DEFAULT_EXTRUDE),
+                (ignore_ignore, DEFAULT_IGNORE_IGNORE),
+                (check, DEFAULT_CHECK),
+                (fail_on_displaced, DEFAULT_FAIL_ON_DISPLACED),
+                (allow_newer, DEFAULT_ALLOW_NEWER),
+                (source_map, DEFAULT_SOURCE_MAP));
+            let generate_options = GenerateOptions {
+                meta,
+                doc_tests,
+                new_impl: new,
+                extrude_impl: extrude,
+                ignore_ignore,
+                check,
+                fail_on_displaced,
+                allow_newer,
+                source_map,
+                formatter: options
+                    .formatter
+                    .as_deref()
+                    .unwrap_or(DEFAULT_FORMATTER)
+                    .parse::<Formatter>()?,
+            };
+
+            // Catch broken models here, rather than as a panic during generation.
+            model.ensure_valid()?;
 
             let mut module_path = output.clone();
 
+            // Everything goes in the group, and it's all written at the end, or not at
+            // all. In check mode we hear about every stale file, not just the first.
+            let mut group = ContextGroup::new();
+
             module_path.set_file_name(TYPES);
             module_path.set_extension(RS_EXT);
 
             debug!("Writing 🖍️ {:?}!", module_path);
             if !test {
-                generate_types(
+                group.add(types_context(
                     &model,
                     &module_path,
                     &package,
-                    meta,
-                    doc_tests,
-                    new,
-                    extrude,
-                    ignore_ignore,
-                )?;
+                    &generate_options,
+                )?);
             } else {
                 debug!("Psych! 🙈");
             }
@@ -139,7 +209,12 @@ impl SarzakModelCompiler for ModelCompiler {
             module_path.set_extension(RS_EXT);
             debug!("Writing ✏️ {:?}!", module_path);
             if !test {
-                generate_store(&model, &module_path, &package, meta, doc_tests)?;
+                group.add(store_context(
+                    &model,
+                    &module_path,
+                    &package,
+                    &generate_options,
+                )?);
             } else {
                 debug!("Psych! 🙉");
             }
@@ -150,18 +225,18 @@ impl SarzakModelCompiler for ModelCompiler {
             module_path.set_extension(RS_EXT);
             debug!("Writing ✒️ {:?}!", module_path);
             if !test {
-                generate_macros(
+                group.add(macros_context(
                     &model,
                     &module_path,
                     &package,
-                    meta,
-                    doc_tests,
-                    ignore_ignore,
-                )?;
+                    &generate_options,
+                )?);
             } else {
                 debug!("Psych! 🙊");
             }
 
+            group.finish(check)?;
+
             Ok(())
         } else {
             Err(ModelCompilerError::CompilerError {
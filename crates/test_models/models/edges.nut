@@ -0,0 +1,33 @@
+domain edges
+
+object Edge (E) {
+    id: Uuid,
+}
+
+object "Object Edge" (OE) {
+}
+
+object Side (SD) {
+    id: Uuid,
+}
+
+object Top (TOP) {
+    id: Uuid,
+    offset: Float,
+}
+
+object Bottom (BOT) {
+}
+
+/// ❗️ {"singleton_object": true}
+object Left (LFT) {
+}
+
+object Bisection (BIS) {
+    id: Uuid,
+    offset: Float,
+}
+
+isa R1 Edge { "Object Edge", Bisection }
+isa R2 "Object Edge" { Side, Bottom, Left }
+isa R3 Side { Top }
@@ -0,0 +1,33 @@
+domain zoo
+
+object Animal (ANI) {
+    id: Uuid,
+}
+
+object Cat (CAT) {
+    id: Uuid,
+    name: String,
+}
+
+object Dog (DOG) {
+    id: Uuid,
+    name: String,
+}
+
+object Pet (PET) {
+    id: Uuid,
+    owner: String,
+}
+
+object Wild (WLD) {
+    id: Uuid,
+    range: String,
+}
+
+object Companion (CMP) {
+    id: Uuid,
+}
+
+isa R1 Animal { Cat, Dog }
+isa R2 Animal { Pet, Wild, Cat }
+isa R3 Companion { Cat, Dog }
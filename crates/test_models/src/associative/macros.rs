@@ -12,8 +12,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.2.0"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 
 /// Macro to traverse [`Anchor`][🦀] ➡ [`IsaUi`][🦞], across [`SubtypeAnchor`][🦑] via _R10_
@@ -25,7 +23,6 @@
 /// [🦀]: crate::associative::types::Anchor
 /// [🦞]: crate::associative::types::IsaUi
 /// [🦑]: crate::associative::types::SubtypeAnchor
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
@@ -36,14 +33,13 @@
 /// # use test_models::associative_maybe_get_one_iui_across_r10;
 /// # let mut store = test_models::associative::ObjectStore::new();
 ///
-/// let anchor_egg = Anchor::new(&mut store, 42);
-/// let isa_ui_rob = IsaUi::new(&mut store, 42);
-/// let subtype_anchor = SubtypeAnchor::new(&mut store, &anchor_egg, &isa_ui_rob);
+/// let anchor_hhw = Anchor::new(&mut store, 42);
+/// let isa_ui_wes = IsaUi::new(&mut store, 42);
+/// let subtype_anchor = SubtypeAnchor::new(&mut store, &anchor_hhw, &isa_ui_wes);
 ///
-/// let isa_ui_jgq = associative_maybe_get_one_iui_across_r10!(anchor_egg, store);
-/// assert_eq!(Some(&isa_ui_rob), isa_ui_jgq);
+/// let isa_ui_pqt = associative_maybe_get_one_iui_across_r10!(anchor_hhw, store);
+/// assert_eq!(Some(&isa_ui_wes), isa_ui_pqt);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"associative_maybe_get_one_iui_across_r10-emit_associative_main"}}}
 macro_rules! associative_maybe_get_one_iui_across_r10 {
@@ -68,25 +64,23 @@ pub use associative_maybe_get_one_iui_across_r10;
 /// [🦀]: crate::associative::types::IsaUi
 /// [🦞]: crate::associative::types::Anchor
 /// [🦑]: crate::associative::types::SubtypeAnchor
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
 /// # use test_models::associative::Anchor;
-/// # use test_models::associative::SubtypeAnchor;
 /// # use test_models::associative::IsaUi;
+/// # use test_models::associative::SubtypeAnchor;
 /// # use test_models::associative_get_many_anch_across_r10;
 /// # let mut store = test_models::associative::ObjectStore::new();
 ///
-/// let anchor_zhb = Anchor::new(&mut store, 42);
-/// let isa_ui_ngi = IsaUi::new(&mut store, 42);
-/// let subtype_anchor = SubtypeAnchor::new(&mut store, &anchor_zhb, &isa_ui_ngi);
+/// let anchor_wnd = Anchor::new(&mut store, 42);
+/// let isa_ui_sov = IsaUi::new(&mut store, 42);
+/// let subtype_anchor = SubtypeAnchor::new(&mut store, &anchor_wnd, &isa_ui_sov);
 ///
-/// let anchor_kbw = associative_get_many_anch_across_r10!(isa_ui_ngi, store);
-/// assert!(anchor_kbw.iter().find(|&x| **x == anchor_zhb).is_some());
+/// let anchor_xqr = associative_get_many_anch_across_r10!(isa_ui_sov, store);
+/// assert!(anchor_xqr.iter().find(|&x| **x == anchor_wnd).is_some());
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"associative_get_many_anch_across_r10-emit_associative_main"}}}
 macro_rules! associative_get_many_anch_across_r10 {
@@ -111,27 +105,25 @@ pub use associative_get_many_anch_across_r10;
 /// [🦀]: crate::associative::types::State
 /// [🦞]: crate::associative::types::Event
 /// [🦑]: crate::associative::types::AcknowledgedEvent
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
 /// # use test_models::associative::AcknowledgedEvent;
-/// # use test_models::associative::State;
 /// # use test_models::associative::Event;
+/// # use test_models::associative::State;
 /// # use test_models::associative_get_many_e_across_r20;
 /// # let mut store = test_models::associative::ObjectStore::new();
 ///
-/// let solid_ants = "hallowed_fear".to_owned();
-/// let state_xda = State::new(&mut store, solid_ants);
-/// let aberrant_humor = "sassy_stick".to_owned();
-/// let event_ijk = Event::new(&mut store, aberrant_humor);
-/// let acknowledged_event = AcknowledgedEvent::new(&mut store, &state_xda, &event_ijk);
+/// let cluttered_twig = "messy_zephyr".to_owned();
+/// let state_dsg = State::new(&mut store, cluttered_twig);
+/// let present_space = "psychedelic_orange".to_owned();
+/// let event_njb = Event::new(&mut store, present_space);
+/// let acknowledged_event = AcknowledgedEvent::new(&mut store, &state_dsg, &event_njb);
 ///
-/// let event_ykv = associative_get_many_e_across_r20!(state_xda, store);
-/// assert!(event_ykv.iter().find(|&x| **x == event_ijk).is_some());
+/// let event_zfi = associative_get_many_e_across_r20!(state_dsg, store);
+/// assert!(event_zfi.iter().find(|&x| **x == event_njb).is_some());
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"associative_get_many_e_across_r20-emit_associative_main"}}}
 macro_rules! associative_get_many_e_across_r20 {
@@ -156,27 +148,25 @@ pub use associative_get_many_e_across_r20;
 /// [🦀]: crate::associative::types::Event
 /// [🦞]: crate::associative::types::State
 /// [🦑]: crate::associative::types::AcknowledgedEvent
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
+/// # use test_models::associative::AcknowledgedEvent;
 /// # use test_models::associative::Event;
 /// # use test_models::associative::State;
-/// # use test_models::associative::AcknowledgedEvent;
 /// # use test_models::associative_get_many_s_across_r20;
 /// # let mut store = test_models::associative::ObjectStore::new();
 ///
-/// let untidy_support = "unequal_song".to_owned();
-/// let state_udq = State::new(&mut store, untidy_support);
-/// let impolite_invention = "agreeable_history".to_owned();
-/// let event_elq = Event::new(&mut store, impolite_invention);
-/// let acknowledged_event = AcknowledgedEvent::new(&mut store, &state_udq, &event_elq);
+/// let magenta_oil = "perfect_operation".to_owned();
+/// let state_vdm = State::new(&mut store, magenta_oil);
+/// let rich_eye = "pretty_transport".to_owned();
+/// let event_gjt = Event::new(&mut store, rich_eye);
+/// let acknowledged_event = AcknowledgedEvent::new(&mut store, &state_vdm, &event_gjt);
 ///
-/// let state_sot = associative_get_many_s_across_r20!(event_elq, store);
-/// assert!(state_sot.iter().find(|&x| **x == state_udq).is_some());
+/// let state_qwo = associative_get_many_s_across_r20!(event_gjt, store);
+/// assert!(state_qwo.iter().find(|&x| **x == state_vdm).is_some());
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"associative_get_many_s_across_r20-emit_associative_main"}}}
 macro_rules! associative_get_many_s_across_r20 {
@@ -24,8 +24,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.2.0"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use std::collections::HashMap;
 
@@ -26,7 +26,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -57,27 +56,23 @@ pub struct AcknowledgedEvent {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"acknowledged_event-new_impl"}}}
 impl AcknowledgedEvent {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"acknowledged_event-new_impl"}}} //⚡️
     /// Inter a new AcknowledgedEvent and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
     /// # use test_models::associative::AcknowledgedEvent;
-    /// # use test_models::associative::State;
     /// # use test_models::associative::Event;
+    /// # use test_models::associative::State;
     /// # let mut store = test_models::associative::ObjectStore::new();
     ///
-    /// let accessible_talk = "unadvised_duck".to_owned();
-    /// let state_gqk = State::new(&mut store, accessible_talk);
-    /// let aboard_paint = "wide_eyed_mint".to_owned();
-    /// let event_nyf = Event::new(&mut store, aboard_paint);
+    /// let offbeat_scarf = "functional_thrill".to_owned();
+    /// let state_ecc = State::new(&mut store, offbeat_scarf);
+    /// let massive_farmer = "mountainous_trees".to_owned();
+    /// let event_evj = Event::new(&mut store, massive_farmer);
     ///
-    /// let acknowledged_event = AcknowledgedEvent::new(&mut store, &state_gqk, &event_nyf);
+    /// let acknowledged_event = AcknowledgedEvent::new(&mut store, &state_ecc, &event_evj);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, state_id: &State, event_id: &Event) -> Self {
         let id = Uuid::new_v5(
             &UUID_NS,
@@ -96,6 +91,9 @@ impl AcknowledgedEvent {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"acknowledged_event-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"acknowledged_event-impl-extras","id":"2979402f-0980-58b6-9601-62f931e7f368"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"acknowledged_event-impl-extras"}}}
+
 /// An anchor, or anchor point, is the location where an arrow from a relationship attached
 /// to an object.
 ///
@@ -122,11 +120,8 @@ pub struct Anchor {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"anchor-new_impl"}}}
 impl Anchor {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"anchor-new_impl"}}} //⚡️
     /// Inter a new Anchor and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -136,7 +131,6 @@ impl Anchor {
     ///
     /// let anchor = Anchor::new(&mut store, 42);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, number: i64) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", number,).as_bytes());
         let new = Self { id, number };
@@ -148,6 +142,9 @@ impl Anchor {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"anchor-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"anchor-impl-extras","id":"27edcc78-f257-5a0b-a2e4-c233987e0889"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"anchor-impl-extras"}}}
+
 /// An event is sent to an object, and processed by the current state. Assuming it accepts the
 /// event. Otherwise it’s dropped on the floor.
 ///
@@ -165,22 +162,18 @@ pub struct Event {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"event-new_impl"}}}
 impl Event {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"event-new_impl"}}} //⚡️
     /// Inter a new Event and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
     /// # use test_models::associative::Event;
     /// # let mut store = test_models::associative::ObjectStore::new();
     ///
-    /// let beneficial_channel = "handsomely_son".to_owned();
+    /// let testy_plate = "frequent_air".to_owned();
     ///
-    /// let event = Event::new(&mut store, beneficial_channel);
+    /// let event = Event::new(&mut store, testy_plate);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
         let new = Self { id, name };
@@ -192,6 +185,9 @@ impl Event {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"event-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"event-impl-extras","id":"dbdfade4-b61a-5e69-ab1a-c4d10e61bedd"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"event-impl-extras"}}}
+
 /// This represents additional data necessary to render an `Isa` relationship in the user interface
 ///.
 ///
@@ -209,11 +205,8 @@ pub struct IsaUi {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"isa_ui-new_impl"}}}
 impl IsaUi {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"isa_ui-new_impl"}}} //⚡️
     /// Inter a new IsaUi and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -223,7 +216,6 @@ impl IsaUi {
     ///
     /// let isa_ui = IsaUi::new(&mut store, 42);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, number: i64) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", number,).as_bytes());
         let new = Self { id, number };
@@ -235,6 +227,9 @@ impl IsaUi {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"isa_ui-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"isa_ui-impl-extras","id":"a7d2303d-e2b5-5c4f-a7ba-a9a852146871"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"isa_ui-impl-extras"}}}
+
 /// An [Object] state, more precisely, a set of states, is where all the action happens.
 ///
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"state-struct-definition"}}}
@@ -251,22 +246,18 @@ pub struct State {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"state-new_impl"}}}
 impl State {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"state-new_impl"}}} //⚡️
     /// Inter a new State and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
     /// # use test_models::associative::State;
     /// # let mut store = test_models::associative::ObjectStore::new();
     ///
-    /// let bloody_toothpaste = "distinct_whistle".to_owned();
+    /// let hungry_pets = "scarce_ink".to_owned();
     ///
-    /// let state = State::new(&mut store, bloody_toothpaste);
+    /// let state = State::new(&mut store, hungry_pets);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
         let new = Self { id, name };
@@ -278,6 +269,9 @@ impl State {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"state-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"state-impl-extras","id":"63af1589-c7cf-50b2-ad7b-d30208ebfec4"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"state-impl-extras"}}}
+
 /// Subtype Anchor
 ///
 /// Just as it sounds, these are [`Anchor`]s used by [`Subtype`]s in an [`Isa`] relationship
@@ -300,25 +294,21 @@ pub struct SubtypeAnchor {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"subtype_anchor-new_impl"}}}
 impl SubtypeAnchor {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"subtype_anchor-new_impl"}}} //⚡️
     /// Inter a new SubtypeAnchor and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
+    /// # use test_models::associative::Anchor;
     /// # use test_models::associative::IsaUi;
     /// # use test_models::associative::SubtypeAnchor;
-    /// # use test_models::associative::Anchor;
     /// # let mut store = test_models::associative::ObjectStore::new();
     ///
-    /// let anchor_amw = Anchor::new(&mut store, 42);
-    /// let isa_ui_zje = IsaUi::new(&mut store, 42);
+    /// let anchor_zzm = Anchor::new(&mut store, 42);
+    /// let isa_ui_ybb = IsaUi::new(&mut store, 42);
     ///
-    /// let subtype_anchor = SubtypeAnchor::new(&mut store, &anchor_amw, &isa_ui_zje);
+    /// let subtype_anchor = SubtypeAnchor::new(&mut store, &anchor_zzm, &isa_ui_ybb);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, anchor_id: &Anchor, isaui_id: &IsaUi) -> Self {
         let id = Uuid::new_v5(
             &UUID_NS,
@@ -336,3 +326,6 @@ impl SubtypeAnchor {
     }
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"subtype_anchor-new_impl"}}}
 }
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"subtype_anchor-impl-extras","id":"bfb6d5f3-5dcc-5638-9a0e-de19eb25c278"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"subtype_anchor-impl-extras"}}}
@@ -0,0 +1,19 @@
+//! Edges Domain
+//!
+//! The purpose of this domain is to test a hierarchy more than one level deep,
+//! with every kind of subtype in it: instances, a supertype, a singleton, and
+//! one without any attributes at all.
+//!
+//! It's a text model, so there's no picture. See `models/edges.nut`.
+use uuid::{uuid, Uuid};
+
+pub mod macros;
+pub mod store;
+pub mod types;
+
+pub use macros::*;
+pub use store::ObjectStore;
+pub use types::*;
+
+// edges
+pub const UUID_NS: Uuid = uuid!("e4f1a343-1dd6-5191-ab16-4901f6eccf51");
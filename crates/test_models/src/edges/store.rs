@@ -0,0 +1,181 @@
+//! ObjectStore for the instances of the "Edges" domain
+//!
+//! An end user should have little need to use this directly.
+//!
+//! This store contains the following instances:
+//!    * [`ObjectEdge`]
+//!    * [`Top`]
+//!    * [`Side`]
+//!    * [`Edge`]
+//!    * [`Bisection`]
+//!
+//! # Generated Code -- edit _with care_.
+//!
+//! Don't mess with anything between `{"magic":"","kind":"CriticalBlockBegin"}`
+//! and `{"magic":"","kind":"CriticalBlockEnd"}`. Otherwise, you should be free
+//! to go wild. Happy hacking!
+//!
+//! Use the following invocation to reproduce:
+// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
+//! ```shell
+//!  /root/crate/target/debug/deps/generate_test_domain-fb40ed7c03f3d8f9 --nocapture
+//! ```
+// {"magic":"","kind":"IgnoreBlockEnd"}
+// {"magic":"","version":"1.0.0"}
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::edges::types::{Bisection, Edge, EdgeLeaf, ObjectEdge, ObjectEdgeLeaf, Side, Top};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ObjectStore {
+    object_edge: HashMap<Uuid, ObjectEdge>,
+    top: HashMap<Uuid, Top>,
+    side: HashMap<Uuid, Side>,
+    edge: HashMap<Uuid, Edge>,
+    bisection: HashMap<Uuid, Bisection>,
+}
+
+impl ObjectStore {
+    pub fn new() -> Self {
+        Self {
+            object_edge: HashMap::new(),
+            top: HashMap::new(),
+            side: HashMap::new(),
+            edge: HashMap::new(),
+            bisection: HashMap::new(),
+        }
+    }
+
+    /// Inter [`ObjectEdge`] into the [`ObjectStore`]
+    ///
+    pub fn inter_object_edge(&mut self, object_edge: ObjectEdge) {
+        self.object_edge.insert(object_edge.get_id(), object_edge);
+    }
+
+    /// Exhume [`Object Edge`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_object_edge(&self, id: &Uuid) -> Option<&ObjectEdge> {
+        self.object_edge.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, ObjectEdge)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_object_edge(&self) -> impl Iterator<Item = (&Uuid, &ObjectEdge)> {
+        self.object_edge.iter()
+    }
+
+    /// Exhume the instance at the bottom of the [`ObjectEdge`] hierarchy
+    ///
+    /// Subtypes that are supertypes are followed down until there aren't any more.
+    pub fn exhume_object_edge_leaf(&self, id: &Uuid) -> Option<ObjectEdgeLeaf<'_>> {
+        let object_edge = self.exhume_object_edge(id)?;
+        match object_edge {
+            ObjectEdge::Left(id) => Some(ObjectEdgeLeaf::Left(*id)),
+            ObjectEdge::Side(id) => {
+                let side = self.exhume_side(id)?;
+                match side {
+                    Side::Top(id) => self.exhume_top(id).map(ObjectEdgeLeaf::Top),
+                }
+            }
+            ObjectEdge::Bottom => Some(ObjectEdgeLeaf::Bottom),
+        }
+    }
+
+    /// Inter [`Top`] into the [`ObjectStore`]
+    ///
+    pub fn inter_top(&mut self, top: Top) {
+        self.top.insert(top.id, top);
+    }
+
+    /// Exhume [`Top`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_top(&self, id: &Uuid) -> Option<&Top> {
+        self.top.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Top)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_top(&self) -> impl Iterator<Item = (&Uuid, &Top)> {
+        self.top.iter()
+    }
+
+    /// Inter [`Side`] into the [`ObjectStore`]
+    ///
+    pub fn inter_side(&mut self, side: Side) {
+        self.side.insert(side.get_id(), side);
+    }
+
+    /// Exhume [`Side`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_side(&self, id: &Uuid) -> Option<&Side> {
+        self.side.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Side)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_side(&self) -> impl Iterator<Item = (&Uuid, &Side)> {
+        self.side.iter()
+    }
+
+    /// Inter [`Edge`] into the [`ObjectStore`]
+    ///
+    pub fn inter_edge(&mut self, edge: Edge) {
+        self.edge.insert(edge.get_id(), edge);
+    }
+
+    /// Exhume [`Edge`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_edge(&self, id: &Uuid) -> Option<&Edge> {
+        self.edge.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Edge)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_edge(&self) -> impl Iterator<Item = (&Uuid, &Edge)> {
+        self.edge.iter()
+    }
+
+    /// Exhume the instance at the bottom of the [`Edge`] hierarchy
+    ///
+    /// Subtypes that are supertypes are followed down until there aren't any more.
+    pub fn exhume_edge_leaf(&self, id: &Uuid) -> Option<EdgeLeaf<'_>> {
+        let edge = self.exhume_edge(id)?;
+        match edge {
+            Edge::ObjectEdge(id) => {
+                let object_edge = self.exhume_object_edge(id)?;
+                match object_edge {
+                    ObjectEdge::Left(id) => Some(EdgeLeaf::Left(*id)),
+                    ObjectEdge::Side(id) => {
+                        let side = self.exhume_side(id)?;
+                        match side {
+                            Side::Top(id) => self.exhume_top(id).map(EdgeLeaf::Top),
+                        }
+                    }
+                    ObjectEdge::Bottom => Some(EdgeLeaf::Bottom),
+                }
+            }
+            Edge::Bisection(id) => self.exhume_bisection(id).map(EdgeLeaf::Bisection),
+        }
+    }
+
+    /// Inter [`Bisection`] into the [`ObjectStore`]
+    ///
+    pub fn inter_bisection(&mut self, bisection: Bisection) {
+        self.bisection.insert(bisection.id, bisection);
+    }
+
+    /// Exhume [`Bisection`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_bisection(&self, id: &Uuid) -> Option<&Bisection> {
+        self.bisection.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Bisection)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_bisection(&self) -> impl Iterator<Item = (&Uuid, &Bisection)> {
+        self.bisection.iter()
+    }
+}
@@ -0,0 +1,447 @@
+//! Types for instances of the "Edges" domain
+//! # Domain Description
+//!
+//!
+//! # Contents
+//!
+//! The following types are defined herein:
+//!    * [`Bisection`]
+//!    * [`Edge`]
+//!    * [`LEFT`]
+//!    * [`ObjectEdge`]
+//!    * [`Side`]
+//!    * [`Top`]
+//!
+//! # Generated Code -- edit _with care_.
+//!
+//! Don't mess with anything between `{"magic":"","kind":"CriticalBlockBegin"}`
+//! and `{"magic":"","kind":"CriticalBlockEnd"}`. Otherwise, you should be free
+//! to go wild. Happy hacking!
+//!
+//! Use the following invocation to reproduce:
+// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
+//! ```shell
+//!  /root/crate/target/debug/deps/generate_test_domain-fb40ed7c03f3d8f9 --nocapture
+//! ```
+// {"magic":"","kind":"IgnoreBlockEnd"}
+// {"magic":"","version":"1.0.0"}
+use serde::{Deserialize, Serialize};
+use uuid::{uuid, Uuid};
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"imports"}}}
+use crate::edges::store::ObjectStore;
+use crate::edges::UUID_NS;
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"imports"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"bisection-struct-definition"}}}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Bisection {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub offset: `f64`,
+    ///
+    pub offset: f64,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"bisection-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"bisection-new_impl"}}}
+impl Bisection {
+    /// Inter a new Bisection and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::Bisection;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    ///
+    /// let bisection = Bisection::new(&mut store, 42.0);
+    ///```
+    pub fn new(store: &mut ObjectStore, offset: f64) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{}::", offset,).as_bytes());
+        let new = Self { id, offset };
+
+        store.inter_bisection(new.clone());
+
+        new
+    }
+    // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"bisection-new_impl"}}}
+}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"bisection-impl-extras","id":"f6496c3c-adfa-5cf5-80b3-21bf2f0d7040"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"bisection-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"edge-enum-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Edge {
+    /// `ObjectEdge(ObjectEdge)`,
+    ///
+    ObjectEdge(Uuid),
+    /// `Bisection(Bisection)`,
+    ///
+    Bisection(Uuid),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"edge-enum-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"edge-enum-get-id-impl"}}}
+impl Edge {
+    /// The id of the instance at the bottom of the hierarchy
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::Edge;
+    /// # use test_models::edges::EdgeLeaf;
+    /// # use test_models::edges::ObjectEdge;
+    /// # use test_models::edges::Side;
+    /// # use test_models::edges::Top;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    /// let top = Top::new(&mut store, 42.0);
+    /// let side = Side::Top(top.id);
+    /// store.inter_side(side.clone());
+    /// let object_edge = ObjectEdge::Side(side.get_id());
+    /// store.inter_object_edge(object_edge.clone());
+    /// let edge = Edge::ObjectEdge(object_edge.get_id());
+    /// store.inter_edge(edge.clone());
+    ///
+    /// assert_eq!(edge.get_id(), top.id);
+    /// assert_eq!(store.exhume_edge_leaf(&edge.get_id()), Some(EdgeLeaf::Top(&top)));
+    ///```
+    pub fn get_id(&self) -> Uuid {
+        match *self {
+            Self::ObjectEdge(z) => z,
+            Self::Bisection(z) => z,
+        }
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"edge-enum-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"edge-test_default"}}}
+impl Edge {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let test = Self::ObjectEdge(ObjectEdge::test_default(store).get_id());
+
+        store.inter_edge(test.clone());
+
+        test
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"edge-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"edge-conversions"}}}
+impl Edge {
+    /// Exhume the [`ObjectEdge`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::Edge;
+    /// # use test_models::edges::ObjectEdge;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    /// let object_edge = ObjectEdge::test_default(&mut store);
+    /// let edge = Edge::from(&object_edge);
+    ///
+    /// assert_eq!(edge.as_object_edge(&store), Some(&object_edge));
+    /// let same: &ObjectEdge = (&edge, &store).try_into().unwrap();
+    /// assert_eq!(same, &object_edge);
+    ///```
+    pub fn as_object_edge<'a>(&self, store: &'a ObjectStore) -> Option<&'a ObjectEdge> {
+        match self {
+            Self::ObjectEdge(id) => store.exhume_object_edge(id),
+            _ => None,
+        }
+    }
+
+    /// Exhume the [`Bisection`], if that's what this is
+    ///
+    pub fn as_bisection<'a>(&self, store: &'a ObjectStore) -> Option<&'a Bisection> {
+        match self {
+            Self::Bisection(id) => store.exhume_bisection(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<&ObjectEdge> for Edge {
+    fn from(object_edge: &ObjectEdge) -> Self {
+        Self::ObjectEdge(object_edge.get_id())
+    }
+}
+
+impl<'a> TryFrom<(&Edge, &'a ObjectStore)> for &'a ObjectEdge {
+    type Error = Edge;
+
+    fn try_from((edge, store): (&Edge, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        edge.as_object_edge(store).ok_or_else(|| edge.clone())
+    }
+}
+
+impl From<&Bisection> for Edge {
+    fn from(bisection: &Bisection) -> Self {
+        Self::Bisection(bisection.id)
+    }
+}
+
+impl<'a> TryFrom<(&Edge, &'a ObjectStore)> for &'a Bisection {
+    type Error = Edge;
+
+    fn try_from((edge, store): (&Edge, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        edge.as_bisection(store).ok_or_else(|| edge.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"edge-conversions"}}}
+
+/// The instances at the bottom of the [`Edge`] hierarchy
+///
+/// This is what [`ObjectStore::exhume_edge_leaf`] finds.
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"edge-leaf-definition"}}}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgeLeaf<'a> {
+    Bisection(&'a Bisection),
+    Bottom,
+    Left(Uuid),
+    Top(&'a Top),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"edge-leaf-definition"}}}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"edge-impl-extras","id":"d01f2378-3539-5b5f-ad97-0d0558f7d40e"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"edge-impl-extras"}}}
+
+/// ❗️ {"singleton_object": true}
+///
+//
+pub const LEFT: Uuid = uuid!["8125ae7e-9edb-5e9c-be33-643f1277e0e0"];
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"object_edge-enum-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ObjectEdge {
+    /// `Left(Left)`,
+    ///
+    Left(Uuid),
+    /// `Side(Side)`,
+    ///
+    Side(Uuid),
+    Bottom,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"object_edge-enum-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"object_edge-enum-get-id-impl"}}}
+impl ObjectEdge {
+    /// The id of the instance at the bottom of the hierarchy
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::ObjectEdge;
+    /// # use test_models::edges::ObjectEdgeLeaf;
+    /// # use test_models::edges::Side;
+    /// # use test_models::edges::Top;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    /// let top = Top::new(&mut store, 42.0);
+    /// let side = Side::Top(top.id);
+    /// store.inter_side(side.clone());
+    /// let object_edge = ObjectEdge::Side(side.get_id());
+    /// store.inter_object_edge(object_edge.clone());
+    ///
+    /// assert_eq!(object_edge.get_id(), top.id);
+    /// assert_eq!(store.exhume_object_edge_leaf(&object_edge.get_id()), Some(ObjectEdgeLeaf::Top(&top)));
+    ///```
+    pub fn get_id(&self) -> Uuid {
+        match *self {
+            Self::Left(z) => z,
+            Self::Side(z) => z,
+            Self::Bottom => Uuid::from_u128(0xcd977757dbcb5e5da0dcd0e6624db6a0),
+        }
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"object_edge-enum-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"object_edge-test_default"}}}
+impl ObjectEdge {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let test = Self::Left(LEFT);
+
+        store.inter_object_edge(test.clone());
+
+        test
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"object_edge-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"object_edge-conversions"}}}
+impl ObjectEdge {
+    /// Exhume the [`Side`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::ObjectEdge;
+    /// # use test_models::edges::Side;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    /// let side = Side::test_default(&mut store);
+    /// let object_edge = ObjectEdge::from(&side);
+    ///
+    /// assert_eq!(object_edge.as_side(&store), Some(&side));
+    /// let same: &Side = (&object_edge, &store).try_into().unwrap();
+    /// assert_eq!(same, &side);
+    ///```
+    pub fn as_side<'a>(&self, store: &'a ObjectStore) -> Option<&'a Side> {
+        match self {
+            Self::Side(id) => store.exhume_side(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Side> for ObjectEdge {
+    fn from(side: &Side) -> Self {
+        Self::Side(side.get_id())
+    }
+}
+
+impl<'a> TryFrom<(&ObjectEdge, &'a ObjectStore)> for &'a Side {
+    type Error = ObjectEdge;
+
+    fn try_from((object_edge, store): (&ObjectEdge, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        object_edge
+            .as_side(store)
+            .ok_or_else(|| object_edge.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"object_edge-conversions"}}}
+
+/// The instances at the bottom of the [`ObjectEdge`] hierarchy
+///
+/// This is what [`ObjectStore::exhume_object_edge_leaf`] finds.
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"object_edge-leaf-definition"}}}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObjectEdgeLeaf<'a> {
+    Bottom,
+    Left(Uuid),
+    Top(&'a Top),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"object_edge-leaf-definition"}}}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"object_edge-impl-extras","id":"6cec25c6-d33b-55c0-a594-9eaaece27ed6"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"object_edge-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"side-enum-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Side {
+    /// `Top(Top)`,
+    ///
+    Top(Uuid),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"side-enum-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"side-enum-get-id-impl"}}}
+impl Side {
+    pub fn get_id(&self) -> Uuid {
+        match *self {
+            Self::Top(z) => z,
+        }
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"side-enum-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"side-test_default"}}}
+impl Side {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let test = Self::Top(Top::new(store, 42.0).id);
+
+        store.inter_side(test.clone());
+
+        test
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"side-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"side-conversions"}}}
+impl Side {
+    /// Exhume the [`Top`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::Side;
+    /// # use test_models::edges::Top;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    /// let top = Top::new(&mut store, 42.0);
+    /// let side = Side::from(&top);
+    ///
+    /// assert_eq!(side.as_top(&store), Some(&top));
+    /// let same: &Top = (&side, &store).try_into().unwrap();
+    /// assert_eq!(same, &top);
+    ///```
+    pub fn as_top<'a>(&self, store: &'a ObjectStore) -> Option<&'a Top> {
+        match self {
+            Self::Top(id) => store.exhume_top(id),
+        }
+    }
+}
+
+impl From<&Top> for Side {
+    fn from(top: &Top) -> Self {
+        Self::Top(top.id)
+    }
+}
+
+impl<'a> TryFrom<(&Side, &'a ObjectStore)> for &'a Top {
+    type Error = Side;
+
+    fn try_from((side, store): (&Side, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        side.as_top(store).ok_or_else(|| side.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"side-conversions"}}}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"side-impl-extras","id":"cb7fc9af-21c2-5336-85d0-b828306a8f30"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"side-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"top-struct-definition"}}}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Top {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub offset: `f64`,
+    ///
+    pub offset: f64,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"top-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"top-new_impl"}}}
+impl Top {
+    /// Inter a new Top and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::edges::Top;
+    /// # let mut store = test_models::edges::ObjectStore::new();
+    ///
+    ///
+    /// let top = Top::new(&mut store, 42.0);
+    ///```
+    pub fn new(store: &mut ObjectStore, offset: f64) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{}::", offset,).as_bytes());
+        let new = Self { id, offset };
+
+        store.inter_top(new.clone());
+
+        new
+    }
+    // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"top-new_impl"}}}
+}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"top-impl-extras","id":"a04b0262-b3be-5721-a8b0-0e790b509243"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"top-impl-extras"}}}
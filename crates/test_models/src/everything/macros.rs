@@ -12,8 +12,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.2.0"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 
 /// Macro to traverse [`Everything`][🦀] ➡ [`RandoObject`][🦞], via _R1_
@@ -24,25 +22,23 @@
 ///
 /// [🦀]: crate::everything::types::Everything
 /// [🦞]: crate::everything::types::RandoObject
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
-/// # use test_models::everything::RandoObject;
 /// # use test_models::everything::Everything;
+/// # use test_models::everything::RandoObject;
 /// # use test_models::everything_get_one_ro_across_r1;
 /// # let mut store = test_models::everything::ObjectStore::new();
 ///
-/// let rando_object_bub = RandoObject::new(&mut store);
-/// let troubled_holiday = "living_net".to_owned();
+/// let rando_object_yxb = RandoObject::new(&mut store);
+/// let clear_receipt = "handy_reaction".to_owned();
 ///
-/// let everything = Everything::new(&mut store, &rando_object_bub, 42, true, 42.0, troubled_holiday);
+/// let everything = Everything::new(&mut store, &rando_object_yxb, 42, true, 42.0, clear_receipt);
 ///
-/// let rando_object_bfx = everything_get_one_ro_across_r1!(everything, store);
-/// assert_eq!(&rando_object_bub, rando_object_bfx);
+/// let rando_object_fvw = everything_get_one_ro_across_r1!(everything, store);
+/// assert_eq!(&rando_object_yxb, rando_object_fvw);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"everything_get_one_ro_across_r1-emit_binary_main"}}}
 macro_rules! everything_get_one_ro_across_r1 {
@@ -64,7 +60,6 @@ pub use everything_get_one_ro_across_r1;
 ///
 /// [🦀]: crate::everything::types::RandoObject
 /// [🦞]: crate::everything::types::Everything
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
@@ -74,21 +69,18 @@ pub use everything_get_one_ro_across_r1;
 /// # use test_models::everything_get_one_e_across_r1;
 /// # let mut store = test_models::everything::ObjectStore::new();
 ///
-/// let rando_object_tie = RandoObject::new(&mut store);
-/// let plant_substance = "quarrelsome_wilderness".to_owned();
+/// let rando_object_tjh = RandoObject::new(&mut store);
+/// let vast_lumber = "needy_girls".to_owned();
 ///
-/// let everything = Everything::new(&mut store, &rando_object_tie, 42, true, 42.0, plant_substance);
-/// let everything_amk = everything_get_one_e_across_r1!(rando_object_tie, store);
+/// let everything = Everything::new(&mut store, &rando_object_tjh, 42, true, 42.0, vast_lumber);
+/// let everything_trt = everything_get_one_e_across_r1!(rando_object_tjh, store);
 ///
-/// assert_eq!(&everything, everything_amk);
+/// assert_eq!(&everything, everything_trt);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"everything_get_one_e_across_r1-emit_binary_main"}}}
 macro_rules! everything_get_one_e_across_r1 {
     ($input:expr, $store:expr) => {{
-//         // {"magic":"","kind":"CriticalBlockBegin"} //⚡️
-//         // {"magic":"","kind":{"CriticalBlockBegin":"rando_object-everything-emit_one_unconditional_lookup"}} //⚡️
         // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"rando_object-everything-emit_one_unconditional_lookup"}}}
         // nut::codegen::template::macros::emit_one_unconditional_lookup
         $store
@@ -96,9 +88,6 @@ macro_rules! everything_get_one_e_across_r1 {
             .find(|z| z.1.rando == $input.id)
             .map(|z| z.1)
             .unwrap()
-// ⚡️         // {"magic":"","kind":"CriticalBlockEnd"}
-// ⚡️             // {"magic":"","kind":{"CriticalBlockEnd":"rando_object-everything-emit_one_unconditional_lookup"}}
-//             // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"rando_object-everything-emit_one_unconditional_lookup"}}} //⚡️
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"rando_object-everything-emit_one_unconditional_lookup"}}}
     }};
 }
@@ -22,7 +22,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -60,11 +59,8 @@ pub struct Everything {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"everything-new_impl"}}}
 impl Everything {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"everything-new_impl"}}} //⚡️
     /// Inter a new Everything and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -72,33 +68,27 @@ impl Everything {
     /// # use test_models::everything::RandoObject;
     /// # let mut store = test_models::everything::ObjectStore::new();
     ///
-    /// let rando_object_wtd = RandoObject::new(&mut store);
-    /// let stiff_point = "spiffy_payment".to_owned();
+    /// let rando_object_lvt = RandoObject::new(&mut store);
+    /// let tasteful_addition = "woozy_attention".to_owned();
     ///
-    /// let everything = Everything::new(&mut store, &rando_object_wtd, 42, true, 42.0, stiff_point);
+    /// let everything = Everything::new(&mut store, &rando_object_lvt, 42, true, 42.0, tasteful_addition);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(
         store: &mut ObjectStore,
         rando: &RandoObject,
-        //         float: f64, //⚡️
         int: i64,
-        //         string: std::string::String, //⚡️
         bool: bool,
         float: f64,
         string: std::string::String,
     ) -> Self {
         let id = Uuid::new_v5(
             &UUID_NS,
-            //             format!("{:?}::{}::{}::{}::{}::", rando, float, int, string, bool,).as_bytes(), //⚡️
             format!("{:?}::{}::{}::{}::{}::", rando, int, bool, float, string,).as_bytes(),
         );
         let new = Self {
             id,
             rando: rando.id,
-            //             float, //⚡️
             int,
-            //             string, //⚡️
             bool,
             float,
             string,
@@ -111,6 +101,9 @@ impl Everything {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"everything-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"everything-impl-extras","id":"d0c37981-f0bd-5431-afdb-644bb5272394"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"everything-impl-extras"}}}
+
 /// Just some random object with which we wish to relate
 ///
 /// How tawdry.
@@ -126,11 +119,8 @@ pub struct RandoObject {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"rando_object-new_impl"}}}
 impl RandoObject {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"rando_object-new_impl"}}} //⚡️
     /// Inter a new RandoObject and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -140,7 +130,6 @@ impl RandoObject {
     ///
     /// let rando_object = RandoObject::new(&mut store);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("",).as_bytes());
         let new = Self { id };
@@ -151,3 +140,6 @@ impl RandoObject {
     }
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"rando_object-new_impl"}}}
 }
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"rando_object-impl-extras","id":"537ce2a3-354b-594c-b346-14e4549fe4fd"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"rando_object-impl-extras"}}}
@@ -24,7 +24,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -60,24 +59,8 @@ pub struct AnotherObject {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"another_object-new_impl"}}}
 impl AnotherObject {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"another_object-new_impl"}}} //⚡️
     /// Inter a new AnotherObject and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
-    /// # Example
-    ///
-    ///```
-    /// # use nut::sarzak::Object;
-    /// # use test_models::imported_object::AnotherObject;
-    /// # let mut store = test_models::imported_object::ObjectStore::new();
-    ///
-    /// let object_rtb = Object::default();
-    ///
-    ///
-    /// let another_object = AnotherObject::new(&mut store, &object_rtb);
-    ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, ptr: &Object) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::", ptr,).as_bytes());
         let new = Self { id, ptr: ptr.id };
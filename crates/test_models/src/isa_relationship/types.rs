@@ -26,7 +26,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use serde::{Deserialize, Serialize};
 use uuid::{uuid, Uuid};
@@ -68,9 +67,9 @@ pub enum SimpleSupertype {
     ///
     SimpleSubtypeA(Uuid),
 }
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"{}-enum-definition"}}}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"simple_supertype-enum-definition"}}}
 
-// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"SimpleSupertype-enum-get-id-impl"}}}
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"simple_supertype-enum-get-id-impl"}}}
 impl SimpleSupertype {
     pub fn get_id(&self) -> Uuid {
         match *self {
@@ -79,8 +78,7 @@ impl SimpleSupertype {
         }
     }
 }
-
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"SimpleSupertype-enum-get-id-impl"}}}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"simple_supertype-enum-get-id-impl"}}}
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"simple_supertype-test_default"}}}
 impl SimpleSupertype {
@@ -94,6 +92,9 @@ impl SimpleSupertype {
 }
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"simple_supertype-test_default"}}}
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"simple_supertype-impl-extras","id":"6339b18b-3929-51ae-ad1a-f0cb4dc73362"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"simple_supertype-impl-extras"}}}
+
 /// This [`Subtype`][s] has [`Attribute`][a]s
 ///
 /// [a]: nut::sarzak::Attribute
@@ -113,22 +114,18 @@ pub struct SubtypeA {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"subtype_a-new_impl"}}}
 impl SubtypeA {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"subtype_a-new_impl"}}} //⚡️
     /// Inter a new SubtypeA and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
     /// # use test_models::isa_relationship::SubtypeA;
     /// # let mut store = test_models::isa_relationship::ObjectStore::new();
     ///
-    /// let real_alley = "sleepy_pleasure".to_owned();
+    /// let berserk_lip = "abhorrent_flight".to_owned();
     ///
-    /// let subtype_a = SubtypeA::new(&mut store, real_alley);
+    /// let subtype_a = SubtypeA::new(&mut store, berserk_lip);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
         let new = Self { id, name };
@@ -140,6 +137,9 @@ impl SubtypeA {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"subtype_a-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"subtype_a-impl-extras","id":"1d0706f7-0c46-589b-8f3a-944f0e5aa612"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"subtype_a-impl-extras"}}}
+
 /// This [`Subtype`][s] has a number
 ///
 /// [s]: nut::sarzak::Subtype
@@ -158,11 +158,8 @@ pub struct SubtypeB {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"subtype_b-new_impl"}}}
 impl SubtypeB {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"subtype_b-new_impl"}}} //⚡️
     /// Inter a new SubtypeB and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -172,7 +169,6 @@ impl SubtypeB {
     ///
     /// let subtype_b = SubtypeB::new(&mut store, 42);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, number: i64) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", number,).as_bytes());
         let new = Self { id, number };
@@ -184,6 +180,9 @@ impl SubtypeB {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"subtype_b-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"subtype_b-impl-extras","id":"8deb75d7-beb7-5451-8152-34255a4f3937"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"subtype_b-impl-extras"}}}
+
 /// A [`Supertype`] with normal [`Subtype`]s
 ///
 /// This was called "Super". Rust didn't like it when it became "super". There needs to be
@@ -199,9 +198,9 @@ pub enum SuperT {
     ///
     SubtypeB(Uuid),
 }
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"{}-enum-definition"}}}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"super_t-enum-definition"}}}
 
-// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"SuperT-enum-get-id-impl"}}}
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"super_t-enum-get-id-impl"}}}
 impl SuperT {
     pub fn get_id(&self) -> Uuid {
         match *self {
@@ -210,19 +209,13 @@ impl SuperT {
         }
     }
 }
-
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"SuperT-enum-get-id-impl"}}}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"super_t-enum-get-id-impl"}}}
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"super_t-test_default"}}}
 impl SuperT {
     pub fn test_default(store: &mut ObjectStore) -> Self {
-        //         // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-        //         let male_cakes = "kindly_achiever".to_owned(); //⚡️
-        //         let test = Self::SubtypeA(SubtypeA::new(store, male_cakes).id); //⚡️
-        // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
-        let loose_manager = "dry_feast".to_owned();
-        let test = Self::SubtypeA(SubtypeA::new(store, loose_manager).id);
-        // {"magic":"","kind":"IgnoreBlockEnd"}
+        let ten_current = "defiant_meat".to_owned();
+        let test = Self::SubtypeA(SubtypeA::new(store, ten_current).id);
 
         store.inter_super_t(test.clone());
 
@@ -230,3 +223,72 @@ impl SuperT {
     }
 }
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"super_t-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"super_t-conversions"}}}
+impl SuperT {
+    /// Exhume the [`SubtypeA`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::isa_relationship::SubtypeA;
+    /// # use test_models::isa_relationship::SuperT;
+    /// # let mut store = test_models::isa_relationship::ObjectStore::new();
+    ///
+    /// let ten_current = "defiant_meat".to_owned();
+    /// let subtype_a = SubtypeA::new(&mut store, ten_current);
+    /// let super_t = SuperT::from(&subtype_a);
+    ///
+    /// assert_eq!(super_t.as_subtype_a(&store), Some(&subtype_a));
+    /// let same: &SubtypeA = (&super_t, &store).try_into().unwrap();
+    /// assert_eq!(same, &subtype_a);
+    ///```
+    pub fn as_subtype_a<'a>(&self, store: &'a ObjectStore) -> Option<&'a SubtypeA> {
+        match self {
+            Self::SubtypeA(id) => store.exhume_subtype_a(id),
+            _ => None,
+        }
+    }
+
+    /// Exhume the [`SubtypeB`], if that's what this is
+    ///
+    pub fn as_subtype_b<'a>(&self, store: &'a ObjectStore) -> Option<&'a SubtypeB> {
+        match self {
+            Self::SubtypeB(id) => store.exhume_subtype_b(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<&SubtypeA> for SuperT {
+    fn from(subtype_a: &SubtypeA) -> Self {
+        Self::SubtypeA(subtype_a.id)
+    }
+}
+
+impl<'a> TryFrom<(&SuperT, &'a ObjectStore)> for &'a SubtypeA {
+    type Error = SuperT;
+
+    fn try_from((super_t, store): (&SuperT, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        super_t.as_subtype_a(store).ok_or_else(|| super_t.clone())
+    }
+}
+
+impl From<&SubtypeB> for SuperT {
+    fn from(subtype_b: &SubtypeB) -> Self {
+        Self::SubtypeB(subtype_b.id)
+    }
+}
+
+impl<'a> TryFrom<(&SuperT, &'a ObjectStore)> for &'a SubtypeB {
+    type Error = SuperT;
+
+    fn try_from((super_t, store): (&SuperT, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        super_t.as_subtype_b(store).ok_or_else(|| super_t.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"super_t-conversions"}}}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"super_t-impl-extras","id":"78833415-f92b-59be-9e0b-b35db2d119e9"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"super_t-impl-extras"}}}
@@ -1,7 +1,9 @@
 pub mod associative;
+pub mod edges;
 pub mod everything;
 pub mod imported_object;
 pub mod isa_relationship;
 pub mod one_to_many;
 pub mod one_to_one;
 pub mod singleton;
+pub mod zoo;
@@ -12,8 +12,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.2.0"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 
 /// Macro to traverse [`A`][🦀] ➡ [`Referent`][🦞], via _R1_
@@ -24,26 +22,24 @@
 ///
 /// [🦀]: crate::one_to_many::types::A
 /// [🦞]: crate::one_to_many::types::Referent
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
-/// # use test_models::one_to_many::Referent;
 /// # use test_models::one_to_many::A;
-/// # use test_models::one_to_many_get_one_tgt_across_r1;
+/// # use test_models::one_to_many::Referent;
+/// # use test_models::one_to_many_get_many_tgts_across_r1;
 /// # let mut store = test_models::one_to_many::ObjectStore::new();
 ///
-/// let profuse_sneeze = "fertile_route".to_owned();
-/// let referent_mmk = Referent::new(&mut store, profuse_sneeze);
-/// let fluttering_value = "voracious_beggar".to_owned();
+/// let nutritious_nose = "superb_crook".to_owned();
+/// let referent_erv = Referent::new(&mut store, nutritious_nose);
+/// let military_lizards = "unsuitable_metal".to_owned();
 ///
-/// let a = A::new(&mut store, &referent_mmk, fluttering_value);
+/// let a = A::new(&mut store, &referent_erv, military_lizards);
 ///
-/// let referent_aob = one_to_many_get_one_tgt_across_r1!(a, store);
-/// assert_eq!(&referent_mmk, referent_aob);
+/// let referent_jxx = one_to_many_get_many_tgts_across_r1!(a, store);
+/// assert_eq!(&referent_erv, referent_jxx);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"one_to_many_get_many_tgts_across_r1-emit_binary_main"}}}
 macro_rules! one_to_many_get_many_tgts_across_r1 {
@@ -59,8 +55,6 @@ macro_rules! one_to_many_get_many_tgts_across_r1 {
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-emit_many_unconditional"}}}
     }};
 }
-pub use one_to_many_get_one_tgt_across_r1;
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_one_tgt_across_r1-emit_binary_main"}}}
 pub use one_to_many_get_many_tgts_across_r1;
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_many_tgts_across_r1-emit_binary_main"}}}
 
@@ -72,26 +66,24 @@ pub use one_to_many_get_many_tgts_across_r1;
 ///
 /// [🦀]: crate::one_to_many::types::Referent
 /// [🦞]: crate::one_to_many::types::A
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
 /// # use test_models::one_to_many::A;
 /// # use test_models::one_to_many::Referent;
-/// # use test_models::one_to_many_get_many_as_across_r1;
+/// # use test_models::one_to_many_get_one_a_across_r1;
 /// # let mut store = test_models::one_to_many::ObjectStore::new();
 ///
-/// let needy_hill = "foolish_clocks".to_owned();
-/// let referent_anv = Referent::new(&mut store, needy_hill);
-/// let smelly_fang = "elated_arch".to_owned();
+/// let alcoholic_brothers = "deafening_beam".to_owned();
+/// let referent_aio = Referent::new(&mut store, alcoholic_brothers);
+/// let fair_frame = "dreary_able".to_owned();
 ///
-/// let a = A::new(&mut store, &referent_anv, smelly_fang);
-/// let a_kil = one_to_many_get_many_as_across_r1!(referent_anv, store);
+/// let a = A::new(&mut store, &referent_aio, fair_frame);
+/// let a_jht = one_to_many_get_one_a_across_r1!(referent_aio, store);
 ///
-/// assert!(a_kil.iter().find(|&x| **x == a).is_some());
+/// assert_eq!(&a, a_jht);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"one_to_many_get_one_a_across_r1-emit_binary_main"}}}
 macro_rules! one_to_many_get_one_a_across_r1 {
@@ -106,42 +98,35 @@ macro_rules! one_to_many_get_one_a_across_r1 {
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-a-emit_one_unconditional_lookup"}}}
     }};
 }
-pub use one_to_many_get_many_as_across_r1;
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_many_as_across_r1-emit_binary_main"}}}
 pub use one_to_many_get_one_a_across_r1;
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_one_a_across_r1-emit_binary_main"}}}
 
-/// Macro to traverse [`B`][🦀] ➡ [`Referent`][🦞], via _R2(c)_
 /// Macro to traverse [`B`][🦀] ➡ [`Referent`][🦞], via _R2_
 ///
-/// This macro expects a &[`B`][🦀], and returns an Option<&[`Referent`][🦞]>.
 /// This macro expects a &[`B`][🦀], and returns a &[`Referent`][🦞].
 ///
 /// Generated by `nut::domain::generate_macros::generate_binary_macro_referrer_to_referent_imp`
 ///
 /// [🦀]: crate::one_to_many::types::B
 /// [🦞]: crate::one_to_many::types::Referent
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
-/// # use test_models::one_to_many::Referent;
 /// # use test_models::one_to_many::B;
-/// # use test_models::one_to_many_maybe_get_one_tgt_across_r2;
+/// # use test_models::one_to_many::Referent;
+/// # use test_models::one_to_many_get_many_tgts_across_r2;
 /// # let mut store = test_models::one_to_many::ObjectStore::new();
 ///
-/// let tame_motion = "goofy_veil".to_owned();
-/// let referent = Referent::new(&mut store, tame_motion);
-/// let exotic_lawyer = "super_bears".to_owned();
-/// let maddening_organization = "six_spade".to_owned();
+/// let hospitable_flame = "simplistic_soap".to_owned();
+/// let referent_zyj = Referent::new(&mut store, hospitable_flame);
+/// let oafish_apple = "defiant_twig".to_owned();
 ///
-/// let b = B::new(&mut store, Some(&referent), exotic_lawyer);
+/// let b = B::new(&mut store, &referent_zyj, oafish_apple);
 ///
-/// let referent_hik = one_to_many_maybe_get_one_tgt_across_r2!(b, store);
-/// assert_eq!(Some(&referent), referent_hik);
+/// let referent_jac = one_to_many_get_many_tgts_across_r2!(b, store);
+/// assert_eq!(&referent_zyj, referent_jac);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"one_to_many_get_many_tgts_across_r2-emit_binary_main"}}}
 macro_rules! one_to_many_get_many_tgts_across_r2 {
@@ -157,42 +142,35 @@ macro_rules! one_to_many_get_many_tgts_across_r2 {
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-emit_many_unconditional"}}}
     }};
 }
-pub use one_to_many_maybe_get_one_tgt_across_r2;
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_maybe_get_one_tgt_across_r2-emit_binary_main"}}}
 pub use one_to_many_get_many_tgts_across_r2;
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_many_tgts_across_r2-emit_binary_main"}}}
 
-/// Macro to traverse [`Referent`][🦀] ➡ [`B`][🦞], via _R2_
 /// Macro to traverse [`Referent`][🦀] ➡ [`B`][🦞], via _R2(c)_
 ///
-/// This macro expects a &[`Referent`][🦀], and returns a &[`B`][🦞].
 /// This macro expects a &[`Referent`][🦀], and returns an Option<&[`B`][🦞]>.
 ///
 /// Generated by `nut::domain::generate_macros::generate_binary_macro_referent_to_referrer_imp`
 ///
 /// [🦀]: crate::one_to_many::types::Referent
 /// [🦞]: crate::one_to_many::types::B
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
 /// # use test_models::one_to_many::B;
 /// # use test_models::one_to_many::Referent;
-/// # use test_models::one_to_many_get_many_bs_across_r2;
+/// # use test_models::one_to_many_maybe_get_one_b_across_r2;
 /// # let mut store = test_models::one_to_many::ObjectStore::new();
 ///
-/// let undesirable_card = "wandering_growth".to_owned();
-/// let endurable_treatment = "colossal_hour".to_owned();
-/// let referent = Referent::new(&mut store, endurable_treatment);
-/// let brown_hat = "wealthy_salt".to_owned();
+/// let succinct_coal = "glib_swim".to_owned();
+/// let referent_zhq = Referent::new(&mut store, succinct_coal);
+/// let acid_meeting = "ahead_wire".to_owned();
 ///
-/// let b = B::new(&mut store, Some(&referent), brown_hat);
+/// let b = B::new(&mut store, &referent_zhq, acid_meeting);
+/// let b_ytm = one_to_many_maybe_get_one_b_across_r2!(referent_zhq, store);
 ///
-/// let b_zje = one_to_many_get_many_bs_across_r2!(referent, store);
-/// assert!(b_zje.iter().find(|&x| **x == b).is_some());
+/// assert_eq!(Some(&b), b_ytm);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"one_to_many_maybe_get_one_b_across_r2-emit_binary_main"}}}
 macro_rules! one_to_many_maybe_get_one_b_across_r2 {
@@ -206,40 +184,35 @@ macro_rules! one_to_many_maybe_get_one_b_across_r2 {
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-b-emit_one_conditional_lookup"}}}
     }};
 }
-pub use one_to_many_get_many_bs_across_r2;
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_many_bs_across_r2-emit_binary_main"}}}
 pub use one_to_many_maybe_get_one_b_across_r2;
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_maybe_get_one_b_across_r2-emit_binary_main"}}}
 
-/// Macro to traverse [`C`][🦀] ➡ [`Referent`][🦞], via _R3_
 /// Macro to traverse [`C`][🦀] ➡ [`Referent`][🦞], via _R3(c)_
 ///
-/// This macro expects a &[`C`][🦀], and returns a &[`Referent`][🦞].
 /// This macro expects a &[`C`][🦀], and returns an Option<&[`Referent`][🦞]>.
 ///
 /// Generated by `nut::domain::generate_macros::generate_binary_macro_referrer_to_referent_imp`
 ///
 /// [🦀]: crate::one_to_many::types::C
 /// [🦞]: crate::one_to_many::types::Referent
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
 /// # use test_models::one_to_many::C;
 /// # use test_models::one_to_many::Referent;
-/// # use test_models::one_to_many_get_one_tgt_across_r3;
+/// # use test_models::one_to_many_maybe_get_many_tgts_across_r3;
 /// # let mut store = test_models::one_to_many::ObjectStore::new();
 ///
-/// let righteous_throat = "petite_thumb".to_owned();
-/// let referent_ttx = Referent::new(&mut store, righteous_throat);
+/// let smooth_letter = "hungry_hospital".to_owned();
+/// let referent = Referent::new(&mut store, smooth_letter);
+/// let sulky_bone = "lying_fifth".to_owned();
 ///
-/// let c = C::new(&mut store, &referent_ttx, 42.0);
+/// let c = C::new(&mut store, Some(&referent), 42.0);
 ///
-/// let referent_hfx = one_to_many_get_one_tgt_across_r3!(c, store);
-/// assert_eq!(&referent_ttx, referent_hfx);
+/// let referent_eaa = one_to_many_maybe_get_many_tgts_across_r3!(c, store);
+/// assert_eq!(Some(&referent), referent_eaa);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"one_to_many_maybe_get_many_tgts_across_r3-emit_binary_main"}}}
 macro_rules! one_to_many_maybe_get_many_tgts_across_r3 {
@@ -255,40 +228,35 @@ macro_rules! one_to_many_maybe_get_many_tgts_across_r3 {
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-emit_many_unconditional"}}}
     }};
 }
-pub use one_to_many_get_one_tgt_across_r3;
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_one_tgt_across_r3-emit_binary_main"}}}
 pub use one_to_many_maybe_get_many_tgts_across_r3;
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_maybe_get_many_tgts_across_r3-emit_binary_main"}}}
 
-/// Macro to traverse [`Referent`][🦀] ➡ [`C`][🦞], via _R3(c)_
 /// Macro to traverse [`Referent`][🦀] ➡ [`C`][🦞], via _R3_
 ///
-/// This macro expects a &[`Referent`][🦀], and returns an Option<&[`C`][🦞]>.
 /// This macro expects a &[`Referent`][🦀], and returns a &[`C`][🦞].
 ///
 /// Generated by `nut::domain::generate_macros::generate_binary_macro_referent_to_referrer_imp`
 ///
 /// [🦀]: crate::one_to_many::types::Referent
 /// [🦞]: crate::one_to_many::types::C
-// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
 ///
 /// # Example
 ///
 ///```
-/// # use test_models::one_to_many::Referent;
 /// # use test_models::one_to_many::C;
-/// # use test_models::one_to_many_maybe_get_many_cs_across_r3;
+/// # use test_models::one_to_many::Referent;
+/// # use test_models::one_to_many_get_one_c_across_r3;
 /// # let mut store = test_models::one_to_many::ObjectStore::new();
 ///
-/// let full_aftermath = "giant_juice".to_owned();
-/// let referent_hzl = Referent::new(&mut store, full_aftermath);
+/// let fine_scarf = "many_scene".to_owned();
+/// let male_yak = "juvenile_leg".to_owned();
+/// let referent = Referent::new(&mut store, male_yak);
 ///
-/// let c = C::new(&mut store, &referent_hzl, 42.0);
-/// let c_jdz = one_to_many_maybe_get_many_cs_across_r3!(referent_hzl, store);
+/// let c = C::new(&mut store, Some(&referent), 42.0);
 ///
-/// assert!(c_jdz.iter().find(|&x| **x == c).is_some());
+/// let c_gmn = one_to_many_get_one_c_across_r3!(referent, store);
+/// assert_eq!(Some(&c), c_gmn);
 ///```
-// {"magic":"","kind":"IgnoreBlockEnd"}
 #[macro_export]
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"one_to_many_get_one_c_across_r3-emit_binary_main"}}}
 macro_rules! one_to_many_get_one_c_across_r3 {
@@ -303,7 +271,5 @@ macro_rules! one_to_many_get_one_c_across_r3 {
         // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-c-emit_one_unconditional_lookup"}}}
     }};
 }
-pub use one_to_many_maybe_get_many_cs_across_r3;
-// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_maybe_get_many_cs_across_r3-emit_binary_main"}}}
 pub use one_to_many_get_one_c_across_r3;
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"one_to_many_get_one_c_across_r3-emit_binary_main"}}}
@@ -24,7 +24,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -53,25 +52,21 @@ pub struct A {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"a-new_impl"}}}
 impl A {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"a-new_impl"}}} //⚡️
     /// Inter a new A and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
-    /// # use test_models::one_to_many::Referent;
     /// # use test_models::one_to_many::A;
+    /// # use test_models::one_to_many::Referent;
     /// # let mut store = test_models::one_to_many::ObjectStore::new();
     ///
-    /// let opposite_sleep = "obtainable_health".to_owned();
-    /// let referent_yob = Referent::new(&mut store, opposite_sleep);
-    /// let voracious_chess = "cuddly_week".to_owned();
+    /// let toothsome_watch = "voracious_stem".to_owned();
+    /// let referent_yoa = Referent::new(&mut store, toothsome_watch);
+    /// let impolite_horse = "zany_pump".to_owned();
     ///
-    /// let a = A::new(&mut store, &referent_yob, voracious_chess);
+    /// let a = A::new(&mut store, &referent_yoa, impolite_horse);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, ptr: &Referent, name: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{}::", ptr, name,).as_bytes());
         let new = Self {
@@ -87,6 +82,9 @@ impl A {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"a-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"a-impl-extras","id":"07d4f7ff-af18-5db5-bd3e-c8df9078a816"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"a-impl-extras"}}}
+
 /// Connected to TGT via _R2_.
 ///
 /// This is for testing a 1c-M relationship.
@@ -100,41 +98,33 @@ pub struct B {
     /// pub id: `Uuid`,
     ///
     pub id: Uuid,
-    //     /// pub ptr: `Option<Referent>`, //⚡️
     /// pub ptr: `Referent`,
     ///
-    //     pub ptr: Option<Uuid>, //⚡️
     pub ptr: Uuid,
 }
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"b-struct-definition"}}}
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"b-new_impl"}}}
 impl B {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"b-new_impl"}}} //⚡️
     /// Inter a new B and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
-    /// # use test_models::one_to_many::Referent;
     /// # use test_models::one_to_many::B;
+    /// # use test_models::one_to_many::Referent;
     /// # let mut store = test_models::one_to_many::ObjectStore::new();
     ///
-    /// let unequaled_aunt = "oceanic_land".to_owned();
-    /// let referent = Referent::new(&mut store, unequaled_aunt);
-    /// let hapless_balloon = "cowardly_cable".to_owned();
+    /// let childlike_bait = "light_reason".to_owned();
+    /// let referent_wqs = Referent::new(&mut store, childlike_bait);
+    /// let caring_zoo = "dispensable_trade".to_owned();
     ///
-    /// let b = B::new(&mut store, Some(&referent), hapless_balloon);
+    /// let b = B::new(&mut store, &referent_wqs, caring_zoo);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
-    //     pub fn new(store: &mut ObjectStore, ptr: Option<&Referent>, baz: std::string::String) -> Self { //⚡️
     pub fn new(store: &mut ObjectStore, ptr: &Referent, baz: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{}::", ptr, baz,).as_bytes());
         let new = Self {
             id,
-            //             ptr: ptr.map(|o| o.id), //⚡️
             ptr: ptr.id,
             baz,
         };
@@ -146,6 +136,9 @@ impl B {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"b-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"b-impl-extras","id":"db839890-ce6f-51e8-91cb-07d494cb81c7"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"b-impl-extras"}}}
+
 /// This is the [`Referrent`] side of a 1-Mc
 ///
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"c-struct-definition"}}}
@@ -157,40 +150,32 @@ pub struct C {
     /// pub jackpot: `f64`,
     ///
     pub jackpot: f64,
-    //     /// pub ptr: `Referent`, //⚡️
     /// pub ptr: `Option<Referent>`,
     ///
-    //     pub ptr: Uuid, //⚡️
     pub ptr: Option<Uuid>,
 }
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"c-struct-definition"}}}
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"c-new_impl"}}}
 impl C {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"c-new_impl"}}} //⚡️
     /// Inter a new C and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
-    /// # use test_models::one_to_many::Referent;
     /// # use test_models::one_to_many::C;
+    /// # use test_models::one_to_many::Referent;
     /// # let mut store = test_models::one_to_many::ObjectStore::new();
     ///
-    /// let ignorant_frame = "devilish_produce".to_owned();
-    /// let referent_uap = Referent::new(&mut store, ignorant_frame);
+    /// let aboard_cats = "knotty_engine".to_owned();
+    /// let referent = Referent::new(&mut store, aboard_cats);
     ///
-    /// let c = C::new(&mut store, &referent_uap, 42.0);
+    /// let c = C::new(&mut store, Some(&referent), 42.0);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
-    //     pub fn new(store: &mut ObjectStore, ptr: &Referent, jackpot: f64) -> Self { //⚡️
     pub fn new(store: &mut ObjectStore, ptr: Option<&Referent>, jackpot: f64) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{}::", ptr, jackpot,).as_bytes());
         let new = Self {
             id,
-            //             ptr: ptr.id, //⚡️
             ptr: ptr.map(|o| o.id),
             jackpot,
         };
@@ -202,6 +187,9 @@ impl C {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"c-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"c-impl-extras","id":"04f50b98-563e-5268-b6b4-7ea3b18d162b"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"c-impl-extras"}}}
+
 /// The object of so many relationships
 ///
 /// I’m related to stuff.
@@ -220,22 +208,18 @@ pub struct Referent {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"referent-new_impl"}}}
 impl Referent {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"referent-new_impl"}}} //⚡️
     /// Inter a new Referent and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
     /// # use test_models::one_to_many::Referent;
     /// # let mut store = test_models::one_to_many::ObjectStore::new();
     ///
-    /// let warm_riddle = "sore_achieve".to_owned();
+    /// let colossal_cent = "pumped_actor".to_owned();
     ///
-    /// let referent = Referent::new(&mut store, warm_riddle);
+    /// let referent = Referent::new(&mut store, colossal_cent);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
         let new = Self { id, name };
@@ -246,3 +230,6 @@ impl Referent {
     }
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-new_impl"}}}
 }
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"referent-impl-extras","id":"92bc9be3-761f-5b31-a013-79a4d5343870"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"referent-impl-extras"}}}
@@ -24,7 +24,6 @@
 //!  /Users/uberfoo/projects/sarzak/nut/target/debug/deps/generate_test_domain-145fdb9ab1f4b4be --nocapture
 //! ```
 // {"magic":"","kind":"IgnoreBlockEnd"}
-// {"magic":"","version":"0.5.0"}
 // {"magic":"","version":"1.0.0"}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -50,21 +49,16 @@ pub struct A {
     /// pub number: `i64`,
     ///
     pub number: i64,
-    //     /// pub ptr: `Referent`, //⚡️
     /// pub ptr: `Option<Referent>`,
     ///
-    //     pub ptr: Uuid, //⚡️
     pub ptr: Option<Uuid>,
 }
 // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"a-struct-definition"}}}
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"a-new_impl"}}}
 impl A {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"a-new_impl"}}} //⚡️
     /// Inter a new A and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -72,18 +66,15 @@ impl A {
     /// # use test_models::one_to_one::Referent;
     /// # let mut store = test_models::one_to_one::ObjectStore::new();
     ///
-    /// let festive_truck = "racial_meat".to_owned();
-    /// let referent_skb = Referent::new(&mut store, festive_truck);
+    /// let lethal_stick = "dashing_paint".to_owned();
+    /// let referent = Referent::new(&mut store, lethal_stick);
     ///
-    /// let a = A::new(&mut store, &referent_skb, 42);
+    /// let a = A::new(&mut store, Some(&referent), 42);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
-    //     pub fn new(store: &mut ObjectStore, ptr: &Referent, number: i64) -> Self { //⚡️
     pub fn new(store: &mut ObjectStore, ptr: Option<&Referent>, number: i64) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{}::", ptr, number,).as_bytes());
         let new = Self {
             id,
-            //             ptr: ptr.id, //⚡️
             ptr: ptr.map(|o| o.id),
             number,
         };
@@ -95,6 +86,9 @@ impl A {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"a-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"a-impl-extras","id":"91da8f64-33b3-58eb-923f-9adf702bdec3"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"a-impl-extras"}}}
+
 /// B: Referrer Unconditional to Referent
 ///
 /// This is a plain Jayne 😉 1-1 relationship, where this guy is formalizing.
@@ -116,11 +110,8 @@ pub struct B {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"b-new_impl"}}}
 impl B {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"b-new_impl"}}} //⚡️
     /// Inter a new B and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
@@ -128,12 +119,11 @@ impl B {
     /// # use test_models::one_to_one::Referent;
     /// # let mut store = test_models::one_to_one::ObjectStore::new();
     ///
-    /// let agonizing_boat = "complex_dock".to_owned();
-    /// let referent_jbd = Referent::new(&mut store, agonizing_boat);
+    /// let childlike_bait = "light_reason".to_owned();
+    /// let referent_wqs = Referent::new(&mut store, childlike_bait);
     ///
-    /// let b = B::new(&mut store, &referent_jbd, true);
+    /// let b = B::new(&mut store, &referent_wqs, true);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, ptr: &Referent, bit: bool) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{}::", ptr, bit,).as_bytes());
         let new = Self {
@@ -149,6 +139,9 @@ impl B {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"b-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"b-impl-extras","id":"db839890-ce6f-51e8-91cb-07d494cb81c7"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"b-impl-extras"}}}
+
 /// C: Referrer to [`Referent`] Bi-Conditional
 ///
 /// This will be an interesting one to translate. Hopefully not too gnarly.🤘
@@ -170,24 +163,20 @@ pub struct C {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"c-new_impl"}}}
 impl C {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"c-new_impl"}}} //⚡️
     /// Inter a new C and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
-    /// # use test_models::one_to_one::Referent;
     /// # use test_models::one_to_one::C;
+    /// # use test_models::one_to_one::Referent;
     /// # let mut store = test_models::one_to_one::ObjectStore::new();
     ///
-    /// let jaded_earthquake = "damp_gate".to_owned();
-    /// let referent = Referent::new(&mut store, jaded_earthquake);
+    /// let aboard_cats = "knotty_engine".to_owned();
+    /// let referent = Referent::new(&mut store, aboard_cats);
     ///
     /// let c = C::new(&mut store, Some(&referent), 42.0);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, ptr: Option<&Referent>, like_water: f64) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{}::", ptr, like_water,).as_bytes());
         let new = Self {
@@ -203,6 +192,9 @@ impl C {
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"c-new_impl"}}}
 }
 
+// {"magic":"","kind":{"UserRegionBegin":{"name":"c-impl-extras","id":"04f50b98-563e-5268-b6b4-7ea3b18d162b"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"c-impl-extras"}}}
+
 /// The target of our relationship tests.
 ///
 /// It is conditionally related to [`OneToOneConditional`] across _R2_, and it is unconditionally
@@ -222,22 +214,18 @@ pub struct Referent {
 
 // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"referent-new_impl"}}}
 impl Referent {
-    //     // {"magic":"","kind":{"CriticalBlockBegin":{"tag":"referent-new_impl"}}} //⚡️
     /// Inter a new Referent and return it's `id`
     ///
-    //     // {"magic":"","kind":{"IgnoreBlockBegin":{}}} //⚡️
-    // {"magic":"","kind":{"IgnoreBlockBegin":{}}}
     /// # Example
     ///
     ///```
     /// # use test_models::one_to_one::Referent;
     /// # let mut store = test_models::one_to_one::ObjectStore::new();
     ///
-    /// let uptight_body = "purring_rice".to_owned();
+    /// let colossal_cent = "pumped_actor".to_owned();
     ///
-    /// let referent = Referent::new(&mut store, uptight_body);
+    /// let referent = Referent::new(&mut store, colossal_cent);
     ///```
-    // {"magic":"","kind":"IgnoreBlockEnd"}
     pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
         let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
         let new = Self { id, name };
@@ -248,3 +236,6 @@ impl Referent {
     }
     // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"referent-new_impl"}}}
 }
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"referent-impl-extras","id":"92bc9be3-761f-5b31-a013-79a4d5343870"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"referent-impl-extras"}}}
@@ -0,0 +1,19 @@
+//! Zoo Domain
+//!
+//! The purpose of this domain is to test subtypes in more than one hierarchy.
+//! `Animal` is the supertype of two isa relationships, so it's a struct, and
+//! `Cat` is a subtype in three of them.
+//!
+//! It's a text model, so there's no picture. See `models/zoo.nut`.
+use uuid::{uuid, Uuid};
+
+pub mod macros;
+pub mod store;
+pub mod types;
+
+pub use macros::*;
+pub use store::ObjectStore;
+pub use types::*;
+
+// zoo
+pub const UUID_NS: Uuid = uuid!("dcfe93f3-7905-5a99-a86a-b26972395a8e");
@@ -0,0 +1,15 @@
+//! Macros for navigating the "Zoo" domain
+//!
+//! # Generated Code -- edit _with care_.
+//!
+//! Don't mess with anything between `{"magic":"","kind":"CriticalBlockBegin"}`
+//! and `{"magic":"","kind":"CriticalBlockEnd"}`. Otherwise, you should be free
+//! to go wild. Happy hacking!
+//!
+//! Use the following invocation to reproduce:
+// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
+//! ```shell
+//!  /root/crate/target/debug/deps/generate_test_domain-fb40ed7c03f3d8f9 --nocapture
+//! ```
+// {"magic":"","kind":"IgnoreBlockEnd"}
+// {"magic":"","version":"1.0.0"}
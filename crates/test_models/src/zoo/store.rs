@@ -0,0 +1,172 @@
+//! ObjectStore for the instances of the "Zoo" domain
+//!
+//! An end user should have little need to use this directly.
+//!
+//! This store contains the following instances:
+//!    * [`Wild`]
+//!    * [`Pet`]
+//!    * [`Cat`]
+//!    * [`Animal`]
+//!    * [`Dog`]
+//!    * [`Companion`]
+//!
+//! # Generated Code -- edit _with care_.
+//!
+//! Don't mess with anything between `{"magic":"","kind":"CriticalBlockBegin"}`
+//! and `{"magic":"","kind":"CriticalBlockEnd"}`. Otherwise, you should be free
+//! to go wild. Happy hacking!
+//!
+//! Use the following invocation to reproduce:
+// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
+//! ```shell
+//!  /root/crate/target/debug/deps/generate_test_domain-fb40ed7c03f3d8f9 --nocapture
+//! ```
+// {"magic":"","kind":"IgnoreBlockEnd"}
+// {"magic":"","version":"1.0.0"}
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::zoo::types::{Animal, Cat, Companion, Dog, Pet, Wild};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ObjectStore {
+    wild: HashMap<Uuid, Wild>,
+    pet: HashMap<Uuid, Pet>,
+    cat: HashMap<Uuid, Cat>,
+    animal: HashMap<Uuid, Animal>,
+    dog: HashMap<Uuid, Dog>,
+    companion: HashMap<Uuid, Companion>,
+}
+
+impl ObjectStore {
+    pub fn new() -> Self {
+        Self {
+            wild: HashMap::new(),
+            pet: HashMap::new(),
+            cat: HashMap::new(),
+            animal: HashMap::new(),
+            dog: HashMap::new(),
+            companion: HashMap::new(),
+        }
+    }
+
+    /// Inter [`Wild`] into the [`ObjectStore`]
+    ///
+    pub fn inter_wild(&mut self, wild: Wild) {
+        self.wild.insert(wild.id, wild);
+    }
+
+    /// Exhume [`Wild`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_wild(&self, id: &Uuid) -> Option<&Wild> {
+        self.wild.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Wild)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_wild(&self) -> impl Iterator<Item = (&Uuid, &Wild)> {
+        self.wild.iter()
+    }
+
+    /// Inter [`Pet`] into the [`ObjectStore`]
+    ///
+    pub fn inter_pet(&mut self, pet: Pet) {
+        self.pet.insert(pet.id, pet);
+    }
+
+    /// Exhume [`Pet`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_pet(&self, id: &Uuid) -> Option<&Pet> {
+        self.pet.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Pet)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_pet(&self) -> impl Iterator<Item = (&Uuid, &Pet)> {
+        self.pet.iter()
+    }
+
+    /// Inter [`Cat`] into the [`ObjectStore`]
+    ///
+    pub fn inter_cat(&mut self, cat: Cat) {
+        self.cat.insert(cat.id, cat);
+    }
+
+    /// Exhume [`Cat`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_cat(&self, id: &Uuid) -> Option<&Cat> {
+        self.cat.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Cat)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_cat(&self) -> impl Iterator<Item = (&Uuid, &Cat)> {
+        self.cat.iter()
+    }
+
+    /// Inter [`Animal`] into the [`ObjectStore`]
+    ///
+    pub fn inter_animal(&mut self, animal: Animal) {
+        self.animal.insert(animal.get_id(), animal);
+    }
+
+    /// Exhume [`Animal`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_animal(&self, id: &Uuid) -> Option<&Animal> {
+        self.animal.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Animal)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_animal(&self) -> impl Iterator<Item = (&Uuid, &Animal)> {
+        self.animal.iter()
+    }
+
+    /// Exhume the [`Animal`] that a subtype is part of
+    ///
+    /// `id` is the id of a subtype, in any of it's hierarchies.
+    ///
+    pub fn exhume_animal_by_subtype(&self, id: &Uuid) -> Option<&Animal> {
+        self.animal
+            .values()
+            .find(|animal| animal.r1.get_id() == *id || animal.r2.get_id() == *id)
+    }
+
+    /// Inter [`Dog`] into the [`ObjectStore`]
+    ///
+    pub fn inter_dog(&mut self, dog: Dog) {
+        self.dog.insert(dog.id, dog);
+    }
+
+    /// Exhume [`Dog`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_dog(&self, id: &Uuid) -> Option<&Dog> {
+        self.dog.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Dog)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_dog(&self) -> impl Iterator<Item = (&Uuid, &Dog)> {
+        self.dog.iter()
+    }
+
+    /// Inter [`Companion`] into the [`ObjectStore`]
+    ///
+    pub fn inter_companion(&mut self, companion: Companion) {
+        self.companion.insert(companion.get_id(), companion);
+    }
+
+    /// Exhume [`Companion`] from the [`ObjectStore`]
+    ///
+    pub fn exhume_companion(&self, id: &Uuid) -> Option<&Companion> {
+        self.companion.get(id)
+    }
+
+    /// Get an iterator over the internal `HashMap<(&Uuid, Companion)>` in the [`ObjectStore`]
+    ///
+    pub fn iter_companion(&self) -> impl Iterator<Item = (&Uuid, &Companion)> {
+        self.companion.iter()
+    }
+}
@@ -0,0 +1,597 @@
+//! Types for instances of the "Zoo" domain
+//! # Domain Description
+//!
+//!
+//! # Contents
+//!
+//! The following types are defined herein:
+//!    * [`Animal`]
+//!    * [`AnimalR1`]
+//!    * [`AnimalR2`]
+//!    * [`Cat`]
+//!    * [`Companion`]
+//!    * [`Dog`]
+//!    * [`Pet`]
+//!    * [`Wild`]
+//!
+//! # Generated Code -- edit _with care_.
+//!
+//! Don't mess with anything between `{"magic":"","kind":"CriticalBlockBegin"}`
+//! and `{"magic":"","kind":"CriticalBlockEnd"}`. Otherwise, you should be free
+//! to go wild. Happy hacking!
+//!
+//! Use the following invocation to reproduce:
+// {"magic":"","kind":{"IgnoreBlockBegin":{}}}
+//! ```shell
+//!  /root/crate/target/debug/deps/generate_test_domain-fb40ed7c03f3d8f9 --nocapture
+//! ```
+// {"magic":"","kind":"IgnoreBlockEnd"}
+// {"magic":"","version":"1.0.0"}
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"imports"}}}
+use crate::zoo::store::ObjectStore;
+use crate::zoo::UUID_NS;
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"imports"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal-struct-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Animal {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub r1: [`AnimalR1`],
+    ///
+    pub r1: AnimalR1,
+    /// pub r2: [`AnimalR2`],
+    ///
+    pub r2: AnimalR2,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal-new_impl"}}}
+impl Animal {
+    /// Inter a new Animal and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::Animal;
+    /// # use test_models::zoo::AnimalR1;
+    /// # use test_models::zoo::AnimalR2;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let r1 = AnimalR1::test_default(&mut store);
+    /// let r2 = AnimalR2::test_default(&mut store);
+    /// let animal = Animal::new(&mut store, r1, r2);
+    ///
+    /// assert_eq!(store.exhume_animal_by_subtype(&animal.r1.get_id()), Some(&animal));
+    /// assert_eq!(store.exhume_animal_by_subtype(&animal.r2.get_id()), Some(&animal));
+    ///```
+    pub fn new(store: &mut ObjectStore, r1: AnimalR1, r2: AnimalR2) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{:?}::{:?}::", r1, r2).as_bytes());
+        let new = Self { id, r1, r2 };
+
+        store.inter_animal(new.clone());
+
+        new
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal-new_impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal-struct-get-id-impl"}}}
+impl Animal {
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal-struct-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal-test_default"}}}
+impl Animal {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let r1 = AnimalR1::test_default(store);
+        let r2 = AnimalR2::test_default(store);
+
+        Self::new(store, r1, r2)
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal-test_default"}}}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"animal-impl-extras","id":"a85c0c72-35e1-54f9-828b-d8a34503b25c"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"animal-impl-extras"}}}
+
+/// The subtypes of [`Animal`] across R1
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r1-enum-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AnimalR1 {
+    /// `Cat(Cat)`,
+    ///
+    Cat(Uuid),
+    /// `Dog(Dog)`,
+    ///
+    Dog(Uuid),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r1-enum-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r1-enum-get-id-impl"}}}
+impl AnimalR1 {
+    pub fn get_id(&self) -> Uuid {
+        match *self {
+            Self::Cat(z) => z,
+            Self::Dog(z) => z,
+        }
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r1-enum-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r1-test_default"}}}
+impl AnimalR1 {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let clever_paint = "tranquil_title".to_owned();
+        let test = Self::Cat(Cat::new(store, clever_paint).id);
+
+        test
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r1-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r1-conversions"}}}
+impl AnimalR1 {
+    /// Exhume the [`Cat`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::AnimalR1;
+    /// # use test_models::zoo::Cat;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let clever_paint = "tranquil_title".to_owned();
+    /// let cat = Cat::new(&mut store, clever_paint);
+    /// let animal_r1 = AnimalR1::from(&cat);
+    ///
+    /// assert_eq!(animal_r1.as_cat(&store), Some(&cat));
+    /// let same: &Cat = (&animal_r1, &store).try_into().unwrap();
+    /// assert_eq!(same, &cat);
+    ///```
+    pub fn as_cat<'a>(&self, store: &'a ObjectStore) -> Option<&'a Cat> {
+        match self {
+            Self::Cat(id) => store.exhume_cat(id),
+            _ => None,
+        }
+    }
+
+    /// Exhume the [`Dog`], if that's what this is
+    ///
+    pub fn as_dog<'a>(&self, store: &'a ObjectStore) -> Option<&'a Dog> {
+        match self {
+            Self::Dog(id) => store.exhume_dog(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Cat> for AnimalR1 {
+    fn from(cat: &Cat) -> Self {
+        Self::Cat(cat.id)
+    }
+}
+
+impl<'a> TryFrom<(&AnimalR1, &'a ObjectStore)> for &'a Cat {
+    type Error = AnimalR1;
+
+    fn try_from((animal_r1, store): (&AnimalR1, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        animal_r1.as_cat(store).ok_or_else(|| animal_r1.clone())
+    }
+}
+
+impl From<&Dog> for AnimalR1 {
+    fn from(dog: &Dog) -> Self {
+        Self::Dog(dog.id)
+    }
+}
+
+impl<'a> TryFrom<(&AnimalR1, &'a ObjectStore)> for &'a Dog {
+    type Error = AnimalR1;
+
+    fn try_from((animal_r1, store): (&AnimalR1, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        animal_r1.as_dog(store).ok_or_else(|| animal_r1.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r1-conversions"}}}
+
+/// The subtypes of [`Animal`] across R2
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r2-enum-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AnimalR2 {
+    /// `Wild(Wild)`,
+    ///
+    Wild(Uuid),
+    /// `Pet(Pet)`,
+    ///
+    Pet(Uuid),
+    /// `Cat(Cat)`,
+    ///
+    Cat(Uuid),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r2-enum-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r2-enum-get-id-impl"}}}
+impl AnimalR2 {
+    pub fn get_id(&self) -> Uuid {
+        match *self {
+            Self::Wild(z) => z,
+            Self::Pet(z) => z,
+            Self::Cat(z) => z,
+        }
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r2-enum-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r2-test_default"}}}
+impl AnimalR2 {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let jumpy_yarn = "ashamed_lake".to_owned();
+        let test = Self::Wild(Wild::new(store, jumpy_yarn).id);
+
+        test
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r2-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"animal_r2-conversions"}}}
+impl AnimalR2 {
+    /// Exhume the [`Wild`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::AnimalR2;
+    /// # use test_models::zoo::Wild;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let jumpy_yarn = "ashamed_lake".to_owned();
+    /// let wild = Wild::new(&mut store, jumpy_yarn);
+    /// let animal_r2 = AnimalR2::from(&wild);
+    ///
+    /// assert_eq!(animal_r2.as_wild(&store), Some(&wild));
+    /// let same: &Wild = (&animal_r2, &store).try_into().unwrap();
+    /// assert_eq!(same, &wild);
+    ///```
+    pub fn as_wild<'a>(&self, store: &'a ObjectStore) -> Option<&'a Wild> {
+        match self {
+            Self::Wild(id) => store.exhume_wild(id),
+            _ => None,
+        }
+    }
+
+    /// Exhume the [`Pet`], if that's what this is
+    ///
+    pub fn as_pet<'a>(&self, store: &'a ObjectStore) -> Option<&'a Pet> {
+        match self {
+            Self::Pet(id) => store.exhume_pet(id),
+            _ => None,
+        }
+    }
+
+    /// Exhume the [`Cat`], if that's what this is
+    ///
+    pub fn as_cat<'a>(&self, store: &'a ObjectStore) -> Option<&'a Cat> {
+        match self {
+            Self::Cat(id) => store.exhume_cat(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Wild> for AnimalR2 {
+    fn from(wild: &Wild) -> Self {
+        Self::Wild(wild.id)
+    }
+}
+
+impl<'a> TryFrom<(&AnimalR2, &'a ObjectStore)> for &'a Wild {
+    type Error = AnimalR2;
+
+    fn try_from((animal_r2, store): (&AnimalR2, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        animal_r2.as_wild(store).ok_or_else(|| animal_r2.clone())
+    }
+}
+
+impl From<&Pet> for AnimalR2 {
+    fn from(pet: &Pet) -> Self {
+        Self::Pet(pet.id)
+    }
+}
+
+impl<'a> TryFrom<(&AnimalR2, &'a ObjectStore)> for &'a Pet {
+    type Error = AnimalR2;
+
+    fn try_from((animal_r2, store): (&AnimalR2, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        animal_r2.as_pet(store).ok_or_else(|| animal_r2.clone())
+    }
+}
+
+impl From<&Cat> for AnimalR2 {
+    fn from(cat: &Cat) -> Self {
+        Self::Cat(cat.id)
+    }
+}
+
+impl<'a> TryFrom<(&AnimalR2, &'a ObjectStore)> for &'a Cat {
+    type Error = AnimalR2;
+
+    fn try_from((animal_r2, store): (&AnimalR2, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        animal_r2.as_cat(store).ok_or_else(|| animal_r2.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"animal_r2-conversions"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"cat-struct-definition"}}}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Cat {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub name: `std::string::String`,
+    ///
+    pub name: std::string::String,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"cat-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"cat-new_impl"}}}
+impl Cat {
+    /// Inter a new Cat and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::Cat;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let foregoing_water = "absent_grip".to_owned();
+    ///
+    /// let cat = Cat::new(&mut store, foregoing_water);
+    ///```
+    pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
+        let new = Self { id, name };
+
+        store.inter_cat(new.clone());
+
+        new
+    }
+    // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"cat-new_impl"}}}
+}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"cat-impl-extras","id":"302da172-cfe7-5097-80e2-7aa9abdb6be6"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"cat-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"companion-enum-definition"}}}
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Companion {
+    /// `Cat(Cat)`,
+    ///
+    Cat(Uuid),
+    /// `Dog(Dog)`,
+    ///
+    Dog(Uuid),
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"companion-enum-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"companion-enum-get-id-impl"}}}
+impl Companion {
+    pub fn get_id(&self) -> Uuid {
+        match *self {
+            Self::Cat(z) => z,
+            Self::Dog(z) => z,
+        }
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"companion-enum-get-id-impl"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"companion-test_default"}}}
+impl Companion {
+    pub fn test_default(store: &mut ObjectStore) -> Self {
+        let fretful_air = "needy_ticket".to_owned();
+        let test = Self::Cat(Cat::new(store, fretful_air).id);
+
+        store.inter_companion(test.clone());
+
+        test
+    }
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"companion-test_default"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"companion-conversions"}}}
+impl Companion {
+    /// Exhume the [`Cat`], if that's what this is
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::Cat;
+    /// # use test_models::zoo::Companion;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let fretful_air = "needy_ticket".to_owned();
+    /// let cat = Cat::new(&mut store, fretful_air);
+    /// let companion = Companion::from(&cat);
+    ///
+    /// assert_eq!(companion.as_cat(&store), Some(&cat));
+    /// let same: &Cat = (&companion, &store).try_into().unwrap();
+    /// assert_eq!(same, &cat);
+    ///```
+    pub fn as_cat<'a>(&self, store: &'a ObjectStore) -> Option<&'a Cat> {
+        match self {
+            Self::Cat(id) => store.exhume_cat(id),
+            _ => None,
+        }
+    }
+
+    /// Exhume the [`Dog`], if that's what this is
+    ///
+    pub fn as_dog<'a>(&self, store: &'a ObjectStore) -> Option<&'a Dog> {
+        match self {
+            Self::Dog(id) => store.exhume_dog(id),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Cat> for Companion {
+    fn from(cat: &Cat) -> Self {
+        Self::Cat(cat.id)
+    }
+}
+
+impl<'a> TryFrom<(&Companion, &'a ObjectStore)> for &'a Cat {
+    type Error = Companion;
+
+    fn try_from((companion, store): (&Companion, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        companion.as_cat(store).ok_or_else(|| companion.clone())
+    }
+}
+
+impl From<&Dog> for Companion {
+    fn from(dog: &Dog) -> Self {
+        Self::Dog(dog.id)
+    }
+}
+
+impl<'a> TryFrom<(&Companion, &'a ObjectStore)> for &'a Dog {
+    type Error = Companion;
+
+    fn try_from((companion, store): (&Companion, &'a ObjectStore)) -> Result<Self, Self::Error> {
+        companion.as_dog(store).ok_or_else(|| companion.clone())
+    }
+}
+
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"companion-conversions"}}}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"companion-impl-extras","id":"f63965ec-2e1a-5115-b226-b67527ce82f4"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"companion-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"dog-struct-definition"}}}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Dog {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub name: `std::string::String`,
+    ///
+    pub name: std::string::String,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"dog-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"dog-new_impl"}}}
+impl Dog {
+    /// Inter a new Dog and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::Dog;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let penitent_crack = "magenta_metal".to_owned();
+    ///
+    /// let dog = Dog::new(&mut store, penitent_crack);
+    ///```
+    pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{}::", name,).as_bytes());
+        let new = Self { id, name };
+
+        store.inter_dog(new.clone());
+
+        new
+    }
+    // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"dog-new_impl"}}}
+}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"dog-impl-extras","id":"e1be2add-a39d-532e-bcb2-c4459717ffe7"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"dog-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"pet-struct-definition"}}}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Pet {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub owner: `std::string::String`,
+    ///
+    pub owner: std::string::String,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"pet-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"pet-new_impl"}}}
+impl Pet {
+    /// Inter a new Pet and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::Pet;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let best_lift = "festive_error".to_owned();
+    ///
+    /// let pet = Pet::new(&mut store, best_lift);
+    ///```
+    pub fn new(store: &mut ObjectStore, owner: std::string::String) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{}::", owner,).as_bytes());
+        let new = Self { id, owner };
+
+        store.inter_pet(new.clone());
+
+        new
+    }
+    // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"pet-new_impl"}}}
+}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"pet-impl-extras","id":"2df463ad-2342-53e8-ba03-642443706d15"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"pet-impl-extras"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"wild-struct-definition"}}}
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Wild {
+    /// pub id: `Uuid`,
+    ///
+    pub id: Uuid,
+    /// pub range: `std::string::String`,
+    ///
+    pub range: std::string::String,
+}
+// {"magic":"","kind":{"CriticalBlockEnd":{"tag":"wild-struct-definition"}}}
+
+// {"magic":"","kind":{"CriticalBlockBegin":{"tag":"wild-new_impl"}}}
+impl Wild {
+    /// Inter a new Wild and return it's `id`
+    ///
+    /// # Example
+    ///
+    ///```
+    /// # use test_models::zoo::Wild;
+    /// # let mut store = test_models::zoo::ObjectStore::new();
+    ///
+    /// let frail_daughter = "bouncy_fireman".to_owned();
+    ///
+    /// let wild = Wild::new(&mut store, frail_daughter);
+    ///```
+    pub fn new(store: &mut ObjectStore, range: std::string::String) -> Self {
+        let id = Uuid::new_v5(&UUID_NS, format!("{}::", range,).as_bytes());
+        let new = Self { id, range };
+
+        store.inter_wild(new.clone());
+
+        new
+    }
+    // {"magic":"","kind":{"CriticalBlockEnd":{"tag":"wild-new_impl"}}}
+}
+
+// {"magic":"","kind":{"UserRegionBegin":{"name":"wild-impl-extras","id":"0967163f-4c21-5682-aeb7-e2531f64deaf"}}}
+// {"magic":"","kind":{"UserRegionEnd":{"name":"wild-impl-extras"}}}
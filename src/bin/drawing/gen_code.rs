@@ -32,7 +32,7 @@
 //! to somehow use this DB representation, outside of code generation.
 use std::{env, path::PathBuf};
 
-use clap::{command, value_parser, Arg};
+use clap::{command, value_parser, Arg, ArgAction};
 use uuid::Uuid;
 
 use nut::codegen::{emitln, get_referent, get_subtypes, Context, Result, SarzakModel};
@@ -53,6 +53,12 @@ fn main() -> Result<()> {
                 .help("generated code file")
                 .value_parser(value_parser!(PathBuf)),
         )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .help("don't write anything, fail if the generated code is out of date")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
     let model_path = args.get_one::<PathBuf>("input_model").unwrap();
@@ -267,5 +273,7 @@ fn main() -> Result<()> {
     });
 
     context.writeln("include!(\"drawing_impls.rs\");");
-    context.commit()
+    context.finish(args.get_flag("check"))?;
+
+    Ok(())
 }
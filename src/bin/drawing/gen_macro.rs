@@ -3,10 +3,10 @@
 //! These are the macros that provide relationship navigation
 use std::{env, path::PathBuf};
 
-use clap::{command, value_parser, Arg};
+use clap::{command, value_parser, Arg, ArgAction};
 
 use nut::codegen::{Result, SarzakModel};
-use nut::domain::generate_macros;
+use nut::domain::{generate_macros, GenerateOptions};
 
 fn main() -> Result<()> {
     let args = command!()
@@ -22,12 +22,27 @@ fn main() -> Result<()> {
                 .help("generated code file")
                 .value_parser(value_parser!(PathBuf)),
         )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .help("don't write anything, fail if the generated code is out of date")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
     let model_path = args.get_one::<PathBuf>("input_model").unwrap();
     let output_path = args.get_one::<PathBuf>("output_file").unwrap();
+    let check = args.get_flag("check");
 
     let model = SarzakModel::load_cuckoo_model(&model_path).unwrap();
 
-    generate_macros(&model, &output_path, "", false, true, false)
+    let options = GenerateOptions {
+        doc_tests: true,
+        check,
+        ..Default::default()
+    };
+
+    generate_macros(&model, output_path, "", &options)?;
+
+    Ok(())
 }
@@ -3,10 +3,10 @@
 //! This is where all of the instances from the domain model reside.
 use std::{env, path::PathBuf};
 
-use clap::{command, value_parser, Arg};
+use clap::{command, value_parser, Arg, ArgAction};
 
 use nut::codegen::{Result, SarzakModel};
-use nut::domain::generate_store;
+use nut::domain::{generate_store, GenerateOptions};
 
 fn main() -> Result<()> {
     let args = command!()
@@ -22,12 +22,27 @@ fn main() -> Result<()> {
                 .help("generated code file")
                 .value_parser(value_parser!(PathBuf)),
         )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .help("don't write anything, fail if the generated code is out of date")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
     let model_path = args.get_one::<PathBuf>("input_model").unwrap();
     let output_path = args.get_one::<PathBuf>("output_file").unwrap();
+    let check = args.get_flag("check");
 
     let model = SarzakModel::load_cuckoo_model(&model_path).unwrap();
 
-    generate_store(&model, &output_path, "", false, true)
+    let options = GenerateOptions {
+        doc_tests: true,
+        check,
+        ..Default::default()
+    };
+
+    generate_store(&model, output_path, "", &options)?;
+
+    Ok(())
 }
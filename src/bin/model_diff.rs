@@ -0,0 +1,53 @@
+//! Compare two models
+//!
+//! Reads a pair of Cuckoo model files and prints what changed between them, by
+//! name, rather than by UUID. Text for people, JSON for machines.
+use std::path::PathBuf;
+
+use clap::Parser;
+use snafu::{prelude::*, Whatever};
+
+use nut::codegen::SarzakModel;
+use nut::sarzak::diff_models;
+
+#[derive(Parser)]
+#[command(author, version, about)]
+#[command(propagate_version = true)]
+struct Args {
+    /// Old Model
+    ///
+    /// The Cuckoo model file from before the change.
+    old: PathBuf,
+
+    /// New Model
+    ///
+    /// The Cuckoo model file from after the change.
+    new: PathBuf,
+
+    /// Output JSON
+    ///
+    /// Print the changes as JSON instead of text.
+    #[clap(long)]
+    json: bool,
+}
+
+pub fn main() -> Result<(), Whatever> {
+    let args = Args::parse();
+
+    let old = SarzakModel::load_cuckoo_model(&args.old)
+        .with_whatever_context(|_| format!("can't load {}", args.old.display()))?;
+    let new = SarzakModel::load_cuckoo_model(&args.new)
+        .with_whatever_context(|_| format!("can't load {}", args.new.display()))?;
+
+    let diff = diff_models(&old, &new);
+
+    if args.json {
+        let json =
+            serde_json::to_string_pretty(&diff).whatever_context("can't serialize changes")?;
+        println!("{}", json);
+    } else {
+        print!("{}", diff);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Draw a model
+//!
+//! Reads a Cuckoo model file and writes a class diagram of it, as Graphviz DOT,
+//! Mermaid, or PlantUML. Or as an SVG, drawn just the way it's laid out in
+//! Cuckoo. Something like this will get you a picture:
+//!
+//! ```ignore
+//! model_export models/cat_dog.json --format dot | dot -Tsvg > cat_dog.svg
+//! ```
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use snafu::{prelude::*, Whatever};
+
+use nut::codegen::SarzakModel;
+use nut::sarzak::{export_model, Format};
+
+#[derive(Parser)]
+#[command(author, version, about)]
+#[command(propagate_version = true)]
+struct Args {
+    /// Model
+    ///
+    /// The Cuckoo model file to draw.
+    model: PathBuf,
+
+    /// Diagram Format
+    ///
+    /// One of dot, mermaid, plantuml, or svg.
+    #[clap(long, short, default_value = "mermaid")]
+    format: Format,
+
+    /// Output File
+    ///
+    /// Where to write the diagram. It goes to stdout if this is missing.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+pub fn main() -> Result<(), Whatever> {
+    let args = Args::parse();
+
+    let model = SarzakModel::load_cuckoo_model(&args.model)
+        .with_whatever_context(|_| format!("can't load {}", args.model.display()))?;
+
+    let diagram = export_model(&model, args.format);
+
+    match args.output {
+        Some(path) => fs::write(&path, diagram)
+            .with_whatever_context(|_| format!("can't write {}", path.display()))?,
+        None => print!("{}", diagram),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Three-way merge of Cuckoo models
+//!
+//! Give it the common ancestor, our version and their version, and it writes a
+//! merged model. Conflicts are reported, and our side wins in the output, so the
+//! result always loads. The exit status is non-zero if there were any, which makes
+//! this usable as a git merge driver:
+//!
+//! ```text
+//! [merge "cuckoo"]
+//!     driver = model_merge %O %A %B --output %A
+//! ```
+use std::{fs::File, path::PathBuf, process};
+
+use clap::Parser;
+use snafu::{prelude::*, Whatever};
+
+use nut::model::{merge_models, ReadModel, WriteModel};
+
+#[derive(Parser)]
+#[command(author, version, about)]
+#[command(propagate_version = true)]
+struct Args {
+    /// Base Model
+    ///
+    /// The common ancestor of the other two.
+    base: PathBuf,
+
+    /// Our Model
+    ///
+    ours: PathBuf,
+
+    /// Their Model
+    ///
+    theirs: PathBuf,
+
+    /// Output File
+    ///
+    /// Where the merged model goes.
+    #[clap(long, short)]
+    output: PathBuf,
+
+    /// Output JSON
+    ///
+    /// Report conflicts as JSON, on stdout.
+    #[clap(long)]
+    json: bool,
+}
+
+fn read(path: &PathBuf) -> Result<nut::model::JSFormat, Whatever> {
+    File::open(path)
+        .with_whatever_context(|_| format!("can't open {}", path.display()))?
+        .from_json()
+        .with_whatever_context(|_| format!("can't parse {}", path.display()))
+}
+
+pub fn main() -> Result<(), Whatever> {
+    let args = Args::parse();
+
+    let base = read(&args.base)?;
+    let ours = read(&args.ours)?;
+    let theirs = read(&args.theirs)?;
+
+    let merge = merge_models(&base, &ours, &theirs).whatever_context("merge failed")?;
+
+    File::create(&args.output)
+        .with_whatever_context(|_| format!("can't create {}", args.output.display()))?
+        .to_json(&merge.merged)
+        .whatever_context("can't write merged model")?;
+
+    if args.json {
+        let json = serde_json::json!({
+            "conflicts": merge.conflicts,
+            "diagnostics": merge.diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+        });
+        println!("{}", json);
+    } else {
+        for conflict in &merge.conflicts {
+            eprintln!("💥 conflict: {}", conflict);
+        }
+        for diagnostic in &merge.diagnostics {
+            eprintln!("⚠️  {}", diagnostic);
+        }
+    }
+
+    if !merge.conflicts.is_empty() {
+        process::exit(1);
+    }
+
+    Ok(())
+}
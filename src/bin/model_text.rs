@@ -0,0 +1,73 @@
+//! Models as Text
+//!
+//! Print a Cuckoo model in the text format, or tidy up a text model by reading it
+//! in and printing it back out. Files ending in `.json` are taken to be Cuckoo
+//! files, and everything else is text.
+//!
+//! It goes the other way too. If the output ends in `.json`, you get a Cuckoo
+//! file, ready to be opened in the tool.
+//!
+//! ```ignore
+//! model_text models/cat_dog.json -o models/cat_dog.nut
+//! model_text models/cat_dog.nut -o models/cat_dog.json
+//! ```
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use snafu::{prelude::*, Whatever};
+
+use nut::codegen::SarzakModel;
+use nut::model::to_text;
+
+#[derive(Parser)]
+#[command(author, version, about)]
+#[command(propagate_version = true)]
+struct Args {
+    /// Model
+    ///
+    /// A Cuckoo model file, or a text model.
+    model: PathBuf,
+
+    /// Output File
+    ///
+    /// Where to write the text. It goes to stdout if this is missing. If it ends
+    /// in `.json`, a Cuckoo model is written instead.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+pub fn main() -> Result<(), Whatever> {
+    let args = Args::parse();
+
+    let model = if args.model.extension().is_some_and(|e| e == "json") {
+        SarzakModel::load_cuckoo_model(&args.model)
+    } else {
+        SarzakModel::load_text_model(&args.model)
+    };
+    // The text model errors are only useful if you can see them.
+    let model = match model {
+        Ok(model) => model,
+        Err(e) => whatever!("{}", e),
+    };
+
+    if let Some(path) = args
+        .output
+        .as_ref()
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+    {
+        if let Err(e) = model.save_cuckoo_model(path) {
+            whatever!("{}", e);
+        }
+        return Ok(());
+    }
+
+    let text = to_text(&model);
+
+    match args.output {
+        Some(path) => fs::write(&path, text)
+            .with_whatever_context(|_| format!("can't write {}", path.display()))?,
+        None => print!("{}", text),
+    }
+
+    Ok(())
+}
@@ -2,9 +2,9 @@ use std::{env, fs::File, path::PathBuf};
 
 use clap::{command, value_parser, Arg};
 
-use nut::{ReadCuckooModel, WriteSchema_v0};
+use nut::{ReadCuckooModel, Schema_v0, WriteSchema_v0};
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = command!()
         .arg(
             Arg::new("input_model")
@@ -24,7 +24,7 @@ fn main() -> std::io::Result<()> {
     let path = args.get_one::<PathBuf>("input_model").unwrap();
     let ooa = File::open(path)?.from_json()?;
 
-    let model = ooa.into();
+    let model: Schema_v0 = ooa.try_into()?;
 
     let path = args.get_one::<PathBuf>("output_schema").unwrap();
     File::create(path)?.to_json(&model)?;
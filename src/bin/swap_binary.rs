@@ -11,7 +11,7 @@ use nut::{
     Relationship_v0,
 };
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = command!()
         .arg(
             Arg::new("input_model")
@@ -6,17 +6,25 @@
 use std::path::PathBuf;
 
 use snafu::prelude::*;
+use uuid::Uuid;
 
 pub mod config;
 pub mod context;
+pub mod format;
 #[macro_use]
 pub mod macros;
+pub mod source_map;
+pub mod symbol_table;
 pub mod template;
 pub mod types;
 
-pub use config::{Config, ConfigValue, ImportedObject, SingletonObject};
-pub use context::{CachingContext, Context, Symbol};
-pub use types::{Field, Ref};
+pub use config::{Config, ConfigValue, IdentityPolicy, ImportedObject, SingletonObject};
+pub use context::{CachingContext, Context, ContextGroup, Displaced, Displacement};
+pub use format::Formatter;
+pub use source_map::{sidecar_path, Construct, Element, ElementKind, Mapped, SourceMap};
+pub use symbol_table::{Symbol, SymbolTable};
+pub use template::example::ExampleValues;
+pub use types::{Field, Ref, Variant};
 
 // Macro re-exports
 pub use begin_crit;
@@ -28,11 +36,13 @@ pub use function;
 
 // Stuff from the Sarzak Domain
 pub use crate::ooa_2::{
-    get_referent, get_referrer, get_subtypes, get_supertype, sarzak_maybe_get_one_r_sub_across_r15,
-    sarzak_maybe_get_one_r_sup_across_r14, Extrude, ObjectStore as SarzakObjectStore,
-    WriteObjectStore,
+    get_assoc_from, get_assoc_one, get_assoc_other, get_referent, get_referrer, get_subtypes,
+    get_supertype, sarzak_maybe_get_one_r_sub_across_r15, sarzak_maybe_get_one_r_sup_across_r14,
+    Extrude, ObjectStore as SarzakObjectStore, WriteObjectStore,
+};
+pub use crate::sarzak::{
+    validate::Diagnostic, ReadSarzakModel, SarzakModel, WriteSarzakModel, VERSION,
 };
-pub use crate::sarzak::{ReadSarzakModel, SarzakModel, WriteSarzakModel, VERSION};
 
 // Stuff from the Drawing Domain
 pub use crate::drawing::{ObjectStore as DrawingObjectStore, UUID_NS as DRAWING_UUID_NS};
@@ -48,7 +58,10 @@ pub(crate) use template::{
         emit_many_conditional_lookup, emit_many_unconditional, emit_one_conditional,
         emit_one_conditional_lookup, emit_one_unconditional, emit_one_unconditional_lookup,
     },
-    types::{emit_enum_main, emit_object_comments, emit_singleton, emit_struct_main},
+    types::{
+        emit_duplicate_identifier, emit_enum_main, emit_enumeration, emit_generalization,
+        emit_object_comments, emit_singleton, emit_struct_main,
+    },
 };
 
 #[derive(Debug, Snafu)]
@@ -84,8 +97,156 @@ pub enum CodeGenError {
     Badness { source: std::io::Error },
     #[snafu(display("😱 serialization error -- somebody really hates us"))]
     SerdeJsonBombed { source: serde_json::Error },
-    #[snafu(display("😱 unable to spawn rustfmt process"))]
-    SpawnRustfmt { source: std::io::Error },
-    #[snafu(display("😱 rustfmt error: {:?}", exit_code))]
-    RustFmt { exit_code: Option<i32> },
+    #[snafu(display("😱 unable to spawn formatter `{}`", program))]
+    SpawnFormatter {
+        program: String,
+        source: std::io::Error,
+    },
+    #[snafu(display(
+        "😱 {} couldn't format the generated code: {}\n{}",
+        formatter,
+        message,
+        snippet
+    ))]
+    Format {
+        formatter: String,
+        message: String,
+        line: Option<usize>,
+        snippet: String,
+    },
+    #[snafu(display("😱 `{}` isn't a formatter", spec))]
+    BadFormatter { spec: String },
+    #[snafu(display(
+        "😱 invalid model {}:\n{}",
+        domain,
+        diagnostics
+            .iter()
+            .map(|d| format!("  {}", d))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))]
+    InvalidModel {
+        domain: String,
+        diagnostics: Vec<Diagnostic>,
+    },
+    #[snafu(display("😱 unable to load cuckoo model {}: {}", path.display(), source))]
+    BadCuckooModel { path: PathBuf, source: Box<Error> },
+    #[snafu(display(
+        "😱 unable to parse cuckoo model at line {}, column {}: {}",
+        line,
+        column,
+        source
+    ))]
+    ParseCuckooModel {
+        line: usize,
+        column: usize,
+        source: serde_json::Error,
+    },
+    #[snafu(display("😱 cuckoo model has no paper"))]
+    EmptyPaperIds,
+    #[snafu(display("😱 cuckoo model paper {} is missing", id))]
+    MissingPaper { id: Uuid },
+    #[snafu(display("😱 cuckoo schema is missing `{}`", key))]
+    MissingSchemaKey { key: String },
+    #[snafu(display("😱 cuckoo model {} {} is missing", kind, id))]
+    MissingEntity { kind: String, id: Uuid },
+    #[snafu(display("😱 cuckoo model expected {} {}, found {}", expected, id, found))]
+    WrongEntityKind {
+        id: Uuid,
+        expected: String,
+        found: String,
+    },
+    #[snafu(display("😱 unable to make sense of model version `{}`", version))]
+    BadModelVersion { version: String },
+    #[snafu(display(
+        "😱 model was written by nut {}, but this is nut {} -- time to upgrade",
+        found,
+        supported
+    ))]
+    NewerModel { found: String, supported: String },
+    #[snafu(display(
+        "😱 migrating model to {} ({}) failed: {}",
+        version,
+        description,
+        source
+    ))]
+    Migration {
+        version: String,
+        description: String,
+        source: Box<Error>,
+    },
+    #[snafu(display("😱 migration path not found in model: {}", path))]
+    MigrationPath { path: String },
+    #[snafu(display("😱 unable to load text model {}", path.display()))]
+    LoadTextModel {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("😱 unable to load text model {}: {}", path.display(), source))]
+    BadTextModel { path: PathBuf, source: Box<Error> },
+    #[snafu(display("😱 {}:{}: {}\n{}", line, column, message, snippet))]
+    ParseTextModel {
+        line: usize,
+        column: usize,
+        message: String,
+        snippet: String,
+    },
+    #[snafu(display("😱 {} is out of date\n{}", path.display(), diff))]
+    StaleOutput { path: PathBuf, diff: String },
+    #[snafu(display(
+        "{}",
+        stale
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))]
+    StaleOutputs { stale: Vec<CodeGenError> },
+    #[snafu(display(
+        "😱 regenerating {} would displace user code:\n{}",
+        path.display(),
+        displaced
+            .iter()
+            .map(|d| format!("  {}", d))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))]
+    DisplacedCode {
+        path: PathBuf,
+        displaced: Vec<Displaced>,
+    },
+    #[snafu(display(
+        "😱 {} was generated by nut {}, but this is nut {} -- time to upgrade",
+        path.display(),
+        found,
+        supported
+    ))]
+    NewerOutput {
+        path: PathBuf,
+        found: String,
+        supported: String,
+    },
+    #[snafu(display("😱 unable to close {}, {} is open", closing, open))]
+    MismatchedBlock { closing: String, open: String },
+    #[snafu(display("😱 {} ends with {} still open", path.display(), open))]
+    UnclosedBlock { path: PathBuf, open: String },
+    #[snafu(display("😱 {} and {} both generate `{}`", first, second, name))]
+    SymbolCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+    #[snafu(display("😱 there's no symbol scope to pop, just the global one"))]
+    PoppedGlobalScope,
+    #[snafu(display(
+        "😱 {} on {} is a `{}`, and there's no making an example of one of those",
+        attribute,
+        object,
+        ty
+    ))]
+    NoExampleValue {
+        object: String,
+        attribute: String,
+        ty: String,
+    },
 }
@@ -25,6 +25,10 @@ impl Config {
         self.inner.get(key)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&Uuid, &ConfigValue)> {
+        self.inner.iter()
+    }
+
     pub fn is_singleton(&self, key: &Uuid) -> bool {
         self.get_singleton(key).is_some()
     }
@@ -53,6 +57,10 @@ impl Config {
         result
     }
 
+    pub fn get_identity(&self, key: &Uuid) -> Option<IdentityPolicy> {
+        self.get(key).and_then(|opt| opt.identity)
+    }
+
     pub fn is_imported(&self, key: &Uuid) -> bool {
         self.get_imported(key).is_some()
     }
@@ -111,6 +119,8 @@ impl Config {
 pub struct ConfigValue {
     pub imported_object: Option<ImportedObject>,
     pub singleton_object: Option<SingletonObject>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<IdentityPolicy>,
 }
 
 impl ConfigValue {
@@ -118,6 +128,7 @@ impl ConfigValue {
         Self {
             imported_object: None,
             singleton_object: None,
+            identity: None,
         }
     }
 }
@@ -149,3 +160,29 @@ pub struct ImportedObject {
 /// type's `id`.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SingletonObject(pub bool);
+
+/// How `new` comes up with an `id`
+///
+/// Out of the box, the `id` is a v5 UUID made out of every attribute. Two
+/// instances that look the same get the same `id`, and the second one quietly
+/// replaces the first in the store. Sometimes that's what you want, and sometimes
+/// it's a bug waiting to happen.
+///
+/// Declaring identifiers on the attributes (`*1`, `*2`, ...) is enough to get
+/// [`IdentityPolicy::Identifier`]. Otherwise it goes in the object description:
+///
+/// `❗️ {"identity": "random"}`
+///
+/// Either way, `new` returns a `Result`, and an instance that would have replaced
+/// another one in the store is a `DuplicateIdentifier` error instead.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityPolicy {
+    /// A v5 UUID made out of the attributes of the first identifier, or all of
+    /// them, if there aren't any
+    Identifier,
+    /// A v4 UUID, which needs the `v4` feature of the `uuid` crate
+    Random,
+    /// Whoever calls `new` passes it in, as the last argument
+    Supplied,
+}
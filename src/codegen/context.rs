@@ -4,13 +4,14 @@
 //! renamed StringContext, and all the code gen stuff should be represented in terms
 //! of Extrude. I think anyway.
 use std::{
-    collections::HashMap,
-    fs::File,
+    collections::HashSet,
+    fmt,
+    fs::{self, File},
     io::{BufRead, BufReader, Read, Write},
+    mem,
     ops::AddAssign,
-    ops::Deref,
+    ops::{Deref, RangeInclusive},
     path::{Path, PathBuf},
-    process,
 };
 
 use diff;
@@ -20,13 +21,24 @@ use snafu::prelude::*;
 use tempfile::NamedTempFile;
 use uuid::Uuid;
 
+use crate::codegen::format::Formatter;
+use crate::codegen::source_map::{
+    remap, sidecar_path, Construct, Element, ElementKind, Mapped, SourceMap,
+};
+use crate::codegen::symbol_table::{Symbol, SymbolTable};
 use crate::codegen::{
     BadnessSnafu, CodeGenError, ContextNoPathSnafu, ContextPathBeingStubbornSnafu, Error,
-    FileCreateSnafu, FileWriteSnafu, Result, SerdeJsonBombedSnafu, SpawnRustfmtSnafu, VERSION,
+    FileCreateSnafu, FileWriteSnafu, Result, SerdeJsonBombedSnafu, VERSION,
 };
+use crate::sarzak::migrate::ModelVersion;
 
 const MAGIC: &str = "";
 
+/// Where a generated file came from
+///
+/// There's exactly one of these in a file that we generated, and it says which
+/// nut did the generating. It's written as a regular comment, after the `//!`
+/// lines, so that it doesn't clutter up the docs.
 #[derive(Deserialize, Serialize)]
 struct ContextHeader {
     magic: String,
@@ -45,6 +57,10 @@ enum DirectiveKind {
     IgnoreBlockEnd,
     CriticalBlockBegin(CriticalBlockBegin),
     CriticalBlockEnd { tag: String },
+    UserRegionBegin(UserRegionBegin),
+    UserRegionEnd { name: String },
+    ElementBegin(Element),
+    ElementEnd { id: Uuid },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -86,6 +102,60 @@ impl IgnoreBlockBegin {
     }
 }
 
+/// A place for the user's code, that we carry along
+///
+/// We generate these empty, and whatever the user puts in them goes wherever the
+/// region goes. They are found again by `id` if there is one, otherwise by `name`.
+/// That way the region for an object sticks with the object, even after it's been
+/// renamed.
+#[derive(Debug, Deserialize, Serialize)]
+struct UserRegionBegin {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Uuid>,
+}
+
+/// User code that regeneration pushed out of the way
+///
+/// When the lines inside of a critical block don't match what we are generating,
+/// the old ones get commented out, with a `//⚡️` on the end. When a critical block
+/// isn't generated at all any more, the whole thing is deleted. Either way, if
+/// somebody edited those lines by hand, they'd like to know.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Displaced {
+    pub path: PathBuf,
+    /// The tag of the critical block
+    pub tag: String,
+    pub kind: Displacement,
+    /// Where it was in the file, before we got to it. Lines count from one.
+    pub lines: RangeInclusive<usize>,
+    /// What was there
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Displacement {
+    CommentedOut,
+    Deleted,
+}
+
+impl fmt::Display for Displaced {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}-{}: {} critical block `{}`",
+            self.path.display(),
+            self.lines.start(),
+            self.lines.end(),
+            match self.kind {
+                Displacement::CommentedOut => "commented out lines in",
+                Displacement::Deleted => "deleted",
+            },
+            self.tag
+        )
+    }
+}
+
 /// A Context for writing output
 /// The general idea is that you pass this around and write to it. When you are
 /// done it commits itself to a the file at the passed in path.
@@ -97,6 +167,15 @@ pub struct Context {
     have_magic: bool,
     magic_string: String,
     ignore_ignore: bool,
+    fail_on_displaced: bool,
+    /// The version of nut that generated the file that's already there
+    found_version: Option<ModelVersion>,
+    allow_newer: bool,
+    /// Write a source map next to the file
+    source_map: bool,
+    /// Where the model elements ended up, once rendered
+    elements: Vec<Mapped>,
+    formatter: Formatter,
 }
 
 impl Context {
@@ -112,6 +191,12 @@ impl Context {
             })
             .context(SerdeJsonBombedSnafu)?,
             ignore_ignore,
+            fail_on_displaced: false,
+            found_version: None,
+            allow_newer: false,
+            source_map: false,
+            elements: Vec::new(),
+            formatter: Formatter::default(),
         };
 
         new.validate_output_file()?;
@@ -130,52 +215,37 @@ impl Context {
             // If so, did we generate it?
             let file = File::open(&path).context(ContextPathBeingStubbornSnafu { path: &path })?;
             if file.metadata().context(BadnessSnafu)?.len() > 0 {
-                let mut line = String::new();
-                let mut reader = BufReader::new(file);
-
                 // Having the header as the first line in the file makes the
-                // documentation ugly. So I'm going to move it to the first
-                // regular comment line.
-                //
-                // So, first we throw away all the lines that start with "//!".
-                reader.read_line(&mut line).context(BadnessSnafu)?;
-                while line.starts_with("//!") {
-                    line.clear();
-                    reader.read_line(&mut line).context(BadnessSnafu)?;
-                }
-
-                // Then we continue as before, except that we only strip off the
-                // first two bytes.
-                //
-                reader.read_line(&mut line).context(BadnessSnafu)?;
-                if loop {
-                    if line.starts_with("//") {
-                        // Strip off the first three bytes, as they should be "//!".
-                        line.replace_range(..2, "");
-                        match serde_json::from_str::<ContextHeader>(&line) {
-                            Ok(header) => {
-                                // We may want to consider doing something with the version.
-                                // It would be nice to replace the header, rather than comment
-                                // out the old line. Not sure how that would work exactly.
-                                if header.magic != MAGIC {
-                                    error!("😱 file not generated by sarzak: {}", path.display());
-                                    break false;
-                                } else {
-                                    // Indicate that writing is safe.
-                                    self.our_file = true;
-                                    break true;
-                                }
-                            }
-                            Err(_e) => {
-                                line.clear();
-                                reader.read_line(&mut line).context(BadnessSnafu)?;
+                // documentation ugly. So it goes with the regular comments that
+                // come after the "//!" lines. Older versions of us didn't replace
+                // the header, they added another, so there may be a few.
+                let mut headers = Vec::new();
+                for line in BufReader::new(file).lines() {
+                    let line = line.context(BadnessSnafu)?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match line.strip_prefix("//") {
+                        Some(comment) => {
+                            if let Ok(header) = serde_json::from_str::<ContextHeader>(comment) {
+                                headers.push(header);
                             }
                         }
-                    } else {
-                        break false;
+                        None => break,
                     }
-                } {
+                }
+
+                if !headers.is_empty() && headers.iter().all(|h| h.magic == MAGIC) {
+                    // Indicate that writing is safe.
                     self.our_file = true;
+
+                    // The newest one is the one that counts.
+                    let mut found = None;
+                    for header in &headers {
+                        let version = header.version.parse::<ModelVersion>()?;
+                        found = found.max(Some(version));
+                    }
+                    self.found_version = found;
                 } else {
                     error!("😱 file not generated by sarzak: {}", path.display());
                 }
@@ -187,14 +257,70 @@ impl Context {
         Ok(())
     }
 
+    /// Refuse to displace user code
+    ///
+    /// Rather than commenting out, or deleting, lines in critical blocks, `commit`
+    /// fails with a [`CodeGenError::DisplacedCode`], and leaves the file be.
+    pub fn fail_on_displaced(&mut self, fail: bool) {
+        self.fail_on_displaced = fail;
+    }
+
+    /// Overwrite files generated by a newer nut
+    ///
+    /// Normally that's a [`CodeGenError::NewerOutput`], because we have no idea
+    /// what a newer version put there, or what it will think of what we put there.
+    /// This turns it into a warning. Either way, the header in the file ends up
+    /// saying that we wrote it, because we did.
+    pub fn allow_newer(&mut self, allow: bool) {
+        self.allow_newer = allow;
+    }
+
+    /// Write a source map too
+    ///
+    /// It goes next to the file, see [`sidecar_path`](crate::codegen::sidecar_path),
+    /// and says which model element each bit of the file came from.
+    pub fn source_map(&mut self, source_map: bool) {
+        self.source_map = source_map;
+    }
+
+    /// Format the output with something other than `rustfmt`
+    ///
+    /// Whatever it is runs after generating, and again after merging with what's
+    /// already in the file. With [`Formatter::None`] the merged output keeps
+    /// whatever stray blank lines the merge left behind.
+    pub fn formatter(&mut self, formatter: Formatter) {
+        self.formatter = formatter;
+    }
+
     pub fn inter_symbol(&mut self, id: Uuid, symbol: Symbol) {
         self.context.inter_symbol(id, symbol)
     }
 
+    pub fn declare_symbol<S: AsRef<str>>(
+        &mut self,
+        id: Uuid,
+        origin: S,
+        symbol: Symbol,
+    ) -> Result<()> {
+        self.context.declare_symbol(id, origin, symbol)
+    }
+
     pub fn exhume_symbol(&self, id: &Uuid) -> Option<&Symbol> {
         self.context.exhume_symbol(id)
     }
 
+    pub fn exhume_symbol_by_name<S: AsRef<str>>(&self, name: S) -> Option<&Symbol> {
+        self.context.exhume_symbol_by_name(name)
+    }
+
+    pub fn push_scope(&mut self) {
+        self.context.push_scope()
+    }
+
+    pub fn pop_scope(&mut self) -> Result<()> {
+        self.context.pop_scope()
+    }
+
     pub fn increase_indent(&mut self) {
         self.context.increase_indent()
     }
@@ -219,6 +345,37 @@ impl Context {
         self.context.end_critical_block(tag)
     }
 
+    pub fn begin_user_region<S: AsRef<str>>(&mut self, name: S, id: Option<Uuid>) -> Result<()> {
+        self.context.begin_user_region(name, id)
+    }
+
+    pub fn end_user_region<S: AsRef<str>>(&mut self, name: S) -> Result<()> {
+        self.context.end_user_region(name)
+    }
+
+    pub fn user_region<S: AsRef<str>>(&mut self, name: S, id: Option<Uuid>) -> Result<()> {
+        self.context.user_region(name, id)
+    }
+
+    pub fn begin_element<S: AsRef<str>>(
+        &mut self,
+        construct: Construct,
+        kind: ElementKind,
+        id: Uuid,
+        name: S,
+    ) -> Result<()> {
+        // This is code starting, so the header goes first, or it ends up inside.
+        if !self.have_magic {
+            self.context.writeln(format!("// {}", self.magic_string));
+            self.have_magic = true;
+        }
+        self.context.begin_element(construct, kind, id, name)
+    }
+
+    pub fn end_element(&mut self, id: Uuid) -> Result<()> {
+        self.context.end_element(id)
+    }
+
     pub fn writeln<S: AsRef<str>>(&mut self, data: S) {
         if !self.have_magic {
             if !data.as_ref().starts_with("//!") && !data.as_ref().starts_with("//") {
@@ -234,133 +391,324 @@ impl Context {
     }
 
     pub fn format(&self, buffer: &String) -> Result<String> {
-        trace!("formatting output buffer");
-        let mut file = NamedTempFile::new().context(BadnessSnafu)?;
-        file.write_all(buffer.as_bytes()).context(BadnessSnafu)?;
+        self.formatter.format(buffer)
+    }
 
-        let child = process::Command::new("rustfmt")
-            // .arg(&path.to_str().expect("this is a pain in the dick"))
-            .args(["--emit", "stdout", &file.path().to_string_lossy()])
-            .stdout(process::Stdio::piped())
-            .spawn()
-            .context(SpawnRustfmtSnafu)?;
+    /// My god, this is a big, ugly son-of-a-bitch.
+    ///
+    /// It's a lot less big now that [`render`](Self::render) does the heavy
+    /// lifting. Still ugly though.
+    ///
+    /// What comes back is everything that got commented out or deleted from
+    /// critical blocks, so that it doesn't just quietly disappear. It's logged
+    /// too.
+    pub fn commit(self) -> Result<Vec<Displaced>> {
+        let staged = self.stage()?;
+        for (path, contents) in &staged.files {
+            persist(stage_file(path, contents)?, path)?;
+        }
 
-        let output = child.wait_with_output().context(BadnessSnafu)?;
+        Ok(staged.displaced)
+    }
 
-        if output.status.success() {
-            let buffer = String::from_utf8_lossy(&output.stdout);
+    /// Do everything but write
+    ///
+    /// This is where we find out if there's a problem, which is the whole point of
+    /// doing it before we write anything.
+    fn stage(mut self) -> Result<Staged> {
+        if self.our_file {
+            match self.path.clone() {
+                Some(path) => {
+                    let existed = path.exists();
+                    let (contents, displaced) = self.render(&path)?;
+                    for d in &displaced {
+                        warn!("⚡️ {}", d);
+                    }
 
-            // Some junk get's appended to the top of stdout.
-            let mut iter = buffer.splitn(3, '\n');
-            iter.next();
-            iter.next();
+                    if contents.is_none() {
+                        debug!("no changes");
+                    } else if existed {
+                        debug!("writing diff");
+                    } else {
+                        debug!("writing output");
+                    }
 
-            Ok(iter.next().unwrap().into())
-        } else {
-            eprintln!("Failure parsing input file, reproduced below...");
-            eprintln!("{}", buffer);
+                    let mut files = Vec::new();
+                    if let Some((map_path, map)) = self.stale_source_map(&path)? {
+                        debug!("writing source map: {}", map_path.display());
+                        files.push((map_path, map));
+                    }
+                    if let Some(contents) = contents {
+                        files.insert(0, (path, contents));
+                    }
 
-            Err(Error(CodeGenError::RustFmt {
-                exit_code: output.status.code(),
-            }))
+                    Ok(Staged { files, displaced })
+                }
+                None => Err(Error(CodeGenError::ContextNoPath)),
+            }
+        } else {
+            warn!("not writing output due to unknown contents of destination file");
+            Ok(Staged {
+                files: Vec::new(),
+                displaced: Vec::new(),
+            })
         }
     }
 
-    /// My god, this is a big, ugly son-of-a-bitch.
+    /// Check that the file on disk is up to date, without touching it
+    ///
+    /// This does everything that [`commit`](Self::commit) does -- formatting,
+    /// merging with what's on disk, the works -- and then doesn't write. If the
+    /// file would have changed, you get a [`CodeGenError::StaleOutput`] with a
+    /// unified diff of what would have changed. That's for CI, so that out of date
+    /// generated code doesn't sneak into a PR.
     ///
-    pub fn commit(mut self) -> Result<()> {
+    /// A file that we didn't generate is left alone, just like `commit` does.
+    /// Since nothing is written, nothing is displaced, and the `Vec` is always
+    /// empty. It's there so that this and `commit` are interchangeable.
+    pub fn check(mut self) -> Result<Vec<Displaced>> {
         if self.our_file {
-            match self.path {
+            match self.path.clone() {
                 Some(ref path) => {
-                    // It looks like we are going to write this thing. Ensure that
-                    // we have added magic.
-                    if !self.have_magic {
-                        self.context.writeln(format!("// {}", self.magic_string));
-                        self.have_magic = true;
-                    }
-
-                    if path.exists() {
-                        // Get the text from the file.
-                        let mut orig = String::new();
-                        File::open(&path)
-                            .context(ContextPathBeingStubbornSnafu { path: &path })?
-                            .read_to_string(&mut orig)
-                            .context(BadnessSnafu)?;
-
-                        // Format our buffer
-                        let formatted = self.format(&self.context.buffer)?;
-                        let (diffed, dirty) =
-                            Context::merge_sources(&orig, &formatted, self.ignore_ignore);
-
-                        if dirty {
-                            // Format the new buffer, because there is going to be junk left over (
-                            // spaces, and blank lines, and whatnot).
-                            let formatted = self.format(&diffed)?;
-
-                            File::create(path)
-                                .context(FileCreateSnafu { path })?
-                                .write_all(formatted.as_bytes())
-                                .context(FileWriteSnafu { path })?;
-
-                            debug!("writing diff");
+                    if let (Some(formatted), _) = self.render(path)? {
+                        let orig = if path.exists() {
+                            self.read_orig(path)?
                         } else {
-                            debug!("no changes");
-                        }
-
-                        Ok(())
+                            String::new()
+                        };
+
+                        Err(Error(CodeGenError::StaleOutput {
+                            path: path.to_path_buf(),
+                            diff: unified_diff(path, &orig, &formatted),
+                        }))
+                    } else if let Some((map_path, map)) = self.stale_source_map(path)? {
+                        let orig = fs::read_to_string(&map_path).unwrap_or_default();
+
+                        Err(Error(CodeGenError::StaleOutput {
+                            diff: unified_diff(&map_path, &orig, &map),
+                            path: map_path,
+                        }))
                     } else {
-                        // Format our buffer
-                        let formatted = self.format(&self.context.buffer)?;
-
-                        File::create(path)
-                            .context(FileCreateSnafu { path })?
-                            .write_all(formatted.as_bytes())
-                            .context(FileWriteSnafu { path })?;
-
-                        debug!("writing output");
-                        Ok(())
+                        debug!("up to date: {}", path.display());
+                        Ok(Vec::new())
                     }
                 }
                 None => Err(Error(CodeGenError::ContextNoPath)),
             }
         } else {
-            warn!("not writing output due to unknown contents of destination file");
-            Ok(())
+            warn!("not checking output due to unknown contents of destination file");
+            Ok(Vec::new())
+        }
+    }
+
+    /// Commit, or check, depending
+    ///
+    /// The generators all end this way, and it saves them an `if`.
+    pub fn finish(self, check: bool) -> Result<Vec<Displaced>> {
+        if check {
+            self.check()
+        } else {
+            self.commit()
         }
     }
 
-    fn merge_sources(orig: &String, formatted: &String, ignore_ignore: bool) -> (String, bool) {
+    /// Work out what should be in the file at `path`
+    ///
+    /// Returns `None` if what's already there is just fine, along with whatever
+    /// user code would be displaced.
+    fn render(&mut self, path: &Path) -> Result<(Option<String>, Vec<Displaced>)> {
+        self.context.check_symbols()?;
+
+        if let Some(open) = self.context.blocks.last() {
+            return Err(Error(CodeGenError::UnclosedBlock {
+                path: path.to_path_buf(),
+                open: open.to_string(),
+            }));
+        }
+
+        if let Some(found) = self.found_version {
+            let current = ModelVersion::current();
+            if found > current {
+                if self.allow_newer {
+                    warn!(
+                        "⚠️ {} was generated by nut {}, and this is nut {}",
+                        path.display(),
+                        found,
+                        current
+                    );
+                } else {
+                    return Err(Error(CodeGenError::NewerOutput {
+                        path: path.to_path_buf(),
+                        found: found.to_string(),
+                        supported: current.to_string(),
+                    }));
+                }
+            } else if found < current {
+                debug!(
+                    "upgrading {} from nut {} to {}",
+                    path.display(),
+                    found,
+                    current
+                );
+            }
+        }
+
+        // It looks like we are going to write this thing. Ensure that
+        // we have added magic.
+        if !self.have_magic {
+            self.context.writeln(format!("// {}", self.magic_string));
+            self.have_magic = true;
+        }
+
+        if path.exists() {
+            // Get the text from the file.
+            let orig = self.read_orig(path)?;
+
+            // Format our buffer, and take the source map markers back out. They
+            // only need to last long enough to find out where rustfmt put things.
+            let (stripped, elements) = strip_elements(&self.format(&self.context.buffer)?);
+            // Move the user's code into the new buffer, before we compare.
+            let (formatted, carried) = carry_user_regions(&orig, &stripped);
+            let (diffed, dirty, mut displaced) =
+                Context::merge_sources(&orig, &formatted, &carried, self.ignore_ignore);
+            for d in &mut displaced {
+                d.path = path.to_path_buf();
+            }
+
+            if !displaced.is_empty() && self.fail_on_displaced {
+                return Err(Error(CodeGenError::DisplacedCode {
+                    path: path.to_path_buf(),
+                    displaced,
+                }));
+            }
+
+            let output = if dirty {
+                // Format the new buffer, because there is going to be junk left over (
+                // spaces, and blank lines, and whatnot).
+                let formatted = self.format(&diffed)?;
+
+                // Dirty, as far as the merge is concerned, doesn't necessarily
+                // mean that the file changes. Commented out lines stay commented.
+                if formatted == orig {
+                    None
+                } else {
+                    Some(formatted)
+                }
+            } else {
+                None
+            };
+
+            self.elements = remap(elements, &stripped, output.as_ref().unwrap_or(&orig));
+
+            Ok((output, displaced))
+        } else {
+            // Format our buffer
+            let (stripped, elements) = strip_elements(&self.format(&self.context.buffer)?);
+            self.elements = elements;
+
+            Ok((Some(stripped), Vec::new()))
+        }
+    }
+
+    /// The source map for `path`, if it's wanted, and it's not what's on disk
+    ///
+    /// Only makes sense after [`render`](Self::render).
+    fn stale_source_map(&self, path: &Path) -> Result<Option<(PathBuf, String)>> {
+        if !self.source_map {
+            return Ok(None);
+        }
+
+        let map = SourceMap {
+            file: path.file_name().map(PathBuf::from).unwrap_or_default(),
+            version: VERSION.to_owned(),
+            elements: self.elements.clone(),
+        };
+        let map = serde_json::to_string_pretty(&map).context(SerdeJsonBombedSnafu)? + "\n";
+
+        let map_path = sidecar_path(path);
+        if fs::read_to_string(&map_path).is_ok_and(|orig| orig == map) {
+            Ok(None)
+        } else {
+            Ok(Some((map_path, map)))
+        }
+    }
+
+    fn read_orig(&self, path: &Path) -> Result<String> {
+        let mut orig = String::new();
+        File::open(path)
+            .context(ContextPathBeingStubbornSnafu { path })?
+            .read_to_string(&mut orig)
+            .context(BadnessSnafu)?;
+
+        Ok(orig)
+    }
+
+    fn merge_sources(
+        orig: &str,
+        formatted: &str,
+        carried: &HashSet<String>,
+        ignore_ignore: bool,
+    ) -> (String, bool, Vec<Displaced>) {
         let mut dirty = false;
-        let mut ignoring = false;
-        let mut delete_ignore = false;
-        let mut crit = false;
+        // Whether we are in a user region in the original, and if it's been carried
+        // into the new buffer.
+        let mut user_region = None;
+        // The blocks that the new buffer has open, innermost last. This follows the
+        // new buffer, which is balanced, because CachingContext makes sure of it.
+        let mut open: Vec<OpenBlock> = Vec::new();
         let mut ignore_crit = false;
         let mut delete_crit = false;
+        // Deleting, because it moved, rather than because it's gone.
+        let mut moved_crit = false;
         let mut delete_last_crit = false; // There's surely a better way to do this.
         let mut delete_crit_tag = String::new();
         let mut diffed = String::new();
+        // Keep track of where we are in the original, so that we can say where the
+        // user's code was.
+        let mut line_number = 0;
+        let mut displaced = Displacements::new(orig);
         // Produce the diff. Lines on the left (from the file) are inserted behind
         // a comment. Otherwise, we just write the line.
         // Unless, there is an ignore directive, then we ... ignore.
-        for diff in diff::lines(orig.as_str(), formatted.as_str()) {
+        for diff in diff::lines(orig, formatted) {
+            // Being inside of an ignore block, no matter how far down, trumps
+            // everything else.
+            let ignoring = open
+                .iter()
+                .any(|b| b.live && b.kind == OpenKind::Ignore { delete: false });
+            let delete_ignore = open
+                .iter()
+                .any(|b| b.live && b.kind == OpenKind::Ignore { delete: true });
+
             match diff {
                 diff::Result::Left(orig) => {
+                    line_number += 1;
+
                     // Pass through blank lines
                     if orig == "" {
                         diffed = diffed + orig + "\n"
+                    } else if is_header(orig) {
+                        // This is an old header. The new one is in the buffer, so
+                        // out with the old.
+                        dirty = true;
+                    } else if track_user_region(&mut user_region, orig, carried) {
+                        // The user's code is already in the new buffer, wherever
+                        // the region ended up.
+                        dirty = true;
                     } else {
                         // I'm not sure where to put this. It seems like the right
                         // place is outside of other directives. So I'll try that
                         // first.
-                        if let Some(directive) = parse_directive(orig) {
+                        let directive = parse_directive(orig);
+                        if let Some(ref directive) = directive {
                             trace!("found directive in orig: {:?}", directive);
 
-                            match directive.kind {
+                            match &directive.kind {
                                 DirectiveKind::CriticalBlockBegin(cb) => {
                                     // If it's uber, leave it be.
                                     if cb.is_uber() {
                                         ignore_crit = true;
-                                    } else {
+                                    } else if !delete_crit {
                                         // We need to be on the lookout for the closing
                                         // magic. Let's create what that looks like here.
                                         if let Ok(crit_end_magic) =
@@ -373,20 +721,33 @@ impl Context {
                                         {
                                             if let None = formatted.find(crit_end_magic.as_str()) {
                                                 // This critical section isn't being written,
-                                                // so remove it from the source file.
+                                                // so remove it from the source file. Along
+                                                // with anything nested inside of it.
+                                                delete_crit = true;
+                                                moved_crit = false;
+                                                delete_last_crit = false;
+                                                delete_crit_tag = cb.tag.clone();
+                                                dirty = true;
+                                            } else if !open.iter().any(|b| {
+                                                b.live && matches!(b.kind, OpenKind::Critical(_))
+                                            }) {
+                                                // It is being written, just not here. The
+                                                // generated code went along with it, so this
+                                                // copy goes too.
                                                 delete_crit = true;
+                                                moved_crit = true;
                                                 delete_last_crit = false;
-                                                delete_crit_tag = cb.tag;
+                                                delete_crit_tag = cb.tag.clone();
                                                 dirty = true;
                                             }
                                         }
                                     }
                                 }
-                                DirectiveKind::CriticalBlockEnd { tag } => {
-                                    if delete_crit && tag == delete_crit_tag {
-                                        delete_last_crit = true;
-                                        ignore_crit = false;
-                                    }
+                                DirectiveKind::CriticalBlockEnd { tag }
+                                    if delete_crit && *tag == delete_crit_tag =>
+                                {
+                                    delete_last_crit = true;
+                                    ignore_crit = false;
                                 }
                                 _ => {}
                             }
@@ -399,19 +760,51 @@ impl Context {
                         } else if delete_ignore || delete_crit {
                             dirty = true;
 
+                            // Forcing ignored blocks out is what ignore_ignore is
+                            // for, so that's not news. Neither is a block that moved,
+                            // except for the lines that didn't move with it.
+                            if delete_crit
+                                && !(moved_crit
+                                    && (directive.is_some()
+                                        || formatted.lines().any(|l| l.trim() == orig.trim())))
+                            {
+                                displaced.push(
+                                    &delete_crit_tag,
+                                    Displacement::Deleted,
+                                    line_number,
+                                    orig,
+                                );
+                            }
+
                             if delete_last_crit {
                                 delete_crit = false;
                             }
-                        } else if crit {
+                        } else if let Some(crit_tag) = open.iter().rev().find_map(|b| match b {
+                            OpenBlock {
+                                live: true,
+                                kind: OpenKind::Critical(tag),
+                            } => Some(tag),
+                            _ => None,
+                        }) {
                             // If this is a critical section, comment out the offending
                             // lines. Otherwise, pass them through.
                             dirty = true;
-                            // Already commented out -- don't cons comments
-                            if orig.contains("⚡️") {
+                            if directive.is_some() || is_displaced_directive(orig) {
+                                // Directives are ours, not the user's. This one moved,
+                                // and the new buffer has it wherever it went. Commenting
+                                // it out just leaves a dead one lying around.
+                            } else if orig.contains("⚡️") {
+                                // Already commented out -- don't cons comments
                                 diffed = diffed + orig + "\n"
                             } else {
                                 // Comment out the line.
-                                diffed = diffed + "// " + orig + " //⚡️" + "\n"
+                                diffed = diffed + "// " + orig + " //⚡️" + "\n";
+                                displaced.push(
+                                    crit_tag,
+                                    Displacement::CommentedOut,
+                                    line_number,
+                                    orig,
+                                );
                             }
                         } else {
                             // Allow the end user to add code outside of critical sections.
@@ -420,6 +813,20 @@ impl Context {
                     }
                 }
                 diff::Result::Both(both, _) => {
+                    line_number += 1;
+                    track_user_region(&mut user_region, both, carried);
+
+                    // A moved block may end up ending right where it used to.
+                    if delete_crit {
+                        if let Some(DirectiveKind::CriticalBlockEnd { tag }) =
+                            parse_directive(both).map(|d| d.kind)
+                        {
+                            if tag == delete_crit_tag {
+                                delete_crit = false;
+                            }
+                        }
+                    }
+
                     // Look for embedded processing directives.
                     //
                     // Processing directives come from the original file. We only
@@ -430,34 +837,12 @@ impl Context {
                     if let Some(directive) = parse_directive(both) {
                         trace!("found directive in both: {:?}", directive);
 
-                        match directive.kind {
-                            DirectiveKind::IgnoreBlockBegin(i) => {
-                                ignoring = {
-                                    if i.is_uber() {
-                                        delete_ignore = false;
-                                        true
-                                    } else {
-                                        if ignore_ignore {
-                                            debug!("ignore_ignore");
-                                            delete_ignore = true;
-                                            false
-                                        } else {
-                                            true
-                                        }
-                                    }
-                                };
-                            }
-                            DirectiveKind::IgnoreBlockEnd => {
-                                delete_ignore = false;
-                                ignoring = false;
-                            }
-                            DirectiveKind::CriticalBlockBegin(_) => {
-                                crit = true;
-                            }
-                            DirectiveKind::CriticalBlockEnd { tag: _ } => {
-                                crit = false;
-                                ignore_crit = false;
-                            }
+                        if let Some(OpenBlock {
+                            kind: OpenKind::Critical(_),
+                            ..
+                        }) = OpenBlock::track(&mut open, directive, true, ignore_ignore)
+                        {
+                            ignore_crit = false;
                         }
                     }
 
@@ -465,6 +850,18 @@ impl Context {
                     diffed = diffed + both + "\n";
                 }
                 diff::Result::Right(new) => {
+                    // Blocks that are only in the new buffer aren't live, but we still
+                    // need to know where they are, so that the nesting adds up.
+                    if let Some(directive) = parse_directive(new) {
+                        if let Some(OpenBlock {
+                            kind: OpenKind::Critical(_),
+                            ..
+                        }) = OpenBlock::track(&mut open, directive, false, ignore_ignore)
+                        {
+                            ignore_crit = false;
+                        }
+                    }
+
                     if !ignoring && !ignore_crit {
                         dirty = true;
 
@@ -475,7 +872,7 @@ impl Context {
             }
         }
 
-        (diffed, dirty)
+        (diffed, dirty, displaced.into_inner())
     }
 
     pub fn to_string(self) -> String {
@@ -483,11 +880,152 @@ impl Context {
     }
 }
 
+/// Generated output, ready to go
+///
+/// `files` is the path and contents of everything that needs writing, which may be
+/// nothing at all when what's on disk is already right, or when it's not ours to
+/// touch.
+#[derive(Debug)]
+struct Staged {
+    files: Vec<(PathBuf, String)>,
+    displaced: Vec<Displaced>,
+}
+
+/// Write `contents` to a temporary file, next to `path`
+///
+/// Being in the same directory means that renaming it into place is atomic, so an
+/// interrupted run never leaves a half written file behind. The permissions are
+/// copied from the file that's being replaced, if there is one.
+fn stage_file(path: &Path, contents: &str) -> Result<NamedTempFile> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut file = NamedTempFile::new_in(dir).context(FileCreateSnafu { path })?;
+    file.write_all(contents.as_bytes())
+        .context(FileWriteSnafu { path })?;
+
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(file.path(), metadata.permissions())
+            .context(FileWriteSnafu { path })?;
+    } else {
+        // Temporary files are private, and source code isn't.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(file.path(), fs::Permissions::from_mode(0o644))
+                .context(FileWriteSnafu { path })?;
+        }
+    }
+
+    Ok(file)
+}
+
+/// Move a staged file into place
+fn persist(file: NamedTempFile, path: &Path) -> Result<()> {
+    file.persist(path)
+        .map_err(|e| e.error)
+        .context(FileWriteSnafu { path })?;
+
+    Ok(())
+}
+
+/// A group of Contexts that are written all together, or not at all
+///
+/// The files for a domain depend on each other. If the macros fail to generate, and
+/// the types and the store are written anyway, the crate doesn't compile any more.
+/// So the group generates, formats, and merges every one of them first. Then it
+/// writes them all to temporary files, next to where they are going. Only once
+/// they have all made it that far are they renamed into place.
+///
+/// A failed rename part way through the last step can still leave things mixed up,
+/// but that's a much smaller window than it used to be.
+#[derive(Debug, Default)]
+pub struct ContextGroup {
+    contexts: Vec<Context>,
+}
+
+impl ContextGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, context: Context) {
+        self.contexts.push(context);
+    }
+
+    /// Write every file in the group, or none of them
+    ///
+    /// Returns the displaced user code from all of the files.
+    pub fn commit(self) -> Result<Vec<Displaced>> {
+        let staged = self
+            .contexts
+            .into_iter()
+            .map(|c| c.stage())
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut files = Vec::new();
+        for (path, contents) in staged.iter().flat_map(|s| &s.files) {
+            files.push((stage_file(path, contents)?, path));
+        }
+
+        for (file, path) in files {
+            persist(file, path)?;
+        }
+
+        Ok(staged.into_iter().flat_map(|s| s.displaced).collect())
+    }
+
+    /// Check every file in the group
+    ///
+    /// Unlike [`Context::check`], this doesn't stop at the first stale file. All
+    /// of them are reported, in a [`CodeGenError::StaleOutputs`].
+    pub fn check(self) -> Result<Vec<Displaced>> {
+        let mut stale = Vec::new();
+        for context in self.contexts {
+            match context.check() {
+                Err(Error(e @ CodeGenError::StaleOutput { .. })) => stale.push(e),
+                result => {
+                    result?;
+                }
+            }
+        }
+
+        if stale.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(Error(CodeGenError::StaleOutputs { stale }))
+        }
+    }
+
+    /// Commit, or check, depending
+    pub fn finish(self, check: bool) -> Result<Vec<Displaced>> {
+        if check {
+            self.check()
+        } else {
+            self.commit()
+        }
+    }
+}
+
 impl AddAssign for Context {
-    fn add_assign(&mut self, rhs: Self) {
+    fn add_assign(&mut self, mut rhs: Self) {
         // We copy the buffer's contents. We do so using our write methods.
         // Doing so ensures indentation is maintained. It's not without it's
         // problems though.
+        self.context
+            .blocks
+            .extend(rhs.context.blocks.iter().cloned());
+
+        // And the symbols.
+        let collisions = self
+            .context
+            .symbols
+            .merge(mem::take(&mut rhs.context.symbols));
+        self.context.collisions.extend(collisions);
+        self.context.collisions.append(&mut rhs.context.collisions);
+
         let rhs = rhs.to_string();
         // We want to avoid adding an extra newline at the end of iteration.
         let mut iter = rhs.split('\n').peekable();
@@ -505,8 +1043,6 @@ impl AddAssign for Context {
                 break;
             }
         }
-
-        // Now we need to copy the symbol table
     }
 }
 
@@ -519,24 +1055,48 @@ impl From<CachingContext> for Context {
             have_magic: false,
             magic_string: "no_magic 😭".to_owned(),
             ignore_ignore: false,
+            fail_on_displaced: false,
+            found_version: None,
+            allow_newer: false,
+            source_map: false,
+            elements: Vec::new(),
+            formatter: Formatter::default(),
         }
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct Symbol {
-    pub value: String,
-    pub value_type: String,
-    pub is_reference: bool,
+/// A block that's been opened, and not yet closed
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Critical(String),
+    Ignore,
+    User(String),
+    Element(Uuid),
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Block::Critical(tag) => write!(f, "critical block `{}`", tag),
+            Block::Ignore => write!(f, "ignore block"),
+            Block::User(name) => write!(f, "user region `{}`", name),
+            Block::Element(id) => write!(f, "model element `{}`", id),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct CachingContext {
     buffer: String,
-    symbol_table: HashMap<Uuid, Symbol>,
+    symbols: SymbolTable,
+    /// Names that collided when another context was added to this one
+    ///
+    /// `+=` can't fail, so they wait here until the scope is popped, or the file
+    /// is rendered.
+    collisions: Vec<CodeGenError>,
     indent: u8,
-    in_critical_block: bool,
-    in_ignored_block: bool,
+    /// Open blocks, innermost last
+    blocks: Vec<Block>,
 }
 
 /// A Context that looks like a String
@@ -547,21 +1107,57 @@ impl CachingContext {
     pub fn new() -> Self {
         Self {
             buffer: String::new(),
-            symbol_table: HashMap::new(),
+            symbols: SymbolTable::new(),
+            collisions: Vec::new(),
             indent: 0,
-            in_critical_block: false,
-            in_ignored_block: false,
+            blocks: Vec::new(),
         }
     }
 
     pub fn inter_symbol(&mut self, id: Uuid, symbol: Symbol) {
-        trace!("interring symbol {} as {}", symbol.value, id);
-        self.symbol_table.insert(id, symbol);
+        self.symbols.insert(id, symbol);
+    }
+
+    /// Inter a symbol that's a name in the generated code
+    ///
+    /// See [`SymbolTable::declare`].
+    pub fn declare_symbol<S: AsRef<str>>(
+        &mut self,
+        id: Uuid,
+        origin: S,
+        symbol: Symbol,
+    ) -> Result<()> {
+        self.symbols.declare(id, origin, symbol)
     }
 
     pub fn exhume_symbol(&self, id: &Uuid) -> Option<&Symbol> {
-        trace!("exhuming symbol at {}", id);
-        self.symbol_table.get(id)
+        self.symbols.get(id)
+    }
+
+    pub fn exhume_symbol_by_name<S: AsRef<str>>(&self, name: S) -> Option<&Symbol> {
+        self.symbols.get_by_name(name)
+    }
+
+    pub fn push_scope(&mut self) {
+        self.symbols.push_scope();
+    }
+
+    /// Close the innermost scope
+    ///
+    /// This is where we find out about names that collided when other contexts
+    /// were added to this one.
+    pub fn pop_scope(&mut self) -> Result<()> {
+        self.symbols.pop_scope()?;
+        self.check_symbols()
+    }
+
+    /// Report the first name that collided while adding contexts, if any did
+    pub fn check_symbols(&mut self) -> Result<()> {
+        if self.collisions.is_empty() {
+            Ok(())
+        } else {
+            Err(Error(self.collisions.remove(0)))
+        }
     }
 
     pub fn increase_indent(&mut self) {
@@ -574,12 +1170,12 @@ impl CachingContext {
         }
     }
 
+    /// Start a block of code that is left alone once it's written
+    ///
+    /// Blocks nest, so this may go inside of a critical block, or another ignore
+    /// block, or the other way 'round.
     pub fn begin_ignore_block(&mut self) -> Result<()> {
-        if self.in_ignored_block {
-            panic!("Started new ignore block, with existing block still active.");
-        }
-
-        self.in_ignored_block = true;
+        self.blocks.push(Block::Ignore);
         self.writeln(format!(
             "// {}",
             serde_json::to_string(&ContextDirective {
@@ -593,11 +1189,7 @@ impl CachingContext {
     }
 
     pub fn end_ignore_block(&mut self) -> Result<()> {
-        if !self.in_ignored_block {
-            panic!("Not inside of an ignore block.");
-        }
-
-        self.in_ignored_block = false;
+        self.end_block(Block::Ignore)?;
         self.writeln(format!(
             "// {}",
             serde_json::to_string(&ContextDirective {
@@ -610,12 +1202,13 @@ impl CachingContext {
         Ok(())
     }
 
+    /// Start a block of code that belongs to us
+    ///
+    /// The tag is how we find it again, next time around, so it needs to be unique
+    /// in the file. It also needs to match the one passed to
+    /// [`end_critical_block`](Self::end_critical_block).
     pub fn begin_critical_block<S: AsRef<str>>(&mut self, tag: S) -> Result<()> {
-        if self.in_critical_block {
-            panic!("Started new critical block, with existing block still active.");
-        }
-
-        self.in_critical_block = true;
+        self.blocks.push(Block::Critical(tag.as_ref().to_owned()));
         self.writeln(format!(
             "// {}",
             serde_json::to_string(&ContextDirective {
@@ -632,11 +1225,7 @@ impl CachingContext {
     }
 
     pub fn end_critical_block<S: AsRef<str>>(&mut self, tag: S) -> Result<()> {
-        if !self.in_critical_block {
-            panic!("Not inside of a critical block.");
-        }
-
-        self.in_critical_block = false;
+        self.end_block(Block::Critical(tag.as_ref().to_owned()))?;
         self.writeln(format!(
             "// {}",
             serde_json::to_string(&ContextDirective {
@@ -651,6 +1240,112 @@ impl CachingContext {
         Ok(())
     }
 
+    /// Start a region for the user's code
+    ///
+    /// Whatever they put between here and [`end_user_region`](Self::end_user_region)
+    /// is carried along when the file is regenerated, even if the region moves. If
+    /// there's an `id`, that's how we find it again, so that it survives being
+    /// renamed. Otherwise it's by `name`. Either way, they need to be unique in the
+    /// file.
+    pub fn begin_user_region<S: AsRef<str>>(&mut self, name: S, id: Option<Uuid>) -> Result<()> {
+        self.blocks.push(Block::User(name.as_ref().to_owned()));
+        self.writeln(format!(
+            "// {}",
+            serde_json::to_string(&ContextDirective {
+                magic: MAGIC.to_owned(),
+                kind: DirectiveKind::UserRegionBegin(UserRegionBegin {
+                    name: name.as_ref().to_owned(),
+                    id,
+                }),
+            })
+            .context(SerdeJsonBombedSnafu)?
+        ));
+
+        Ok(())
+    }
+
+    pub fn end_user_region<S: AsRef<str>>(&mut self, name: S) -> Result<()> {
+        self.end_block(Block::User(name.as_ref().to_owned()))?;
+        self.writeln(format!(
+            "// {}",
+            serde_json::to_string(&ContextDirective {
+                magic: MAGIC.to_owned(),
+                kind: DirectiveKind::UserRegionEnd {
+                    name: name.as_ref().to_owned()
+                },
+            })
+            .context(SerdeJsonBombedSnafu)?
+        ));
+
+        Ok(())
+    }
+
+    /// An empty user region, which is how they usually start out
+    pub fn user_region<S: AsRef<str>>(&mut self, name: S, id: Option<Uuid>) -> Result<()> {
+        self.begin_user_region(name.as_ref(), id)?;
+        self.end_user_region(name)
+    }
+
+    /// Start some code that was generated from `id`, in the model
+    ///
+    /// This is for the source map. The directives are gone by the time the file is
+    /// written, they only need to survive rustfmt. So they need to go on a line of
+    /// their own, someplace that won't change the layout: around items, fields,
+    /// variants, that sort of thing. And not in anything that gets a prefix, like
+    /// a doc test.
+    pub fn begin_element<S: AsRef<str>>(
+        &mut self,
+        construct: Construct,
+        kind: ElementKind,
+        id: Uuid,
+        name: S,
+    ) -> Result<()> {
+        self.blocks.push(Block::Element(id));
+        self.writeln(format!(
+            "// {}",
+            serde_json::to_string(&ContextDirective {
+                magic: MAGIC.to_owned(),
+                kind: DirectiveKind::ElementBegin(Element {
+                    construct,
+                    kind,
+                    id,
+                    name: name.as_ref().to_owned(),
+                }),
+            })
+            .context(SerdeJsonBombedSnafu)?
+        ));
+
+        Ok(())
+    }
+
+    pub fn end_element(&mut self, id: Uuid) -> Result<()> {
+        self.end_block(Block::Element(id))?;
+        self.writeln(format!(
+            "// {}",
+            serde_json::to_string(&ContextDirective {
+                magic: MAGIC.to_owned(),
+                kind: DirectiveKind::ElementEnd { id },
+            })
+            .context(SerdeJsonBombedSnafu)?
+        ));
+
+        Ok(())
+    }
+
+    /// Close the innermost block, so long as it's `block`
+    fn end_block(&mut self, block: Block) -> Result<()> {
+        match self.blocks.last() {
+            Some(open) if *open == block => {
+                self.blocks.pop();
+                Ok(())
+            }
+            open => Err(Error(CodeGenError::MismatchedBlock {
+                closing: block.to_string(),
+                open: open.map_or("nothing".to_owned(), |b| b.to_string()),
+            })),
+        }
+    }
+
     pub fn writeln<S: AsRef<str>>(&mut self, data: S) {
         self.write(data);
         // I figured out what this is about. It's to get rid of spaces at the
@@ -711,11 +1406,16 @@ impl Deref for CachingContext {
 }
 
 impl AddAssign for CachingContext {
-    fn add_assign(&mut self, rhs: Self) {
-        // First we need to copy over the symbol table.
-        for (k, v) in &rhs.symbol_table {
-            self.symbol_table.insert(*k, v.clone());
-        }
+    fn add_assign(&mut self, mut rhs: Self) {
+        // First we need to copy over the symbol table. Names that collide are
+        // remembered, and reported later.
+        let collisions = self.symbols.merge(mem::take(&mut rhs.symbols));
+        self.collisions.extend(collisions);
+        self.collisions.append(&mut rhs.collisions);
+
+        // Anything left open over there is open over here now, and it's up to us
+        // to close it.
+        self.blocks.extend(rhs.blocks.iter().cloned());
 
         // Next we copy the buffer's contents. We do so using our write methods.
         // Doing so ensures indentation is maintained. It's not without it's
@@ -754,3 +1454,367 @@ fn parse_directive(line: &str) -> Option<ContextDirective> {
         None
     }
 }
+
+/// A block that the new buffer has open, while merging
+///
+/// Only blocks that are in the original file too are live. The rest are brand new,
+/// and there's nothing to merge them with.
+struct OpenBlock {
+    kind: OpenKind,
+    live: bool,
+}
+
+#[derive(PartialEq)]
+enum OpenKind {
+    Critical(String),
+    Ignore { delete: bool },
+}
+
+impl OpenBlock {
+    /// Open, or close, a block for `directive`
+    ///
+    /// Returns the block that was closed, if it was an ending sort of directive.
+    fn track(
+        open: &mut Vec<Self>,
+        directive: ContextDirective,
+        live: bool,
+        ignore_ignore: bool,
+    ) -> Option<Self> {
+        match directive.kind {
+            DirectiveKind::IgnoreBlockBegin(i) => {
+                // Uber ignore blocks stay put, ignore_ignore or not.
+                let delete = ignore_ignore && !i.is_uber();
+                if live && delete {
+                    debug!("ignore_ignore");
+                }
+                open.push(OpenBlock {
+                    kind: OpenKind::Ignore { delete },
+                    live,
+                });
+                None
+            }
+            DirectiveKind::CriticalBlockBegin(cb) => {
+                open.push(OpenBlock {
+                    kind: OpenKind::Critical(cb.tag),
+                    live,
+                });
+                None
+            }
+            DirectiveKind::IgnoreBlockEnd | DirectiveKind::CriticalBlockEnd { .. } => open.pop(),
+            // User regions aren't blocks, as far as merging goes. They are taken
+            // care of before we get here. Elements are gone by then.
+            DirectiveKind::UserRegionBegin(_)
+            | DirectiveKind::UserRegionEnd { .. }
+            | DirectiveKind::ElementBegin(_)
+            | DirectiveKind::ElementEnd { .. } => None,
+        }
+    }
+}
+
+/// A user region, and what's in it, from the original file
+struct UserRegion<'a> {
+    name: String,
+    id: Option<Uuid>,
+    lines: Vec<&'a str>,
+}
+
+/// Put the contents of the user regions in `orig` into the same regions in
+/// `formatted`
+///
+/// Returns the new buffer, and the names of the regions in the original that found
+/// a new home. Regions that didn't are left where they are, for the merge to deal
+/// with like any other code.
+fn carry_user_regions(orig: &str, formatted: &str) -> (String, HashSet<String>) {
+    let mut regions: Vec<UserRegion> = Vec::new();
+    let mut current: Option<UserRegion> = None;
+    for line in orig.lines() {
+        match parse_directive(line).map(|d| d.kind) {
+            Some(DirectiveKind::UserRegionBegin(begin)) => {
+                current = Some(UserRegion {
+                    name: begin.name,
+                    id: begin.id,
+                    lines: Vec::new(),
+                })
+            }
+            Some(DirectiveKind::UserRegionEnd { .. }) => regions.extend(current.take()),
+            _ => {
+                if let Some(ref mut region) = current {
+                    region.lines.push(line);
+                }
+            }
+        }
+    }
+
+    let mut carried = HashSet::new();
+    let mut buffer = String::new();
+    let mut skipping = false;
+    for line in formatted.lines() {
+        match parse_directive(line).map(|d| d.kind) {
+            Some(DirectiveKind::UserRegionBegin(begin)) => {
+                buffer = buffer + line + "\n";
+
+                let found = regions
+                    .iter()
+                    .filter(|r| !carried.contains(&r.name))
+                    .find(|r| r.id.is_some() && r.id == begin.id)
+                    .or_else(|| {
+                        regions
+                            .iter()
+                            .filter(|r| !carried.contains(&r.name))
+                            .find(|r| r.name == begin.name)
+                    });
+
+                if let Some(region) = found {
+                    if region.name != begin.name {
+                        debug!("user region `{}` is now `{}`", region.name, begin.name);
+                    }
+                    for line in &region.lines {
+                        buffer = buffer + line + "\n";
+                    }
+                    carried.insert(region.name.clone());
+                    // Whatever we generated in there is replaced by what they had.
+                    skipping = true;
+                }
+            }
+            Some(DirectiveKind::UserRegionEnd { .. }) => {
+                buffer = buffer + line + "\n";
+                skipping = false;
+            }
+            _ => {
+                if !skipping {
+                    buffer = buffer + line + "\n";
+                }
+            }
+        }
+    }
+
+    for region in regions.iter().filter(|r| !carried.contains(&r.name)) {
+        warn!(
+            "⚠️ user region `{}` isn't generated any more, leaving it be",
+            region.name
+        );
+    }
+
+    (buffer, carried)
+}
+
+/// Keep track of the user region that we are in, in the original file
+///
+/// Returns true if the line belongs to a region that was carried.
+fn track_user_region(region: &mut Option<bool>, line: &str, carried: &HashSet<String>) -> bool {
+    match parse_directive(line).map(|d| d.kind) {
+        Some(DirectiveKind::UserRegionBegin(begin)) => {
+            let is_carried = carried.contains(&begin.name);
+            *region = Some(is_carried);
+            is_carried
+        }
+        Some(DirectiveKind::UserRegionEnd { .. }) => region.take().unwrap_or(false),
+        _ => region.unwrap_or(false),
+    }
+}
+
+/// Take the element directives out of `formatted`, and remember where they were
+///
+/// An element doesn't start, or end, with a blank line, or one of our directives.
+/// Those aren't anything that the compiler is going to complain about.
+///
+/// Taking out a line can leave two blank lines in a row, where rustfmt only allows
+/// one. The second one goes too, so that the output is just what it would have
+/// been without the directives.
+fn strip_elements(formatted: &str) -> (String, Vec<Mapped>) {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut open: Vec<(Element, usize)> = Vec::new();
+    let mut elements = Vec::new();
+    let mut stripped = false;
+    for line in formatted.lines() {
+        match parse_directive(line).map(|d| d.kind) {
+            Some(DirectiveKind::ElementBegin(element)) => {
+                open.push((element, lines.len() + 1));
+                stripped = true;
+            }
+            Some(DirectiveKind::ElementEnd { .. }) => {
+                if let Some((element, mut start)) = open.pop() {
+                    let mut end = lines.len();
+                    let filler = |l: &str| l.trim().is_empty() || parse_directive(l).is_some();
+                    while start <= end && filler(lines[start - 1]) {
+                        start += 1;
+                    }
+                    while end >= start && filler(lines[end - 1]) {
+                        end -= 1;
+                    }
+                    if start <= end {
+                        elements.push(Mapped {
+                            element,
+                            start,
+                            end,
+                        });
+                    }
+                }
+                stripped = true;
+            }
+            _ => {
+                let blank = line.trim().is_empty();
+                if !(stripped && blank && lines.last().is_none_or(|l| l.trim().is_empty())) {
+                    lines.push(line);
+                }
+                stripped = false;
+            }
+        }
+    }
+
+    // Outer ones first
+    elements.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut buffer = lines.join("\n");
+    if formatted.ends_with('\n') {
+        buffer.push('\n');
+    }
+
+    (buffer, elements)
+}
+
+/// Is this a header that we wrote?
+fn is_header(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix("//")
+        .and_then(|l| serde_json::from_str::<ContextHeader>(l).ok())
+        .is_some_and(|h| h.magic == MAGIC)
+}
+
+/// Is this a directive that got commented out, back before we knew better?
+fn is_displaced_directive(line: &str) -> bool {
+    line.trim_end()
+        .strip_suffix("//⚡️")
+        .and_then(|l| l.trim_start().strip_prefix("//"))
+        .is_some_and(|l| parse_directive(l).is_some())
+}
+
+/// Lines of context around each hunk, like `diff -u`
+const DIFF_CONTEXT: usize = 3;
+
+/// A unified diff, for humans and CI logs
+///
+/// `diff::lines` does the hard part. This just groups the changes into hunks, with
+/// some context, and puts the `@@` headers on them.
+fn unified_diff(path: &Path, old: &str, new: &str) -> String {
+    // (tag, line, old line number, new line number), numbers are one past the line.
+    let mut lines = Vec::new();
+    let (mut old_line, mut new_line) = (0, 0);
+    for result in diff::lines(old, new) {
+        match result {
+            diff::Result::Left(l) => {
+                old_line += 1;
+                lines.push(('-', l, old_line, new_line));
+            }
+            diff::Result::Both(l, _) => {
+                old_line += 1;
+                new_line += 1;
+                lines.push((' ', l, old_line, new_line));
+            }
+            diff::Result::Right(r) => {
+                new_line += 1;
+                lines.push(('+', r, old_line, new_line));
+            }
+        }
+    }
+
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, ..))| *tag != ' ')
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path.display(), path.display());
+    let mut iter = changes.iter().peekable();
+    while let Some(first) = iter.next() {
+        let mut last = *first;
+        while let Some(next) = iter.peek() {
+            if **next - last > 2 * DIFF_CONTEXT {
+                break;
+            }
+            last = *iter.next().unwrap();
+        }
+
+        let start = first.saturating_sub(DIFF_CONTEXT);
+        let end = (last + DIFF_CONTEXT + 1).min(lines.len());
+        let hunk = &lines[start..end];
+
+        let old_count = hunk.iter().filter(|(tag, ..)| *tag != '+').count();
+        let new_count = hunk.iter().filter(|(tag, ..)| *tag != '-').count();
+        // Where the hunk starts is the line before the first one in it, plus one,
+        // unless the hunk is empty on that side. Then it's just the line before.
+        let (old_before, new_before) = if start == 0 {
+            (0, 0)
+        } else {
+            (lines[start - 1].2, lines[start - 1].3)
+        };
+        let old_start = if old_count == 0 {
+            old_before
+        } else {
+            old_before + 1
+        };
+        let new_start = if new_count == 0 {
+            new_before
+        } else {
+            new_before + 1
+        };
+
+        out += &format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_count, new_start, new_count
+        );
+        for (tag, line, ..) in hunk {
+            out.push(*tag);
+            out += line;
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Collects displaced lines into regions
+///
+/// Lines that are next to each other, give or take blank lines, in the same block,
+/// and displaced the same way, are one region.
+struct Displacements<'a> {
+    orig: Vec<&'a str>,
+    regions: Vec<Displaced>,
+}
+
+impl<'a> Displacements<'a> {
+    fn new(orig: &'a str) -> Self {
+        Self {
+            orig: orig.split('\n').collect(),
+            regions: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, tag: &str, kind: Displacement, line: usize, text: &str) {
+        if let Some(last) = self.regions.last_mut() {
+            let end = *last.lines.end();
+            if last.tag == tag
+                && last.kind == kind
+                && self.orig[end..line - 1].iter().all(|l| l.trim().is_empty())
+            {
+                last.lines = *last.lines.start()..=line;
+                last.text.push('\n');
+                last.text.push_str(text);
+                return;
+            }
+        }
+
+        self.regions.push(Displaced {
+            path: PathBuf::new(),
+            tag: tag.to_owned(),
+            kind,
+            lines: line..=line,
+            text: text.to_owned(),
+        });
+    }
+
+    fn into_inner(self) -> Vec<Displaced> {
+        self.regions
+    }
+}
@@ -0,0 +1,407 @@
+//! Formatting Generated Code
+//!
+//! The templates don't try very hard to make pretty code. They used to rely on
+//! `rustfmt` to sort it out, which was great until `rustfmt` wasn't installed, or
+//! a new toolchain formatted things differently, or the build was sandboxed and
+//! couldn't spawn anything at all.
+//!
+//! So now it's pluggable. There's the built-in pretty-printer, which doesn't need
+//! anything, any command that reads Rust on stdin and writes it on stdout, which
+//! is how `rustfmt` is run, and nothing at all.
+//!
+//! Whichever it is, if it chokes on what we generated, the error includes the bit
+//! of generated code that it choked on.
+use std::{
+    fmt,
+    io::Write,
+    process::{Command, Stdio},
+    str::FromStr,
+    thread,
+};
+
+use log::trace;
+use snafu::prelude::*;
+
+use crate::codegen::{BadnessSnafu, CodeGenError, Error, Result, SpawnFormatterSnafu};
+
+/// How many lines either side of the problem go in the snippet
+const CONTEXT_LINES: usize = 3;
+
+const INDENT: &str = "    ";
+
+/// Something that formats generated code
+///
+/// The default is `rustfmt`, because that's what we've always used. Parse one
+/// from a string: `builtin`, `none`, or a command line, e.g., `rustfmt --edition
+/// 2021`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Formatter {
+    /// The in-process pretty-printer
+    ///
+    /// It fixes indentation and blank lines, and that's about it. It doesn't
+    /// wrap long lines, or add trailing commas, or any of the other things that
+    /// `rustfmt` does. It also can't fail, unless the delimiters don't match.
+    Builtin,
+    /// Run a program
+    ///
+    /// The code goes in on stdin, and the formatted code comes out on stdout.
+    Command { program: String, args: Vec<String> },
+    /// Leave the code as it is
+    None,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::rustfmt()
+    }
+}
+
+impl fmt::Display for Formatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin => write!(f, "builtin"),
+            Self::Command { program, args } => {
+                write!(f, "{}", program)?;
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                Ok(())
+            }
+            Self::None => write!(f, "none"),
+        }
+    }
+}
+
+impl FromStr for Formatter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "builtin" => Ok(Self::Builtin),
+            "none" => Ok(Self::None),
+            command => {
+                let mut words = command.split_whitespace().map(|w| w.to_owned());
+                match words.next() {
+                    Some(program) => Ok(Self::Command {
+                        program,
+                        args: words.collect(),
+                    }),
+                    None => Err(Error(CodeGenError::BadFormatter { spec: s.to_owned() })),
+                }
+            }
+        }
+    }
+}
+
+impl Formatter {
+    pub fn rustfmt() -> Self {
+        Self::Command {
+            program: "rustfmt".to_owned(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn format(&self, buffer: &str) -> Result<String> {
+        trace!("formatting output buffer with {}", self);
+        match self {
+            Self::Builtin => pretty_print(buffer),
+            Self::Command { program, args } => self.run(program, args, buffer),
+            Self::None => Ok(buffer.to_owned()),
+        }
+    }
+
+    fn run(&self, program: &str, args: &[String], buffer: &str) -> Result<String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(SpawnFormatterSnafu { program })?;
+
+        // Feed it from another thread, or a big file fills up the pipe to stdout
+        // while we are still stuck writing to stdin.
+        let mut stdin = child.stdin.take().unwrap();
+        let input = buffer.to_owned();
+        let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let output = child.wait_with_output().context(BadnessSnafu)?;
+        // If it quit without reading everything, that's what the status is for.
+        let _ = writer.join();
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let (line, column) = error_location(&stderr).unzip();
+            let message = stderr
+                .lines()
+                .find_map(|l| l.strip_prefix("error"))
+                .map(|l| l.split_once(": ").map_or(l, |(_, m)| m).to_owned())
+                .unwrap_or_else(|| match output.status.code() {
+                    Some(code) => format!("exit code {}", code),
+                    None => "killed by a signal".to_owned(),
+                });
+
+            Err(self.error(buffer, message, line, column.flatten()))
+        }
+    }
+
+    fn error(
+        &self,
+        buffer: &str,
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    ) -> Error {
+        Error(CodeGenError::Format {
+            formatter: self.to_string(),
+            message,
+            line,
+            snippet: line.map_or_else(String::new, |l| snippet(buffer, l, column)),
+        })
+    }
+}
+
+/// Find the first ` --> file:line:column` in a compiler style error message
+fn error_location(stderr: &str) -> Option<(usize, Option<usize>)> {
+    stderr.lines().find_map(|l| {
+        let (_, location) = l.split_once("--> ")?;
+        let mut parts = location.trim().rsplitn(3, ':');
+        let column = parts.next()?.parse().ok();
+        let line = parts.next()?.parse().ok()?;
+
+        Some((line, column))
+    })
+}
+
+/// The lines around `line` in `buffer`, in the style of the compiler
+///
+/// Lines count from one.
+fn snippet(buffer: &str, line: usize, column: Option<usize>) -> String {
+    let lines = buffer.lines().collect::<Vec<_>>();
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line + CONTEXT_LINES).min(lines.len());
+
+    let gutter = " ".repeat(last.to_string().len());
+    let mut snippet = format!("{} |", gutter);
+    for n in first..=last {
+        let marker = if n == line { ">" } else { " " };
+        snippet += &format!(
+            "\n{:>width$}{}| {}",
+            n,
+            marker,
+            lines[n - 1],
+            width = gutter.len()
+        );
+        if let (true, Some(column)) = (n == line, column) {
+            snippet += &format!("\n{} | {}^", gutter, " ".repeat(column.saturating_sub(1)));
+        }
+    }
+
+    snippet
+}
+
+/// Where the lexer is, between lines
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Lexer {
+    Code,
+    /// In a string, with the number of hashes if it's raw
+    Str(Option<usize>),
+    /// In a block comment, nested this deep
+    Comment(usize),
+}
+
+/// The built-in formatter
+///
+/// Indent by how deeply nested in delimiters each line is, trim the ends, and
+/// tidy the blank lines. Lines that start inside of a string are left be.
+fn pretty_print(buffer: &str) -> Result<String> {
+    // The open delimiters, and where they are, for when they don't match.
+    let mut open: Vec<(char, usize, usize)> = Vec::new();
+    let mut state = Lexer::Code;
+    // Each line, and whether it's verbatim.
+    let mut lines: Vec<(String, bool)> = Vec::new();
+
+    for (n, raw) in buffer.lines().enumerate() {
+        let n = n + 1;
+
+        if let Lexer::Str(_) = state {
+            // Whitespace in a string is significant.
+            lines.push((raw.to_owned(), true));
+        } else {
+            let line = raw.trim();
+            let closers = line
+                .chars()
+                .take_while(|c| matches!(c, '}' | ']' | ')'))
+                .count();
+            let mut depth = open.len().saturating_sub(closers);
+            // Method chains hang off of the line before.
+            if line.starts_with('.') && !line.starts_with("..") {
+                depth += 1;
+            }
+
+            if line.is_empty() {
+                lines.push((String::new(), false));
+            } else {
+                lines.push((INDENT.repeat(depth) + line, false));
+            }
+        }
+
+        let chars = raw.chars().collect::<Vec<_>>();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            match state {
+                Lexer::Str(hashes) => match (c, hashes) {
+                    ('\\', None) => i += 1,
+                    ('"', None) => state = Lexer::Code,
+                    ('"', Some(h))
+                        if chars[i + 1..].iter().take_while(|&&c| c == '#').count() >= h =>
+                    {
+                        i += h;
+                        state = Lexer::Code;
+                    }
+                    _ => {}
+                },
+                Lexer::Comment(nesting) => match (c, next) {
+                    ('*', Some('/')) => {
+                        i += 1;
+                        state = if nesting == 1 {
+                            Lexer::Code
+                        } else {
+                            Lexer::Comment(nesting - 1)
+                        };
+                    }
+                    ('/', Some('*')) => {
+                        i += 1;
+                        state = Lexer::Comment(nesting + 1);
+                    }
+                    _ => {}
+                },
+                Lexer::Code => match c {
+                    '/' if next == Some('/') => break,
+                    '/' if next == Some('*') => {
+                        i += 1;
+                        state = Lexer::Comment(1);
+                    }
+                    '"' => state = Lexer::Str(None),
+                    'r' | 'b'
+                        if is_raw_string(&chars[i..])
+                            && !is_ident(chars.get(i.wrapping_sub(1))) =>
+                    {
+                        let start = chars[i..].iter().position(|&c| c == '"').unwrap();
+                        let hashes = chars[i..i + start].iter().filter(|&&c| c == '#').count();
+                        i += start;
+                        state = Lexer::Str(Some(hashes));
+                    }
+                    '\'' => i += char_literal(&chars[i..]),
+                    '{' | '[' | '(' => open.push((c, n, i + 1)),
+                    '}' | ']' | ')' => {
+                        let column = Some(i + 1);
+                        match open.pop() {
+                            Some((o, _, _)) if matching(o) == c => {}
+                            Some((o, l, col)) => {
+                                return Err(Formatter::Builtin.error(
+                                    buffer,
+                                    format!(
+                                        "`{}` doesn't match the `{}` at line {}, column {}",
+                                        c, o, l, col
+                                    ),
+                                    Some(n),
+                                    column,
+                                ))
+                            }
+                            None => {
+                                return Err(Formatter::Builtin.error(
+                                    buffer,
+                                    format!("unexpected `{}`", c),
+                                    Some(n),
+                                    column,
+                                ))
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+            }
+            i += 1;
+        }
+    }
+
+    if let Some((o, l, col)) = open.pop() {
+        return Err(Formatter::Builtin.error(
+            buffer,
+            format!("unclosed `{}`", o),
+            Some(l),
+            Some(col),
+        ));
+    }
+
+    // No blank lines at the start or end of the file, or a block, or two in a row.
+    let mut output = String::new();
+    let mut blank = false;
+    for (i, (line, verbatim)) in lines.iter().enumerate() {
+        if *verbatim {
+            if blank {
+                output.push('\n');
+            }
+        } else if line.is_empty() {
+            blank = true;
+            continue;
+        } else if blank && !output.is_empty() && !opens_block(&lines[..i]) && !closes_block(line) {
+            output.push('\n');
+        }
+        blank = false;
+        output += line;
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+fn matching(open: char) -> char {
+    match open {
+        '{' => '}',
+        '[' => ']',
+        _ => ')',
+    }
+}
+
+fn is_ident(c: Option<&char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || *c == '_')
+}
+
+/// `r"`, `r#"`, `br"`, and friends
+fn is_raw_string(chars: &[char]) -> bool {
+    let rest = match chars {
+        ['b', 'r', rest @ ..] | ['r', rest @ ..] => rest,
+        _ => return false,
+    };
+    let hashes = rest.iter().take_while(|&&c| c == '#').count();
+
+    rest.get(hashes) == Some(&'"')
+}
+
+/// How many more characters a char literal takes, or zero for a lifetime
+fn char_literal(chars: &[char]) -> usize {
+    match chars {
+        ['\'', '\\', rest @ ..] => rest.iter().position(|&c| c == '\'').map_or(0, |p| p + 2),
+        ['\'', _, '\'', ..] => 2,
+        _ => 0,
+    }
+}
+
+/// Does the last non-blank line open a block?
+fn opens_block(lines: &[(String, bool)]) -> bool {
+    lines
+        .iter()
+        .rev()
+        .find(|(l, _)| !l.is_empty())
+        .is_some_and(|(l, verbatim)| !verbatim && l.ends_with('{'))
+}
+
+fn closes_block(line: &str) -> bool {
+    line.trim_start().starts_with('}')
+}
@@ -0,0 +1,124 @@
+//! Source Maps for Generated Code
+//!
+//! When the compiler complains about something in generated code, the thing that
+//! really needs fixing is usually in the model. A source map says which model
+//! element each chunk of generated code came from, so that an editor (or a bot)
+//! can jump straight there.
+//!
+//! The map lives next to the file that it's for, in a sidecar JSON file. The
+//! templates mark where things come from as they go, and [`Context`] works out
+//! where those marks ended up once the file has been formatted and merged.
+//!
+//! [`Context`]: crate::codegen::Context
+use std::path::{Path, PathBuf};
+
+use diff;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What sort of model element some code came from
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ElementKind {
+    Object,
+    Attribute,
+    Relationship,
+    Enumeration,
+}
+
+/// What sort of code was generated for it
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Construct {
+    Struct,
+    Enum,
+    Variant,
+    Field,
+    New,
+    Macro,
+}
+
+/// A model element, and the code generated for it
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Element {
+    pub construct: Construct,
+    pub kind: ElementKind,
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// An [`Element`], and where it is in the file
+///
+/// Lines count from one, and `end` is inclusive, just like the compiler does it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Mapped {
+    #[serde(flatten)]
+    pub element: Element,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The contents of a sidecar file
+///
+/// Elements are sorted by where they start, outer ones first. So the last one
+/// that contains a line is the most specific thing that there is to say about it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SourceMap {
+    /// The file that this is a map of, relative to the map
+    pub file: PathBuf,
+    /// The nut that generated both of them
+    pub version: String,
+    pub elements: Vec<Mapped>,
+}
+
+impl SourceMap {
+    /// The elements that generated `line`, innermost last
+    pub fn lookup(&self, line: usize) -> impl Iterator<Item = &Mapped> {
+        self.elements
+            .iter()
+            .filter(move |m| m.start <= line && line <= m.end)
+    }
+}
+
+/// Where the source map for `path` goes
+///
+/// `src/types.rs` gets `src/types.map.json`.
+pub fn sidecar_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().with_extension("map.json")
+}
+
+/// Move elements mapped against `from`, so that they are mapped against `to`
+///
+/// `to` is `from` after merging with whatever was already on disk. So there may be
+/// user code in there, and commented out lines, and what have you. A line diff
+/// sorts all that out. Anything that's in the user's code, rather than ours, is
+/// just in the middle of an element. An element that doesn't have a single line
+/// left is dropped.
+pub(crate) fn remap(elements: Vec<Mapped>, from: &str, to: &str) -> Vec<Mapped> {
+    if from == to {
+        return elements;
+    }
+
+    // `lines[n]` is where line `n + 1` of `from` went.
+    let mut lines = Vec::new();
+    let mut to_line = 0;
+    for d in diff::lines(from, to) {
+        match d {
+            diff::Result::Both(_, _) => {
+                to_line += 1;
+                lines.push(Some(to_line));
+            }
+            diff::Result::Left(_) => lines.push(None),
+            diff::Result::Right(_) => to_line += 1,
+        }
+    }
+
+    elements
+        .into_iter()
+        .filter_map(|m| {
+            let moved = |l: usize| lines.get(l - 1).copied().flatten();
+            let start = (m.start..=m.end).find_map(moved)?;
+            let end = (m.start..=m.end).rev().find_map(moved)?;
+
+            Some(Mapped { start, end, ..m })
+        })
+        .collect()
+}
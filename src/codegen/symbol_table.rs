@@ -0,0 +1,182 @@
+//! Symbol Tables for Code Generation
+//!
+//! Symbols used to go in a flat `HashMap`, keyed by `Uuid`, and merging two of
+//! them just overwrote whatever was there. That's fine for the anonymous values
+//! that get passed around when rendering calls to `new`. It's not fine for names
+//! that end up in the generated code. Two attributes that turn into the same
+//! identifier make a struct that doesn't compile, and the error is a long way from
+//! the model that caused it.
+//!
+//! So now there are scopes, and names. A named symbol is _declared_, and declaring
+//! a name that's already in the innermost scope, for some other model element, is
+//! a [`CodeGenError::SymbolCollision`].
+use std::collections::HashMap;
+
+use log::trace;
+use uuid::Uuid;
+
+use crate::codegen::{CodeGenError, Error, Result};
+
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    pub value: String,
+    pub value_type: String,
+    pub is_reference: bool,
+}
+
+/// A declared name, and who declared it
+#[derive(Clone, Debug)]
+struct Declaration {
+    id: Uuid,
+    origin: String,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Scope {
+    symbols: HashMap<Uuid, Symbol>,
+    names: HashMap<String, Declaration>,
+}
+
+impl Scope {
+    /// Drop the name that `id` was declared with, if it was
+    fn forget_name(&mut self, id: &Uuid) {
+        if let Some(symbol) = self.symbols.get(id) {
+            if self.names.get(&symbol.value).map(|d| d.id) == Some(*id) {
+                self.names.remove(&symbol.value);
+            }
+        }
+    }
+}
+
+/// Symbols, in nested scopes
+///
+/// There's always a global scope, at the bottom. Lookups start at the innermost
+/// scope and work their way out, so inner names shadow outer ones. That's
+/// allowed. Two of the same name in the same scope is not.
+#[derive(Clone, Debug)]
+pub struct SymbolTable {
+    /// Innermost last
+    scopes: Vec<Scope>,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![Scope::default()],
+        }
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    /// Close the innermost scope, and forget what's in it
+    pub fn pop_scope(&mut self) -> Result<()> {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
+            Ok(())
+        } else {
+            Err(Error(CodeGenError::PoppedGlobalScope))
+        }
+    }
+
+    /// How many scopes deep we are, counting the global one
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Add a symbol that doesn't have a name, in the innermost scope
+    ///
+    /// These are values, like `42`, or `Some(&foo)`. There's nothing to collide
+    /// with.
+    pub fn insert(&mut self, id: Uuid, symbol: Symbol) {
+        trace!("interring symbol {} as {}", symbol.value, id);
+        let scope = self.innermost();
+        scope.forget_name(&id);
+        scope.symbols.insert(id, symbol);
+    }
+
+    /// Add a symbol whose value is a name in the generated code
+    ///
+    /// `origin` says what in the model it came from, e.g., "attribute `type`", for
+    /// when it collides with something.
+    pub fn declare<S: AsRef<str>>(&mut self, id: Uuid, origin: S, symbol: Symbol) -> Result<()> {
+        trace!("declaring symbol {} as {}", symbol.value, id);
+        let scope = self.innermost();
+        match scope.names.get(&symbol.value) {
+            Some(existing) if existing.id != id => {
+                return Err(Error(CodeGenError::SymbolCollision {
+                    name: symbol.value,
+                    first: existing.origin.clone(),
+                    second: origin.as_ref().to_owned(),
+                }));
+            }
+            _ => {}
+        }
+
+        // Declared again, under a new name. The old one is free for the taking.
+        scope.forget_name(&id);
+        scope.names.insert(
+            symbol.value.clone(),
+            Declaration {
+                id,
+                origin: origin.as_ref().to_owned(),
+            },
+        );
+        scope.symbols.insert(id, symbol);
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<&Symbol> {
+        trace!("exhuming symbol at {}", id);
+        self.scopes.iter().rev().find_map(|s| s.symbols.get(id))
+    }
+
+    pub fn get_by_name<S: AsRef<str>>(&self, name: S) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|s| {
+            s.names
+                .get(name.as_ref())
+                .and_then(|d| s.symbols.get(&d.id))
+        })
+    }
+
+    /// Pull everything in `other` into our innermost scope
+    ///
+    /// Whatever scopes `other` has are flattened, outer to inner. Every name that
+    /// collides is returned, rather than just the first, and the rest are merged
+    /// regardless.
+    pub fn merge(&mut self, other: SymbolTable) -> Vec<CodeGenError> {
+        let mut collisions = Vec::new();
+        for scope in other.scopes {
+            let mut named = HashMap::new();
+            for (name, declaration) in scope.names {
+                named.insert(declaration.id, (name, declaration.origin));
+            }
+
+            for (id, symbol) in scope.symbols {
+                match named.remove(&id) {
+                    Some((name, origin)) if name == symbol.value => {
+                        if let Err(Error(e)) = self.declare(id, origin, symbol) {
+                            collisions.push(e);
+                        }
+                    }
+                    _ => self.insert(id, symbol),
+                }
+            }
+        }
+
+        collisions
+    }
+
+    fn innermost(&mut self) -> &mut Scope {
+        // There's always the global scope.
+        self.scopes.last_mut().unwrap()
+    }
+}
@@ -3,6 +3,7 @@ use uuid::Uuid;
 
 use crate::codegen::{emitln, CachingContext};
 
+pub mod example;
 pub mod macros;
 pub mod types;
 
@@ -0,0 +1,67 @@
+//! Example Values for Doc Tests
+//!
+//! Doc tests need values to pass to `new`, and names to bind them to. These
+//! used to come from `names::Generator` and `random_string`, which meant that
+//! every time the code was generated the doc tests changed. So they went in
+//! ignore blocks, and you needed `ignore_ignore` to get new ones. Blech.
+//!
+//! Now the randomness is seeded from the model and the thing that we are
+//! generating the test for. Same model, same doc tests.
+//!
+//! The generator is [`ChaCha8Rng`], by name. `StdRng` is allowed to change
+//! between versions of `rand`, and every doc test would change with it.
+use names::{ADJECTIVES, NOUNS};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use uuid::Uuid;
+
+use crate::codegen::SarzakModel;
+
+const VAR_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Deterministic example values
+///
+/// Create one of these per doc test, and pass it down to everything that
+/// contributes to the test. The values that come out depend on the order in
+/// which they are asked for, which is fine, because so does the generated code.
+#[derive(Clone, Debug)]
+pub struct ExampleValues {
+    rng: ChaCha8Rng,
+}
+
+impl ExampleValues {
+    /// Seed a new generator from `model`, and `id`
+    ///
+    /// `id` is whatever the test is for, usually an object.
+    pub fn new(model: &SarzakModel, id: &Uuid) -> Self {
+        let mut seed = [0; 32];
+        seed[..16].copy_from_slice(model.id.as_bytes());
+        seed[16..].copy_from_slice(id.as_bytes());
+
+        Self {
+            rng: ChaCha8Rng::from_seed(seed),
+        }
+    }
+
+    /// A name, like `sleepy_panda`
+    ///
+    /// This is what `names::Generator` used to give us, snake cased. It's used
+    /// for variable names, so hyphens, as in `well-groomed`, won't do.
+    pub fn name(&mut self) -> String {
+        let adjective = ADJECTIVES.choose(&mut self.rng).unwrap();
+        let noun = NOUNS.choose(&mut self.rng).unwrap();
+
+        format!("{}_{}", adjective, noun).replace('-', "_")
+    }
+
+    /// An identifier based on `base`, like `dog_xyz`
+    ///
+    /// This is what `AS_RIDENT` used to render.
+    pub fn ident<S: AsRef<str>>(&mut self, base: S) -> String {
+        let suffix: String = (0..3)
+            .map(|_| VAR_CHARS[self.rng.gen_range(0..VAR_CHARS.len())] as char)
+            .collect();
+
+        format!("{}_{}", base.as_ref(), suffix)
+    }
+}
@@ -2,16 +2,17 @@
 //!
 //! This was pretty clean, and template like. Now I'm adding doc test generation
 //! and it's getting messy.
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
 use heck::ToSnakeCase;
+use uuid::Uuid;
 
 use crate::codegen::{
     begin_crit, create_arg_string, emitln, end_crit, function,
-    sarzak_maybe_get_one_r_sup_across_r14, template::types::emit_render_new, CachingContext,
-    Result, SarzakModel,
+    sarzak_maybe_get_one_r_sup_across_r14, template::types::emit_render_new, types::new_unwrap,
+    CachingContext, ExampleValues, Result, SarzakModel,
 };
-use crate::sarzak::{AttributeName, Object, AS_IDENT, AS_RIDENT, AS_TYPE};
+use crate::sarzak::{AttributeName, Object, AS_IDENT, AS_TYPE};
 
 /// Template for the main body of a generated macro
 ///
@@ -77,11 +78,9 @@ pub fn emit_binary_main(
     );
 
     if doc_tests {
-        context.begin_ignore_block()?;
         context += create_binary_macro_test(
             from, to, store, domain, package, name, backwards, many, cond,
         )?;
-        context.end_ignore_block()?;
     }
 
     emitln!(context, "#[macro_export]");
@@ -129,15 +128,19 @@ fn create_binary_macro_test(
     // comes from, and how to get at it.
     // We can generate both a from and a to, and sort it out there. It's easier
     // than grabbing all the fields and relationship and inspecting them first.
-    let mut from_use_statements = HashSet::new();
-    let mut to_use_statements = HashSet::new();
+    //
+    // There's more than one macro for each object, so the macro name goes into
+    // the seed too. Both sides share it, so that their names don't collide.
+    let mut values = ExampleValues::new(store, &Uuid::new_v5(&from.id, macro_name.as_bytes()));
+    let mut from_use_statements = BTreeSet::new();
+    let mut to_use_statements = BTreeSet::new();
     let (mut from_ctx, from_args) = emit_render_new(
         from,
         store,
         domain,
         package,
         &mut from_use_statements,
-        false,
+        &mut values,
         false,
     )?;
     let (mut to_ctx, to_args) = emit_render_new(
@@ -146,7 +149,7 @@ fn create_binary_macro_test(
         domain,
         package,
         &mut to_use_statements,
-        false,
+        &mut values,
         false,
     )?;
 
@@ -218,15 +221,16 @@ fn create_binary_macro_test(
                 // instantiate the from object
                 emitln!(
                     context,
-                    "/// let {} = {}::new(&mut store{});",
+                    "/// let {} = {}::new(&mut store{}){};",
                     from.render(AS_IDENT),
                     from.render(AS_TYPE),
-                    create_arg_string(&from_args, &context)
+                    create_arg_string(&from_args, &context),
+                    new_unwrap(from, store)
                 );
 
                 // invoke the macro
                 emitln!(context, "///");
-                let lhs = to.render(AS_RIDENT);
+                let lhs = values.ident(to.render(AS_IDENT));
                 emitln!(
                     context,
                     "/// let {} = {}!({}, store);",
@@ -283,14 +287,15 @@ fn create_binary_macro_test(
                 // Instantiate the to object
                 emitln!(
                     context,
-                    "/// let {} = {}::new(&mut store{});",
+                    "/// let {} = {}::new(&mut store{}){};",
                     to.render(AS_IDENT),
                     to.render(AS_TYPE),
-                    create_arg_string(&to_args, &context)
+                    create_arg_string(&to_args, &context),
+                    new_unwrap(to, store)
                 );
 
                 // Invoke the macro
-                let lhs = to.render(AS_RIDENT);
+                let lhs = values.ident(to.render(AS_IDENT));
                 emitln!(
                     context,
                     "/// let {} = {}!({}, store);",
@@ -372,24 +377,26 @@ fn create_binary_macro_test(
         if !backwards {
             emitln!(
                 context,
-                "/// let {} = {}::new(&mut store{});",
+                "/// let {} = {}::new(&mut store{}){};",
                 from.render(AS_IDENT),
                 from.render(AS_TYPE),
-                create_arg_string(&from_args, &context)
+                create_arg_string(&from_args, &context),
+                new_unwrap(from, store)
             );
         } else {
             emitln!(
                 context,
-                "/// let {} = {}::new(&mut store{});",
+                "/// let {} = {}::new(&mut store{}){};",
                 to.render(AS_IDENT),
                 to.render(AS_TYPE),
-                create_arg_string(&to_args, &context)
+                create_arg_string(&to_args, &context),
+                new_unwrap(to, store)
             );
         }
 
         emitln!(context, "///");
         // Render the macro call
-        let lhs = to.render(AS_RIDENT);
+        let lhs = values.ident(to.render(AS_IDENT));
         emitln!(
             context,
             "/// let {} = {}!({}, store);",
@@ -742,10 +749,8 @@ pub fn emit_associative_main(
     );
 
     if doc_tests {
-        let _ = context.begin_ignore_block();
         context +=
             create_associative_macro_test(to, ass, store, domain, package, name, cond, many)?;
-        let _ = context.end_ignore_block();
     }
 
     emitln!(context, "#[macro_export]");
@@ -787,14 +792,15 @@ fn create_associative_macro_test(
 ) -> Result<CachingContext> {
     let mut context = CachingContext::new();
 
-    let mut use_statements = HashSet::new();
+    let mut values = ExampleValues::new(store, &Uuid::new_v5(&assoc.id, macro_name.as_bytes()));
+    let mut use_statements = BTreeSet::new();
     let (mut ctx, args) = emit_render_new(
         assoc,
         store,
         domain,
         package,
         &mut use_statements,
-        false,
+        &mut values,
         false,
     )?;
 
@@ -820,10 +826,11 @@ fn create_associative_macro_test(
 
     emitln!(
         context,
-        "/// let {} = {}::new(&mut store{});",
+        "/// let {} = {}::new(&mut store{}){};",
         assoc.render(AS_IDENT),
         assoc.render(AS_TYPE),
-        create_arg_string(&args, &context)
+        create_arg_string(&args, &context),
+        new_unwrap(assoc, store)
     );
 
     let cheat = context.exhume_symbol(&args[0]).unwrap().clone();
@@ -831,7 +838,7 @@ fn create_associative_macro_test(
 
     emitln!(context, "///");
 
-    let lhs = to.render(AS_RIDENT);
+    let lhs = values.ident(to.render(AS_IDENT));
     let lhs_type = to.render(AS_TYPE);
 
     emitln!(
@@ -10,24 +10,27 @@
 //! I need another level of abstraction. Something I'll address in the next code
 //! generator. It'll be based off of this generated code, and I'm going to call
 //! it ✨grace✨🐶.
-use std::{collections::HashSet, env};
+use std::{collections::BTreeSet, env};
 
 use heck::ToSnakeCase;
 use log::{debug, trace};
-use names::Generator;
-use snafu::prelude::*;
 use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
 use crate::codegen::{
     create_arg_string, emit, emitln, func_name, get_referent,
-    sarzak_maybe_get_one_r_sup_across_r14, CachingContext, Field, LoadCuckooModelSnafu, Ref,
-    Result, SarzakModel, Symbol,
+    sarzak_maybe_get_one_r_sup_across_r14,
+    types::{
+        get_enum_subtypes, get_leaves, has_default, id_format_spec, is_multi_level, leaf_type,
+        new_unwrap, unit_id, Identity,
+    },
+    CachingContext, CodeGenError, Construct, ElementKind, Error, ExampleValues, Field,
+    IdentityPolicy, Ref, Result, SarzakModel, Symbol, Variant,
 };
 
 use crate::sarzak::{
-    Attribute, Conditionality, Object, RelSide, Relationship, AS_CONST, AS_IDENT, AS_RIDENT,
-    AS_TYPE, UUID_NS,
+    Attribute, Conditionality, Enumeration, Isa, Object, RelSide, Relationship, Type, AS_CONST,
+    AS_IDENT, AS_TYPE, UUID_NS,
 };
 
 const MAX_LEN: usize = 90;
@@ -105,6 +108,148 @@ pub fn emit_singleton(object: &Object) -> CachingContext {
     context
 }
 
+/// Generate a user defined enumeration
+///
+/// The first variant is the default, since the structs that use it need one.
+pub fn emit_enumeration(enumeration: &Enumeration) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+
+    context += emit_object_comments(&enumeration.description, "///");
+    begin_crit!(
+        context,
+        "{}-enumeration-definition",
+        enumeration.render(AS_IDENT)
+    )?;
+    context.begin_element(
+        Construct::Enum,
+        ElementKind::Enumeration,
+        enumeration.id,
+        enumeration.name.inner(),
+    )?;
+    emitln!(
+        context,
+        "#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]"
+    );
+    emitln!(context, "pub enum {} {{", enumeration.render(AS_TYPE));
+    context.increase_indent();
+    for (i, variant) in enumeration.variants.iter().enumerate() {
+        if i == 0 {
+            emitln!(context, "#[default]");
+        }
+        emitln!(context, "{},", variant.render(AS_TYPE));
+    }
+    context.decrease_indent();
+    emitln!(context, "}");
+    context.end_element(enumeration.id)?;
+    end_crit!(
+        context,
+        "{}-enumeration-definition",
+        enumeration.render(AS_IDENT)
+    )?;
+    emitln!(context, "");
+
+    Ok(context)
+}
+
+/// Generate the error that `new` returns when an instance is already taken
+///
+/// There's just the one of these per domain, no matter how many objects have
+/// identifiers. The `identifier` is the number from the model, so `*2` is `2`.
+/// It's `0` when it's just the `id` that's already in use.
+pub fn emit_duplicate_identifier() -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+
+    emitln!(
+        context,
+        "/// There's already an instance with the same identifier in the store"
+    );
+    begin_crit!(context, "duplicate_identifier-definition")?;
+    emitln!(context, "#[derive(Clone, Debug, PartialEq)]");
+    emitln!(context, "pub struct DuplicateIdentifier {");
+    context.increase_indent();
+    emitln!(context, "/// The object that was being created");
+    emitln!(context, "pub object: &'static str,");
+    emitln!(
+        context,
+        "/// The identifier that's taken, or `0` for the `id` itself"
+    );
+    emitln!(context, "pub identifier: u16,");
+    emitln!(context, "/// The `id` of the instance that's already there");
+    emitln!(context, "pub id: Uuid,");
+    context.decrease_indent();
+    emitln!(context, "}");
+    emitln!(context, "");
+    emitln!(context, "impl std::fmt::Display for DuplicateIdentifier {");
+    context.increase_indent();
+    emitln!(
+        context,
+        "fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {"
+    );
+    context.increase_indent();
+    emitln!(context, "match self.identifier {");
+    context.increase_indent();
+    emitln!(
+        context,
+        "0 => write!(f, \"there is already a {} with id {}\", self.object, self.id),"
+    );
+    emitln!(
+        context,
+        "n => write!(f, \"there is already a {} with identifier *{} ({})\", self.object, n, self.id),"
+    );
+    context.decrease_indent();
+    emitln!(context, "}");
+    context.decrease_indent();
+    emitln!(context, "}");
+    context.decrease_indent();
+    emitln!(context, "}");
+    emitln!(context, "");
+    emitln!(context, "impl std::error::Error for DuplicateIdentifier {}");
+    end_crit!(context, "duplicate_identifier-definition")?;
+    emitln!(context, "");
+
+    Ok(context)
+}
+
+/// What an enum is generated for
+///
+/// Usually it's a supertype, and the enum is named for it. A supertype that's in
+/// more than one isa relationship gets an enum for each of them instead, named
+/// for the object and the relationship, e.g., `AnimalR1`. See
+/// [`emit_generalization`].
+struct EnumSource {
+    type_name: String,
+    ident: String,
+    kind: ElementKind,
+    id: Uuid,
+    name: String,
+    /// Only the supertype itself goes in the store.
+    interred: bool,
+}
+
+impl EnumSource {
+    fn supertype(object: &Object) -> Self {
+        Self {
+            type_name: object.render(AS_TYPE),
+            ident: object.render(AS_IDENT),
+            kind: ElementKind::Object,
+            id: object.id,
+            name: object.name.inner().to_owned(),
+            interred: true,
+        }
+    }
+
+    fn hierarchy(object: &Object, isa: &Isa) -> Self {
+        Self {
+            type_name: format!("{}R{}", object.render(AS_TYPE), isa.number),
+            ident: format!("{}_r{}", object.render(AS_IDENT), isa.number),
+            kind: ElementKind::Relationship,
+            id: isa.id,
+            name: format!("R{}", isa.number),
+            interred: false,
+        }
+    }
+}
+
 /// Generate Enum
 ///
 pub fn emit_enum_main(
@@ -117,85 +262,862 @@ pub fn emit_enum_main(
 ) -> Result<CachingContext> {
     trace!("in `{}` with {}", func_name!(), object.name);
 
+    let mut context = emit_enum(
+        &EnumSource::supertype(object),
+        &subtypes,
+        store,
+        domain,
+        package,
+        doc_tests,
+    )?;
+
+    context += emit_impl_extras(object)?;
+
+    Ok(context)
+}
+
+/// Generate a supertype that's in more than one isa relationship
+///
+/// An enum only gets one set of variants, and emitting one per relationship
+/// left us with two enums with the same name. So each relationship gets an enum
+/// of it's own, named for the supertype and the relationship, e.g., `AnimalR1`.
+/// The supertype is a struct that holds one of each, along with an id of it's
+/// own. It has a `get_id`, just like the enum would, so the store and anything
+/// that refers to it can't tell the difference.
+///
+/// A subtype that's in more than one of them is just a variant in each. Same
+/// goes for a subtype of more than one supertype.
+pub fn emit_generalization(
+    object: &Object,
+    hierarchies: Vec<(&Isa, Vec<&Object>)>,
+    store: &SarzakModel,
+    domain: &str,
+    package: &str,
+    doc_tests: bool,
+) -> Result<CachingContext> {
+    trace!("in `{}` with {}", func_name!(), object.name);
+
     let mut context = CachingContext::new();
 
-    let mut subtype_variants = Vec::new();
+    let sources = hierarchies
+        .iter()
+        .map(|(isa, _)| EnumSource::hierarchy(object, isa))
+        .collect::<Vec<_>>();
+    // The struct has a field for each, named for the relationship.
+    let fields = sources
+        .iter()
+        .map(|s| s.name.to_lowercase())
+        .collect::<Vec<_>>();
 
-    begin_crit!(context, "{}-enum-definition", object.render(AS_IDENT))?;
+    begin_crit!(context, "{}-struct-definition", object.render(AS_IDENT))?;
+    context.begin_element(
+        Construct::Struct,
+        ElementKind::Object,
+        object.id,
+        object.name.inner(),
+    )?;
+    emitln!(
+        context,
+        "#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]"
+    );
+    emitln!(context, "pub struct {} {{", object.render(AS_TYPE));
+    context.increase_indent();
+
+    context.push_scope();
+    context.declare_symbol(
+        Uuid::new_v5(&object.id, b"id"),
+        "the `id` field",
+        Symbol {
+            value: "id".to_owned(),
+            value_type: "Uuid".to_owned(),
+            is_reference: false,
+        },
+    )?;
+    emitln!(context, "/// pub id: `Uuid`,");
+    emitln!(context, "///");
+    emitln!(context, "pub id: Uuid,");
+
+    for (source, field) in sources.iter().zip(&fields) {
+        context.declare_symbol(
+            source.id,
+            format!("isa relationship `{}`", source.name),
+            Symbol {
+                value: field.clone(),
+                value_type: source.type_name.clone(),
+                is_reference: false,
+            },
+        )?;
+        context.begin_element(Construct::Field, source.kind, source.id, &source.name)?;
+        emitln!(context, "/// pub {}: [`{}`],", field, source.type_name);
+        emitln!(context, "///");
+        emitln!(context, "pub {}: {},", field, source.type_name);
+        context.end_element(source.id)?;
+    }
+    context.pop_scope()?;
+
+    context.decrease_indent();
+    emitln!(context, "}");
+    context.end_element(object.id)?;
+    end_crit!(context, "{}-struct-definition", object.render(AS_IDENT))?;
+    emitln!(context, "");
+
+    let params = fields
+        .iter()
+        .zip(&sources)
+        .map(|(f, s)| format!("{}: {}", f, s.type_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let args = fields.join(", ");
+
+    // `new` is all there is to make one, so it doesn't wait for `new_impl`.
+    context.begin_element(
+        Construct::New,
+        ElementKind::Object,
+        object.id,
+        object.name.inner(),
+    )?;
+    begin_crit!(context, "{}-new_impl", object.render(AS_IDENT))?;
+    emitln!(context, "impl {} {{", object.render(AS_TYPE));
+    context.increase_indent();
+
+    emitln!(
+        context,
+        "/// Inter a new {} and return it's `id`",
+        object.render(AS_TYPE)
+    );
+    emitln!(context, "///");
+    if doc_tests {
+        let mut use_statements = BTreeSet::new();
+        use_statements.insert(object.render(AS_TYPE));
+        for source in &sources {
+            use_statements.insert(source.type_name.clone());
+        }
+
+        emitln!(context, "/// # Example");
+        emitln!(context, "///");
+        emitln!(context, "///```");
+        for us in &use_statements {
+            emitln!(
+                context,
+                "/// # use {}::{}::{};",
+                package,
+                domain.to_snake_case(),
+                us
+            );
+        }
+        emitln!(
+            context,
+            "/// # let mut store = {}::{}::ObjectStore::new();",
+            package,
+            domain.to_snake_case()
+        );
+        emitln!(context, "///");
+        for (source, field) in sources.iter().zip(&fields) {
+            emitln!(
+                context,
+                "/// let {} = {}::test_default(&mut store);",
+                field,
+                source.type_name
+            );
+        }
+        emitln!(
+            context,
+            "/// let {} = {}::new(&mut store, {});",
+            object.render(AS_IDENT),
+            object.render(AS_TYPE),
+            args
+        );
+        // It's id is it's own, so this is how to get back to it from a subtype.
+        emitln!(context, "///");
+        for field in &fields {
+            emitln!(
+                context,
+                "/// assert_eq!(store.exhume_{}_by_subtype(&{}.{}.get_id()), Some(&{}));",
+                object.render(AS_IDENT),
+                object.render(AS_IDENT),
+                field,
+                object.render(AS_IDENT)
+            );
+        }
+        emitln!(context, "///```");
+    }
+
+    emitln!(
+        context,
+        "pub fn new(store: &mut ObjectStore, {}) -> Self {{",
+        params
+    );
+    context.increase_indent();
+    emitln!(
+        context,
+        "let id = Uuid::new_v5(&UUID_NS, format!(\"{}\", {}).as_bytes());",
+        "{:?}::".repeat(fields.len()),
+        args
+    );
+    emitln!(context, "let new = Self {{ id, {} }};", args);
+    emitln!(context, "");
+    emitln!(
+        context,
+        "store.inter_{}(new.clone());",
+        object.render(AS_IDENT)
+    );
+    emitln!(context, "");
+    emitln!(context, "new");
+    context.decrease_indent();
+    emitln!(context, "}");
+
+    context.decrease_indent();
+    emitln!(context, "}");
+    end_crit!(context, "{}-new_impl", object.render(AS_IDENT))?;
+    context.end_element(object.id)?;
+    emitln!(context, "");
+
+    begin_crit!(context, "{}-struct-get-id-impl", object.render(AS_IDENT))?;
+    emitln!(context, "impl {} {{", object.render(AS_TYPE));
+    context.increase_indent();
+    emitln!(context, "pub fn get_id(&self) -> Uuid {");
+    context.increase_indent();
+    emitln!(context, "self.id");
+    context.decrease_indent();
+    emitln!(context, "}");
+    context.decrease_indent();
+    emitln!(context, "}");
+    end_crit!(context, "{}-struct-get-id-impl", object.render(AS_IDENT))?;
+    emitln!(context, "");
+
+    if doc_tests {
+        begin_crit!(context, "{}-test_default", object.render(AS_IDENT))?;
+        emitln!(context, "impl {} {{", object.render(AS_TYPE));
+        context.increase_indent();
+        emitln!(
+            context,
+            "pub fn test_default(store: &mut ObjectStore) -> Self {"
+        );
+        context.increase_indent();
+        for (source, field) in sources.iter().zip(&fields) {
+            emitln!(
+                context,
+                "let {} = {}::test_default(store);",
+                field,
+                source.type_name
+            );
+        }
+        emitln!(context, "");
+        emitln!(context, "Self::new(store, {})", args);
+        context.decrease_indent();
+        emitln!(context, "}");
+        context.decrease_indent();
+        emitln!(context, "}");
+        end_crit!(context, "{}-test_default", object.render(AS_IDENT))?;
+        emitln!(context, "");
+    }
+
+    context += emit_impl_extras(object)?;
+
+    for ((_, subtypes), source) in hierarchies.iter().zip(&sources) {
+        emitln!(
+            context,
+            "/// The subtypes of [`{}`] across {}",
+            object.render(AS_TYPE),
+            source.name
+        );
+        context += emit_enum(source, subtypes, store, domain, package, doc_tests)?;
+    }
+
+    Ok(context)
+}
+
+fn emit_enum(
+    source: &EnumSource,
+    subtypes: &Vec<&Object>,
+    store: &SarzakModel,
+    domain: &str,
+    package: &str,
+    doc_tests: bool,
+) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+
+    begin_crit!(context, "{}-enum-definition", source.ident)?;
+    context.begin_element(Construct::Enum, source.kind, source.id, &source.name)?;
 
     // Write out the enum starter.
     context.writeln("#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]");
-    context.writeln(format!("pub enum {} {{", object.render(AS_TYPE)));
+    context.writeln(format!("pub enum {} {{", source.type_name));
+
+    // Iterate over the (sorted) subtypes and add them as variants.
+    context.increase_indent();
+    context.push_scope();
+
+    for sub in subtypes {
+        let type_str = sub.render(AS_TYPE);
+
+        context.declare_symbol(
+            sub.id,
+            format!("subtype `{}`", sub.name),
+            Symbol {
+                value: type_str.clone(),
+                value_type: source.type_name.clone(),
+                is_reference: false,
+            },
+        )?;
+        context.begin_element(
+            Construct::Variant,
+            ElementKind::Object,
+            sub.id,
+            sub.name.inner(),
+        )?;
+        if Variant::of(sub, store).has_id() {
+            context.writeln(format!("/// `{}({})`,", type_str, type_str));
+            emitln!(context, "///");
+            context.writeln(format!("{}(Uuid),", type_str));
+        } else {
+            context.writeln(format!("{},", type_str));
+        }
+        context.end_element(sub.id)?;
+    }
+    context.pop_scope()?;
+    context.decrease_indent();
+
+    context.writeln("}");
+
+    context.end_element(source.id)?;
+    end_crit!(context, "{}-enum-definition", source.ident)?;
+
+    context.writeln("");
+
+    context += emit_enum_main_impl(source, subtypes, store, domain, package, doc_tests)?;
+
+    if doc_tests {
+        context += emit_enum_default_impl(source, subtypes, store, domain, package)?;
+    }
+
+    context += emit_enum_conversions(source, subtypes, store, domain, package, doc_tests)?;
+
+    // Only the supertype is in the store, so that's the only one that can be
+    // looked up.
+    if source.interred && is_multi_level(subtypes, store) {
+        context += emit_enum_leaf(source, subtypes, store)?;
+    }
+
+    Ok(context)
+}
+
+/// Generate Enum implementations
+///
+/// This specific implementation is to create the `get_id` method for the enum, which
+/// varies with the enum value.
+///
+/// A variant that's a supertype holds it's `get_id()`, so this is the id of
+/// whatever is at the bottom, however far down that is. When it is more than
+/// one level, the doc test builds one from the bottom up to prove it.
+fn emit_enum_main_impl(
+    source: &EnumSource,
+    subtypes: &Vec<&Object>,
+    store: &SarzakModel,
+    domain: &str,
+    package: &str,
+    doc_tests: bool,
+) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+
+    begin_crit!(context, "{}-enum-get-id-impl", source.ident)?;
+
+    emitln!(context, "impl {} {{", source.type_name);
+
+    context.increase_indent();
+    if source.interred && is_multi_level(subtypes, store) {
+        emitln!(
+            context,
+            "/// The id of the instance at the bottom of the hierarchy"
+        );
+        emitln!(context, "///");
+        if doc_tests {
+            context += emit_enum_leaf_doc_test(source, subtypes, store, domain, package)?;
+        }
+    }
+    emitln!(context, "pub fn get_id(&self) -> Uuid {");
+
+    context.increase_indent();
+    emitln!(context, "match *self {");
+
+    context.increase_indent();
+    for sub in subtypes {
+        if Variant::of(sub, store).has_id() {
+            emitln!(context, "Self::{}(z) => z,", sub.render(AS_TYPE));
+        } else {
+            emitln!(
+                context,
+                "Self::{} => Uuid::from_u128({:#x}),",
+                sub.render(AS_TYPE),
+                unit_id(sub).as_u128()
+            );
+        }
+    }
+    context.decrease_indent();
+    emitln!(context, "}");
+
+    context.decrease_indent();
+    emitln!(context, "}");
+
+    context.decrease_indent();
+    emitln!(context, "}");
+
+    end_crit!(context, "{}-enum-get-id-impl", source.ident)?;
+
+    // What's this mean? I wish I knew.
+    // Self-closing braces anyone?
+    emitln!(context, "");
+
+    Ok(context)
+}
+
+/// Generate a doc test that builds a leaf through every level
+///
+/// Down the first branch that's another enum, at every level, until there aren't
+/// any. Then build whatever is at the bottom, and wrap it in each enum on the
+/// way back up.
+fn emit_enum_leaf_doc_test(
+    source: &EnumSource,
+    subtypes: &Vec<&Object>,
+    store: &SarzakModel,
+    domain: &str,
+    package: &str,
+) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+    let module = format!("{}::{}", package, domain.to_snake_case());
+
+    let mut path = Vec::new();
+    let mut level = subtypes.clone();
+    loop {
+        let next = *level
+            .iter()
+            .find(|s| get_enum_subtypes(s, store).is_some())
+            .unwrap_or(&level[0]);
+        path.push(next);
+        match get_enum_subtypes(next, store) {
+            Some(subs) => level = subs,
+            None => break,
+        }
+    }
+
+    let leaves = get_leaves(subtypes, store);
+    let leaf_enum = leaf_type(&source.type_name, &leaves, store, "'_");
+    let leaf_enum = leaf_enum.trim_end_matches("<'_>");
+    let bottom = *path.last().unwrap();
+
+    let mut use_statements = BTreeSet::new();
+    use_statements.insert(format!("use {}::{};", module, source.type_name));
+    use_statements.insert(format!("use {}::{};", module, leaf_enum));
+    for obj in &path[..path.len() - 1] {
+        use_statements.insert(format!("use {}::{};", module, obj.render(AS_TYPE)));
+    }
+
+    // The value that goes in the variant above, and the leaf it turns into.
+    let mut body = CachingContext::new();
+    let (mut value, leaf) = match Variant::of(bottom, store) {
+        Variant::Instance => {
+            let mut values = ExampleValues::new(store, &source.id);
+            let (ctx, args) = emit_render_new(
+                bottom,
+                store,
+                domain,
+                package,
+                &mut use_statements,
+                &mut values,
+                false,
+            )?;
+            body += ctx;
+            emitln!(
+                body,
+                "let {} = {}::new(&mut store{}){};",
+                bottom.render(AS_IDENT),
+                bottom.render(AS_TYPE),
+                create_arg_string(&args, &body),
+                new_unwrap(bottom, store)
+            );
+            (
+                Some(format!("{}.id", bottom.render(AS_IDENT))),
+                format!("(&{})", bottom.render(AS_IDENT)),
+            )
+        }
+        Variant::Supertype => {
+            use_statements.insert(format!("use {}::{};", module, bottom.render(AS_TYPE)));
+            emitln!(
+                body,
+                "let {} = {}::test_default(&mut store);",
+                bottom.render(AS_IDENT),
+                bottom.render(AS_TYPE)
+            );
+            (
+                Some(format!("{}.get_id()", bottom.render(AS_IDENT))),
+                format!("(&{})", bottom.render(AS_IDENT)),
+            )
+        }
+        Variant::Singleton => {
+            use_statements.insert(format!("use {}::{};", module, bottom.render(AS_CONST)));
+            (
+                Some(bottom.render(AS_CONST)),
+                format!("({})", bottom.render(AS_CONST)),
+            )
+        }
+        Variant::Unit => (None, String::new()),
+    };
+    let leaf_id = value.clone();
+
+    // Back up, wrapping as we go.
+    let parents = path[..path.len() - 1]
+        .iter()
+        .rev()
+        .map(|p| (p.render(AS_TYPE), p.render(AS_IDENT)))
+        .chain([(source.type_name.clone(), source.ident.clone())]);
+    for (child, (parent_type, parent_ident)) in path.iter().rev().zip(parents) {
+        match value {
+            Some(value) => emitln!(
+                body,
+                "let {} = {}::{}({});",
+                parent_ident,
+                parent_type,
+                child.render(AS_TYPE),
+                value
+            ),
+            None => emitln!(
+                body,
+                "let {} = {}::{};",
+                parent_ident,
+                parent_type,
+                child.render(AS_TYPE)
+            ),
+        }
+        emitln!(
+            body,
+            "store.inter_{}({}.clone());",
+            parent_ident,
+            parent_ident
+        );
+        value = Some(format!("{}.get_id()", parent_ident));
+    }
+
+    emitln!(body, "");
+    if let Some(leaf_id) = leaf_id {
+        emitln!(body, "assert_eq!({}.get_id(), {});", source.ident, leaf_id);
+    }
+    emitln!(
+        body,
+        "assert_eq!(store.exhume_{}_leaf(&{}.get_id()), Some({}::{}{}));",
+        source.ident,
+        source.ident,
+        leaf_enum,
+        bottom.render(AS_TYPE),
+        leaf
+    );
+
+    emitln!(context, "/// # Example");
+    emitln!(context, "///");
+    emitln!(context, "///```");
+    for us in &use_statements {
+        emitln!(context, "/// # {}", us);
+    }
+    emitln!(
+        context,
+        "/// # let mut store = {}::ObjectStore::new();",
+        module
+    );
+    emitln!(context, "///");
+    body.insert_prefix("/// ");
+    context += body;
+    emitln!(context, "///```");
+
+    Ok(context)
+}
+
+/// Generate conversions between the enum and it's subtypes
+///
+/// All we have in a variant is an id. Getting from there to the subtype means
+/// matching on the variant, and then exhuming it from the store. Everybody was
+/// writing that by hand, so now it's generated. There's an `as_<subtype>` for
+/// each subtype, `From` a subtype to the enum, and `TryFrom` the enum, with the
+/// store, back to the subtype. That one hands back the enum if it's not the
+/// right variant, like `TryFrom<Vec<T>>` for an array does.
+///
+/// Singletons and subtypes without an id don't have anything to convert to.
+fn emit_enum_conversions(
+    source: &EnumSource,
+    subtypes: &Vec<&Object>,
+    store: &SarzakModel,
+    domain: &str,
+    package: &str,
+    doc_tests: bool,
+) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
 
-    // Iterate over the (sorted) subtypes and add them as variants.
-    context.increase_indent();
+    let stored = subtypes
+        .iter()
+        .filter(|s| Variant::of(s, store).is_stored())
+        .collect::<Vec<_>>();
+    if stored.is_empty() {
+        return Ok(context);
+    }
 
-    subtypes.iter().for_each(|sub| {
-        let type_str = sub.render(AS_TYPE);
-        subtype_variants.push(type_str.clone());
+    let name = source.type_name.as_str();
+    let ident = source.ident.as_str();
 
-        if sub.attributes.len() > 0 {
-            context.writeln(format!("/// `{}({})`,", type_str, type_str));
-            emitln!(context, "///");
-            context.writeln(format!("{}(Uuid),", type_str));
-        } else {
-            context.writeln(format!("{},", type_str));
+    begin_crit!(context, "{}-conversions", ident)?;
+    emitln!(context, "impl {} {{", name);
+    context.increase_indent();
+    for (i, sub) in stored.iter().enumerate() {
+        emitln!(
+            context,
+            "/// Exhume the [`{}`], if that's what this is",
+            sub.render(AS_TYPE)
+        );
+        emitln!(context, "///");
+        if doc_tests && i == 0 {
+            context += emit_enum_conversion_doc_test(source, sub, store, domain, package)?;
+        }
+        emitln!(
+            context,
+            "pub fn as_{}<'a>(&self, store: &'a ObjectStore) -> Option<&'a {}> {{",
+            sub.render(AS_IDENT),
+            sub.render(AS_TYPE)
+        );
+        context.increase_indent();
+        emitln!(context, "match self {");
+        context.increase_indent();
+        emitln!(
+            context,
+            "Self::{}(id) => store.exhume_{}(id),",
+            sub.render(AS_TYPE),
+            sub.render(AS_IDENT)
+        );
+        if subtypes.len() > 1 {
+            emitln!(context, "_ => None,");
+        }
+        context.decrease_indent();
+        emitln!(context, "}");
+        context.decrease_indent();
+        emitln!(context, "}");
+        if i + 1 < stored.len() {
+            emitln!(context, "");
         }
-    });
+    }
     context.decrease_indent();
+    emitln!(context, "}");
+    emitln!(context, "");
 
-    context.writeln("}");
-
-    end_crit!(context, "{}-enum-definition")?;
-
-    context.writeln("");
+    for sub in &stored {
+        // Supertypes have `get_id`, everything else has an `id`.
+        let id = match Variant::of(sub, store) {
+            Variant::Supertype => "get_id()",
+            _ => "id",
+        };
 
-    context += emit_enum_main_impl(&object, &subtype_variants)?;
+        emitln!(
+            context,
+            "impl From<&{}> for {} {{",
+            sub.render(AS_TYPE),
+            name
+        );
+        context.increase_indent();
+        emitln!(
+            context,
+            "fn from({}: &{}) -> Self {{",
+            sub.render(AS_IDENT),
+            sub.render(AS_TYPE)
+        );
+        context.increase_indent();
+        emitln!(
+            context,
+            "Self::{}({}.{})",
+            sub.render(AS_TYPE),
+            sub.render(AS_IDENT),
+            id
+        );
+        context.decrease_indent();
+        emitln!(context, "}");
+        context.decrease_indent();
+        emitln!(context, "}");
+        emitln!(context, "");
 
-    if doc_tests {
-        context += emit_enum_default_impl(&object, &subtypes, store, domain, package)?;
+        emitln!(
+            context,
+            "impl<'a> TryFrom<(&{}, &'a ObjectStore)> for &'a {} {{",
+            name,
+            sub.render(AS_TYPE)
+        );
+        context.increase_indent();
+        emitln!(context, "type Error = {};", name);
+        emitln!(context, "");
+        emitln!(
+            context,
+            "fn try_from(({}, store): (&{}, &'a ObjectStore)) -> Result<Self, Self::Error> {{",
+            ident,
+            name
+        );
+        context.increase_indent();
+        emitln!(
+            context,
+            "{}.as_{}(store).ok_or_else(|| {}.clone())",
+            ident,
+            sub.render(AS_IDENT),
+            ident
+        );
+        context.decrease_indent();
+        emitln!(context, "}");
+        context.decrease_indent();
+        emitln!(context, "}");
+        emitln!(context, "");
     }
+    end_crit!(context, "{}-conversions", ident)?;
+    emitln!(context, "");
 
     Ok(context)
 }
 
-/// Generate Enum implementations
+/// Generate a doc test for the conversions
 ///
-/// This specific implementation is to create the `get_id` method for the enum, which
-/// varies with the enum value.
-pub fn emit_enum_main_impl(
-    object: &Object,
-    subtype_variants: &Vec<String>,
+/// Make a subtype, turn it into the enum, and get it back, both ways.
+fn emit_enum_conversion_doc_test(
+    source: &EnumSource,
+    sub: &Object,
+    store: &SarzakModel,
+    domain: &str,
+    package: &str,
 ) -> Result<CachingContext> {
     let mut context = CachingContext::new();
+    let module = format!("{}::{}", package, domain.to_snake_case());
 
-    begin_crit!(context, "{}-enum-get-id-impl", object.render(AS_TYPE))?;
-
-    emitln!(context, "impl {} {{", object.render(AS_TYPE));
+    let mut use_statements = BTreeSet::new();
+    use_statements.insert(format!("use {}::{};", module, source.type_name));
+    use_statements.insert(format!("use {}::{};", module, sub.render(AS_TYPE)));
 
-    context.increase_indent();
-    emitln!(context, "pub fn get_id(&self) -> Uuid {");
+    let mut body = CachingContext::new();
+    if Variant::of(sub, store) == Variant::Supertype {
+        emitln!(
+            body,
+            "let {} = {}::test_default(&mut store);",
+            sub.render(AS_IDENT),
+            sub.render(AS_TYPE)
+        );
+    } else {
+        let mut values = ExampleValues::new(store, &source.id);
+        let (ctx, args) = emit_render_new(
+            sub,
+            store,
+            domain,
+            package,
+            &mut use_statements,
+            &mut values,
+            false,
+        )?;
+        body += ctx;
+        emitln!(
+            body,
+            "let {} = {}::new(&mut store{}){};",
+            sub.render(AS_IDENT),
+            sub.render(AS_TYPE),
+            create_arg_string(&args, &body),
+            new_unwrap(sub, store)
+        );
+    }
+    emitln!(
+        body,
+        "let {} = {}::from(&{});",
+        source.ident,
+        source.type_name,
+        sub.render(AS_IDENT)
+    );
+    emitln!(body, "");
+    emitln!(
+        body,
+        "assert_eq!({}.as_{}(&store), Some(&{}));",
+        source.ident,
+        sub.render(AS_IDENT),
+        sub.render(AS_IDENT)
+    );
+    emitln!(
+        body,
+        "let same: &{} = (&{}, &store).try_into().unwrap();",
+        sub.render(AS_TYPE),
+        source.ident
+    );
+    emitln!(body, "assert_eq!(same, &{});", sub.render(AS_IDENT));
 
-    context.increase_indent();
-    emitln!(context, "match *self {");
+    emitln!(context, "/// # Example");
+    emitln!(context, "///");
+    emitln!(context, "///```");
+    for us in &use_statements {
+        emitln!(context, "/// # {}", us);
+    }
+    emitln!(
+        context,
+        "/// # let mut store = {}::ObjectStore::new();",
+        module
+    );
+    emitln!(context, "///");
+    body.insert_prefix("/// ");
+    context += body;
+    emitln!(context, "///```");
 
-    context.increase_indent();
-    subtype_variants.iter().for_each(|sub| {
-        emitln!(context, "Self::{}(z) => z,", sub);
-    });
-    context.decrease_indent();
-    emitln!(context, "}");
+    Ok(context)
+}
 
-    context.decrease_indent();
-    emitln!(context, "}");
+/// Generate the leaf enum for a supertype with more than one level
+///
+/// It's what the store's `exhume_{}_leaf` returns: whatever is at the bottom
+/// of the hierarchy.
+fn emit_enum_leaf(
+    source: &EnumSource,
+    subtypes: &Vec<&Object>,
+    store: &SarzakModel,
+) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+    let leaves = get_leaves(subtypes, store);
 
+    emitln!(
+        context,
+        "/// The instances at the bottom of the [`{}`] hierarchy",
+        source.type_name
+    );
+    emitln!(context, "///");
+    emitln!(
+        context,
+        "/// This is what [`ObjectStore::exhume_{}_leaf`] finds.",
+        source.ident
+    );
+    begin_crit!(context, "{}-leaf-definition", source.ident)?;
+    emitln!(context, "#[derive(Clone, Copy, Debug, PartialEq)]");
+    emitln!(
+        context,
+        "pub enum {} {{",
+        leaf_type(&source.type_name, &leaves, store, "'a")
+    );
+    context.increase_indent();
+    context.push_scope();
+    for leaf in &leaves {
+        context.declare_symbol(
+            leaf.id,
+            format!("subtype `{}`", leaf.name),
+            Symbol {
+                value: leaf.render(AS_TYPE),
+                value_type: format!("{}Leaf", source.type_name),
+                is_reference: false,
+            },
+        )?;
+        match Variant::of(leaf, store) {
+            Variant::Instance | Variant::Supertype => emitln!(
+                context,
+                "{}(&'a {}),",
+                leaf.render(AS_TYPE),
+                leaf.render(AS_TYPE)
+            ),
+            Variant::Singleton => emitln!(context, "{}(Uuid),", leaf.render(AS_TYPE)),
+            Variant::Unit => emitln!(context, "{},", leaf.render(AS_TYPE)),
+        }
+    }
+    context.pop_scope()?;
     context.decrease_indent();
     emitln!(context, "}");
-
-    end_crit!(context, "{}-enum-get-id-impl", object.render(AS_TYPE))?;
-
-    // What's this mean? I wish I knew.
-    // Self-closing braces anyone?
+    end_crit!(context, "{}-leaf-definition", source.ident)?;
     emitln!(context, "");
 
     Ok(context)
@@ -223,8 +1145,8 @@ pub fn emit_enum_main_impl(
 /// I'd say I did my job well. 🎉 The hardest part was getting the things I
 /// needed passed in as parameters to the function. The code generation bit was
 /// simplicity itself.
-pub fn emit_enum_default_impl(
-    object: &Object,
+fn emit_enum_default_impl(
+    source: &EnumSource,
     subtypes: &Vec<&Object>,
     store: &SarzakModel,
     domain: &str,
@@ -232,8 +1154,8 @@ pub fn emit_enum_default_impl(
 ) -> Result<CachingContext> {
     let mut context = CachingContext::new();
 
-    begin_crit!(context, "{}-test_default", object.render(AS_IDENT))?;
-    emitln!(context, "impl {} {{", object.render(AS_TYPE));
+    begin_crit!(context, "{}-test_default", source.ident)?;
+    emitln!(context, "impl {} {{", source.type_name);
 
     context.increase_indent();
     // I'd like to inter store into the symbol table, and then have them figure
@@ -272,6 +1194,8 @@ pub fn emit_enum_default_impl(
             variant.render(AS_TYPE),
             variant.render(AS_TYPE)
         );
+    } else if !Variant::of(variant, store).has_id() {
+        emitln!(context, "let test = Self::{};", variant.render(AS_TYPE));
     } else {
         if store.config.is_singleton(&variant.id) {
             emitln!(
@@ -281,24 +1205,24 @@ pub fn emit_enum_default_impl(
                 variant.render(AS_CONST)
             )
         } else {
-            debug!("getting ☯️  in {} for {}", func_name!(), object.name);
+            debug!("getting ☯️  in {} for {}", func_name!(), source.name);
             trace!(
                 "calling emit_render_new from `{}` with {}",
                 func_name!(),
-                object.name
+                source.name
             );
-            let mut use_statements = HashSet::new();
+            let mut use_statements = BTreeSet::new();
+            let mut values = ExampleValues::new(store, &source.id);
             let (ctx, args) = emit_render_new(
                 variant,
                 store,
                 domain,
                 package,
                 &mut use_statements,
-                true,
+                &mut values,
                 true,
             )?;
 
-            context.begin_ignore_block()?;
             for us in &use_statements {
                 emitln!(context, us);
             }
@@ -307,23 +1231,20 @@ pub fn emit_enum_default_impl(
 
             emitln!(
                 context,
-                "let test = Self::{}({}::new(store{}).id);",
+                "let test = Self::{}({}::new(store{}){}.id);",
                 variant.render(AS_TYPE),
                 variant.render(AS_TYPE),
-                create_arg_string(&args, &context)
+                create_arg_string(&args, &context),
+                new_unwrap(variant, store)
             );
-
-            context.end_ignore_block()?;
         }
     }
 
     emitln!(context, "");
-    emitln!(
-        context,
-        "store.inter_{}(test.clone());",
-        object.render(AS_IDENT)
-    );
-    emitln!(context, "");
+    if source.interred {
+        emitln!(context, "store.inter_{}(test.clone());", source.ident);
+        emitln!(context, "");
+    }
 
     emitln!(context, "test");
 
@@ -332,7 +1253,7 @@ pub fn emit_enum_default_impl(
 
     context.decrease_indent();
     emitln!(context, "}");
-    end_crit!(context, "{}-test_default", object.render(AS_IDENT))?;
+    end_crit!(context, "{}-test_default", source.ident)?;
     // Self-closing braces anyone?
     emitln!(context, "");
 
@@ -357,11 +1278,24 @@ pub fn emit_struct_main(
     trace!("in `{}` with {}", func_name!(), object.name);
 
     begin_crit!(context, "{}-struct-definition", object.render(AS_IDENT))?;
+    context.begin_element(
+        Construct::Struct,
+        ElementKind::Object,
+        object.id,
+        object.name.inner(),
+    )?;
     // Generate the struct "prelude"?
-    emitln!(
-        context,
-        "#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]"
-    );
+    if attrs.iter().all(|a| has_default(&a.attr_t)) {
+        emitln!(
+            context,
+            "#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]"
+        );
+    } else {
+        emitln!(
+            context,
+            "#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]"
+        );
+    }
     emitln!(
         context,
         format!(
@@ -386,16 +1320,38 @@ pub fn emit_struct_main(
     // These were passed in sorted, but sometimes they generate out of order, but
     // only when generating sarzak and drawing. I tried turning meta off, but that
     // didn't do anything.
+    //
+    // The fields get a scope of their own, so that two of them with the same name
+    // is an error here, rather than in rustc.
+    context.push_scope();
     for attr in attrs {
+        context.declare_symbol(
+            attr.id,
+            format!("attribute `{}`", attr.name),
+            Symbol {
+                value: attr.render(AS_IDENT),
+                value_type: attr.attr_t.to_string(),
+                is_reference: false,
+            },
+        )?;
+        context.begin_element(
+            Construct::Field,
+            ElementKind::Attribute,
+            attr.id,
+            attr.name.inner(),
+        )?;
         emitln!(context, "/// pub {}: `{}`,", attr.name, attr.attr_t);
         emitln!(context, "///");
         emitln!(context, "pub {}: {},", attr.render(AS_IDENT), attr.attr_t);
+        context.end_element(attr.id)?;
     }
     context.decrease_indent();
 
     // Generate attributes from relationship that we are formalizing.
-    context += emit_struct_rel_attr(object, store, false);
+    context += emit_struct_rel_attr(object, store, false)?;
+    context.pop_scope()?;
     emitln!(context, "}");
+    context.end_element(object.id)?;
     end_crit!(context, "{}-struct-definition", object.render(AS_IDENT))?;
 
     emitln!(context, "");
@@ -408,6 +1364,24 @@ pub fn emit_struct_main(
         context += emit_struct_extrude_impl(object, domain)?;
     }
 
+    context += emit_impl_extras(object)?;
+
+    Ok(context)
+}
+
+/// Generate a place for hand written code
+///
+/// It's empty, and it's theirs. Whatever they put in it follows the object around,
+/// even if it's renamed.
+fn emit_impl_extras(object: &Object) -> Result<CachingContext> {
+    let mut context = CachingContext::new();
+
+    context.user_region(
+        format!("{}-impl-extras", object.render(AS_IDENT)),
+        Some(object.id),
+    )?;
+    emitln!(context, "");
+
     Ok(context)
 }
 
@@ -415,7 +1389,11 @@ pub fn emit_struct_main(
 ///
 /// The for_from thing is just gross. Seriously. This is long overdue for a
 /// refactor, but it's at end of life...
-fn emit_struct_rel_attr(object: &Object, store: &SarzakModel, for_from: bool) -> CachingContext {
+fn emit_struct_rel_attr(
+    object: &Object,
+    store: &SarzakModel,
+    for_from: bool,
+) -> Result<CachingContext> {
     let mut context = CachingContext::new();
 
     // We are looking for relationships where we are the Referrer. These are either
@@ -441,6 +1419,7 @@ fn emit_struct_rel_attr(object: &Object, store: &SarzakModel, for_from: bool) ->
                         referent.id,
                         from_rel.conditionality,
                         referent.render(AS_TYPE).clone(),
+                        (rel.id, rel.number),
                     ));
                 }
             }
@@ -460,6 +1439,7 @@ fn emit_struct_rel_attr(object: &Object, store: &SarzakModel, for_from: bool) ->
                         from_ref.one_referential_attribute.render(AS_IDENT).clone(),
                         from_ref.one_referential_attribute.clone(),
                         one_obj.name.clone(),
+                        (rel.id, rel.number),
                     ));
                     assoc_rels.push((
                         from_ref
@@ -468,6 +1448,7 @@ fn emit_struct_rel_attr(object: &Object, store: &SarzakModel, for_from: bool) ->
                             .clone(),
                         from_ref.other_referential_attribute.clone(),
                         other_obj.name.clone(),
+                        (rel.id, rel.number),
                     ));
                 }
             }
@@ -481,19 +1462,50 @@ fn emit_struct_rel_attr(object: &Object, store: &SarzakModel, for_from: bool) ->
     context.increase_indent();
 
     // Emit associative attributes one and other
-    for (ident, attr_name, obj_name) in &assoc_rels {
+    for (ident, attr_name, obj_name, (rel_id, number)) in &assoc_rels {
         if !for_from {
+            context.declare_symbol(
+                Uuid::new_v5(rel_id, ident.as_bytes()),
+                format!("referential attribute `{}` (R{})", attr_name, number),
+                Symbol {
+                    value: ident.clone(),
+                    value_type: "Uuid".to_owned(),
+                    is_reference: false,
+                },
+            )?;
+            context.begin_element(
+                Construct::Field,
+                ElementKind::Relationship,
+                *rel_id,
+                format!("R{}", number),
+            )?;
             emitln!(context, "/// pub {}: `{}`,", attr_name, obj_name);
             emitln!(context, "///");
             emitln!(context, "pub {}: Uuid,", ident);
+            context.end_element(*rel_id)?;
         } else {
             emitln!(context, "{}: orig.{},", ident, ident)
         }
     }
 
     // Emit binary referrer attributes
-    for (ident, referent_name, attr_name, id, cond, ty) in &binary_rels {
+    for (ident, referent_name, attr_name, id, cond, ty, (rel_id, number)) in &binary_rels {
         if !for_from {
+            context.declare_symbol(
+                Uuid::new_v5(rel_id, ident.as_bytes()),
+                format!("referential attribute `{}` (R{})", attr_name, number),
+                Symbol {
+                    value: ident.clone(),
+                    value_type: "Uuid".to_owned(),
+                    is_reference: false,
+                },
+            )?;
+            context.begin_element(
+                Construct::Field,
+                ElementKind::Relationship,
+                *rel_id,
+                format!("R{}", number),
+            )?;
             // If this is an imported object, then indicate so in the comments.
             // We know that it's a pointer, so it's got type `Uuid`.
             match store.config.is_imported(&id) {
@@ -522,13 +1534,14 @@ fn emit_struct_rel_attr(object: &Object, store: &SarzakModel, for_from: bool) ->
                     }
                 },
             }
+            context.end_element(*rel_id)?;
         } else {
             emitln!(context, "{}: orig.{},", ident, ident)
         }
     }
     context.decrease_indent();
 
-    context
+    Ok(context)
 }
 
 fn emit_struct_impls(
@@ -546,9 +1559,31 @@ fn emit_struct_impls(
     let mut id_format = String::new();
     let mut id_list = String::new();
 
+    // The parameters to `new` are the fields, plus the store, which had better not
+    // be a field.
+    context.push_scope();
+    context.declare_symbol(
+        Uuid::new_v5(&object.id, b"store"),
+        "the `store` parameter to `new`",
+        Symbol {
+            value: "store".to_owned(),
+            value_type: "&mut ObjectStore".to_owned(),
+            is_reference: true,
+        },
+    )?;
+
     for f in &fields {
         match f {
             Field::Reference(r) => {
+                context.declare_symbol(
+                    Uuid::new_v5(&r.referent.id, r.ref_attr.inner().as_bytes()),
+                    format!("referential attribute `{}`", r.ref_attr),
+                    Symbol {
+                        value: r.ref_attr.render(AS_IDENT),
+                        value_type: f.render(AS_TYPE),
+                        is_reference: true,
+                    },
+                )?;
                 if r.optional {
                     field_list += &format!(
                         "{}: Option<&{}>, ",
@@ -565,13 +1600,28 @@ fn emit_struct_impls(
                 }
             }
             Field::Attribute(a) => {
+                context.declare_symbol(
+                    a.id,
+                    format!("attribute `{}`", a.name),
+                    Symbol {
+                        value: f.render(AS_IDENT),
+                        value_type: a.attr_t.to_string(),
+                        is_reference: false,
+                    },
+                )?;
                 field_list += &format!("{}: {}, ", f.render(AS_IDENT), a.attr_t);
-                id_format += "{}::";
+                id_format += id_format_spec(&a.attr_t);
                 id_list += &format!("{}, ", f.render(AS_IDENT));
             }
         }
     }
 
+    context.begin_element(
+        Construct::New,
+        ElementKind::Object,
+        object.id,
+        object.name.inner(),
+    )?;
     begin_crit!(context, "{}-new_impl", object.render(AS_IDENT))?;
 
     emitln!(context, "impl {} {{", object.render(AS_TYPE));
@@ -584,26 +1634,62 @@ fn emit_struct_impls(
     );
     emitln!(context, "///");
 
+    let identity = Identity::of(object, store);
+    if identity.is_some() {
+        emitln!(
+            context,
+            "/// It's a [`DuplicateIdentifier`] if there's already one just like it."
+        );
+        emitln!(context, "///");
+    }
+
     if doc_tests {
-        context.begin_ignore_block()?;
         context += emit_struct_doc_tests(object, store, domain, package)?;
-        context.end_ignore_block()?;
     }
 
     // Emit the function header
-    emitln!(
-        context,
-        "pub fn new(store: &mut ObjectStore, {}) -> Self {{",
-        field_list
-    );
+    match &identity {
+        None => emitln!(
+            context,
+            "pub fn new(store: &mut ObjectStore, {}) -> Self {{",
+            field_list
+        ),
+        Some(identity) => {
+            let id_param = if identity.policy == IdentityPolicy::Supplied {
+                context.declare_symbol(
+                    Uuid::new_v5(&object.id, b"id"),
+                    "the `id` parameter to `new`",
+                    Symbol {
+                        value: "id".to_owned(),
+                        value_type: "Uuid".to_owned(),
+                        is_reference: false,
+                    },
+                )?;
+                "id: Uuid"
+            } else {
+                ""
+            };
+            emitln!(
+                context,
+                "pub fn new(store: &mut ObjectStore, {}{}) -> Result<Self, DuplicateIdentifier> {{",
+                field_list,
+                id_param
+            );
+        }
+    }
 
     context.increase_indent();
-    emitln!(
-        context,
-        "let id = Uuid::new_v5(&UUID_NS, format!(\"{}\", {}).as_bytes());",
-        id_format,
-        id_list
-    );
+    match &identity {
+        None => emitln!(
+            context,
+            "let id = Uuid::new_v5(&UUID_NS, format!(\"{}\", {}).as_bytes());",
+            id_format,
+            id_list
+        ),
+        Some(identity) => {
+            emit_identity_checks(&mut context, object, identity, &id_format, &id_list)
+        }
+    }
     emitln!(context, "let new = Self {");
 
     // Render the field list
@@ -636,8 +1722,7 @@ fn emit_struct_impls(
                     path.set_extension("json");
 
                     // 🦀 This should be cached or something.
-                    let io_store = SarzakModel::load_cuckoo_model(&path)
-                        .context(LoadCuckooModelSnafu { path: &path })?;
+                    let io_store = SarzakModel::load_cuckoo_model(&path)?;
                     //         ✨                    ✨         ✨
                     // How the fuck does this work? Oh -- the UUID is based off it's name,
                     // so as long as the name is correct, we'll be able to look it up by
@@ -688,7 +1773,11 @@ fn emit_struct_impls(
         object.render(AS_IDENT)
     );
     emitln!(context, "");
-    emitln!(context, "new");
+    if identity.is_some() {
+        emitln!(context, "Ok(new)");
+    } else {
+        emitln!(context, "new");
+    }
 
     context.decrease_indent();
     emitln!(context, "}");
@@ -696,12 +1785,93 @@ fn emit_struct_impls(
     end_crit!(context, "{}-new_impl", object.render(AS_IDENT))?;
     context.decrease_indent();
     emitln!(context, "}");
+    context.end_element(object.id)?;
+    context.pop_scope()?;
 
     emitln!(context, "");
 
     Ok(context)
 }
 
+/// Come up with an `id`, and make sure nobody else has it
+///
+/// We check the `id` first, since that's cheap. Any other identifiers mean a
+/// trip through the store. It's not fast, but it beats quietly clobbering
+/// somebody. 😬
+fn emit_identity_checks(
+    context: &mut CachingContext,
+    object: &Object,
+    identity: &Identity,
+    id_format: &str,
+    id_list: &str,
+) {
+    let ident = object.render(AS_IDENT);
+    let name = object.name.inner();
+
+    let primary = identity.primary();
+    match (identity.policy, primary) {
+        (IdentityPolicy::Identifier, Some((_, attrs))) => {
+            let format: String = attrs.iter().map(|a| id_format_spec(&a.attr_t)).collect();
+            let list: String = attrs
+                .iter()
+                .map(|a| format!("{}, ", a.render(AS_IDENT)))
+                .collect();
+            emitln!(
+                context,
+                "let id = Uuid::new_v5(&UUID_NS, format!(\"{}\", {}).as_bytes());",
+                format,
+                list
+            );
+        }
+        (IdentityPolicy::Identifier, None) => emitln!(
+            context,
+            "let id = Uuid::new_v5(&UUID_NS, format!(\"{}\", {}).as_bytes());",
+            id_format,
+            id_list
+        ),
+        (IdentityPolicy::Random, _) => emitln!(context, "let id = Uuid::new_v4();"),
+        (IdentityPolicy::Supplied, _) => {}
+    }
+
+    emitln!(context, "if store.exhume_{}(&id).is_some() {{", ident);
+    context.increase_indent();
+    emitln!(
+        context,
+        "return Err(DuplicateIdentifier {{ object: \"{}\", identifier: {}, id }});",
+        name,
+        primary.map_or(0, |(n, _)| n)
+    );
+    context.decrease_indent();
+    emitln!(context, "}");
+
+    for (number, attrs) in &identity.identifiers {
+        if primary.map(|(n, _)| n) == Some(*number) {
+            continue;
+        }
+        let matches = attrs
+            .iter()
+            .map(|a| format!("{}.{} == {}", ident, a.render(AS_IDENT), a.render(AS_IDENT)))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        emitln!(
+            context,
+            "if let Some((id, _)) = store.iter_{}().find(|(_, {})| {}) {{",
+            ident,
+            ident,
+            matches
+        );
+        context.increase_indent();
+        emitln!(
+            context,
+            "return Err(DuplicateIdentifier {{ object: \"{}\", identifier: {}, id: *id }});",
+            name,
+            number
+        );
+        context.decrease_indent();
+        emitln!(context, "}");
+    }
+}
+
 fn emit_struct_extrude_impl(object: &Object, domain: &str) -> Result<CachingContext> {
     let mut context = CachingContext::new();
 
@@ -761,14 +1931,15 @@ fn emit_struct_doc_tests(
         func_name!(),
         object.name
     );
-    let mut use_statements = HashSet::new();
+    let mut use_statements = BTreeSet::new();
+    let mut values = ExampleValues::new(store, &object.id);
     let (mut ctx, args) = emit_render_new(
         object,
         store,
         domain,
         package,
         &mut use_statements,
-        false,
+        &mut values,
         false,
     )?;
 
@@ -789,16 +1960,87 @@ fn emit_struct_doc_tests(
     emitln!(context, "///");
     emitln!(
         context,
-        "/// let {} = {}::new(&mut store{});",
+        "/// let {} = {}::new(&mut store{}){};",
         object.render(AS_IDENT),
         object.render(AS_TYPE),
-        create_arg_string(&args, &context)
+        create_arg_string(&args, &context),
+        new_unwrap(object, store)
     );
     emitln!(context, "///```");
 
     Ok(context)
 }
 
+/// Render a value for an attribute of type `ty`
+///
+/// Anything that needs a `let` gets one, and what comes back is what goes in the
+/// argument list. `None` means that there's no value to be had for `ty`.
+fn render_value(
+    ty: &Type,
+    context: &mut CachingContext,
+    domain: &str,
+    package: &str,
+    use_stmts: &mut BTreeSet<String>,
+    values: &mut ExampleValues,
+    internal: bool,
+) -> Option<String> {
+    // Had to make everything a String to get the generated strings to
+    // live long enough. Rust is smart.
+    let value = match ty {
+        Type::Boolean => "true".to_owned(),
+        Type::Float | Type::Float32 => "42.0".to_owned(),
+        Type::Integer
+        | Type::Integer8
+        | Type::Integer16
+        | Type::Integer32
+        | Type::Unsigned8
+        | Type::Unsigned16
+        | Type::Unsigned32
+        | Type::Unsigned64 => "42".to_owned(),
+        Type::String => {
+            let ident = values.name();
+            emitln!(context, "let {} = \"{}\".to_owned();", ident, values.name());
+            ident
+        }
+        Type::Uuid => {
+            let ident = values.name();
+            emitln!(context, "let {} = Uuid::default();", ident);
+            ident
+        }
+        Type::Timestamp => "std::time::SystemTime::UNIX_EPOCH".to_owned(),
+        Type::Duration => "std::time::Duration::from_secs(42)".to_owned(),
+        Type::Bytes => {
+            let ident = values.name();
+            emitln!(context, "let {} = b\"{}\".to_vec();", ident, values.name());
+            ident
+        }
+        Type::Optional(ty) => format!(
+            "Some({})",
+            render_value(ty, context, domain, package, use_stmts, values, internal)?
+        ),
+        Type::Enumeration(e) => {
+            if !internal {
+                use_stmts.insert(format!(
+                    "use {}::{}::{};",
+                    package,
+                    domain.to_snake_case(),
+                    e.render(AS_TYPE)
+                ));
+            }
+            // Validation doesn't let an enumeration without variants through, but
+            // there's no sense panicking over it here.
+            match e.variants.first() {
+                Some(v) => format!("{}::{}", e.render(AS_TYPE), v.render(AS_TYPE)),
+                None => format!("{}::default()", e.render(AS_TYPE)),
+            }
+        }
+        // References are made by the relationship, not by us.
+        Type::ForeignKey(_) => return None,
+    };
+
+    Some(value)
+}
+
 /// Render a `new` invocation
 ///
 /// Render all of the statements necessary to invoke object::new(). This function
@@ -807,14 +2049,17 @@ fn emit_struct_doc_tests(
 /// I wonder how hard it would be to make this work for any function invocation?
 /// As it is we are brushing up against the problem of knowing about function
 /// arguments. Dodged a bullet with `Object::default`.
+///
+/// `internal` is for code inside the crate, i.e., `test_default`, rather than a
+/// doc test. It doesn't need use statements, and `store` is already a reference.
 pub(crate) fn emit_render_new(
     object: &Object,
     store: &SarzakModel,
     domain: &str,
     package: &str,
-    use_stmts: &mut HashSet<String>,
+    use_stmts: &mut BTreeSet<String>,
+    values: &mut ExampleValues,
     internal: bool,
-    store_is_ref: bool,
 ) -> Result<(CachingContext, Vec<Uuid>)> {
     trace!("in `{}` with {}", func_name!(), object.name);
 
@@ -845,32 +2090,22 @@ pub(crate) fn emit_render_new(
     for f in &fields {
         match f {
             Field::Attribute(a) => {
-                // Had to make everything a String to get the generated strings to
-                // live long enough. Rust is smart.
-                let arg = match a.attr_t.to_string().as_str() {
-                    "bool" => "true".to_owned(),
-                    "f64" => "42.0".to_owned(),
-                    "i64" => "42".to_owned(),
-                    "std::string::String" => {
-                        let ident = Generator::default().next().unwrap().to_snake_case();
-                        emitln!(
-                            context,
-                            "let {} = \"{}\".to_owned();",
-                            ident,
-                            Generator::default().next().unwrap().to_snake_case()
-                        );
-                        ident
-                    }
-                    "Uuid" => {
-                        let ident = Generator::default().next().unwrap().to_snake_case();
-                        emitln!(context, "let {} = Uuid::default();", ident);
-                        ident
-                    }
-                    wtf => unimplemented!(
-                        "I don't know about the `{}` type. How'd that get in here? 🤔",
-                        wtf
-                    ),
-                };
+                let arg = render_value(
+                    &a.attr_t,
+                    &mut context,
+                    domain,
+                    package,
+                    use_stmts,
+                    values,
+                    internal,
+                )
+                .ok_or_else(|| {
+                    Error(CodeGenError::NoExampleValue {
+                        object: object.name.inner().clone(),
+                        attribute: a.name.inner().clone(),
+                        ty: a.attr_t.to_string(),
+                    })
+                })?;
 
                 let id = Uuid::new_v4();
                 context.inter_symbol(
@@ -904,31 +2139,27 @@ pub(crate) fn emit_render_new(
                             object.name
                         );
                         let (ctx, new_args) = emit_render_new(
-                            r.referent,
-                            store,
-                            domain,
-                            package,
-                            use_stmts,
-                            internal,
-                            store_is_ref,
+                            r.referent, store, domain, package, use_stmts, values, internal,
                         )?;
                         context += ctx;
 
-                        if store_is_ref {
+                        if internal {
                             emitln!(
                                 context,
-                                "let {} = {}::new(store{});",
+                                "let {} = {}::new(store{}){};",
                                 r.referent.render(AS_IDENT),
                                 r.referent.render(AS_TYPE),
-                                create_arg_string(&new_args, &context)
+                                create_arg_string(&new_args, &context),
+                                new_unwrap(r.referent, store)
                             );
                         } else {
                             emitln!(
                                 context,
-                                "let {} = {}::new(&mut store{});",
+                                "let {} = {}::new(&mut store{}){};",
                                 r.referent.render(AS_IDENT),
                                 r.referent.render(AS_TYPE),
-                                create_arg_string(&new_args, &context)
+                                create_arg_string(&new_args, &context),
+                                new_unwrap(r.referent, store)
                             );
                         }
 
@@ -964,8 +2195,8 @@ pub(crate) fn emit_render_new(
                         ));
                     }
 
-                    let lhs = r.referent.render(AS_RIDENT);
-                    if store_is_ref {
+                    let lhs = values.ident(r.referent.render(AS_IDENT));
+                    if internal {
                         emitln!(
                             context,
                             "let {} = {}::test_default(store);",
@@ -1027,13 +2258,7 @@ pub(crate) fn emit_render_new(
                             object.name
                         );
                         let (ctx, new_args) = emit_render_new(
-                            r.referent,
-                            store,
-                            domain,
-                            package,
-                            use_stmts,
-                            internal,
-                            store_is_ref,
+                            r.referent, store, domain, package, use_stmts, values, internal,
                         )?;
                         context += ctx;
 
@@ -1061,7 +2286,7 @@ pub(crate) fn emit_render_new(
                                 ));
                             }
 
-                            let lhs = r.referent.render(AS_RIDENT);
+                            let lhs = values.ident(r.referent.render(AS_IDENT));
                             let lhs_type = r.referent.render(AS_TYPE);
 
                             // WTF am I blabbering about here?
@@ -1101,15 +2326,14 @@ pub(crate) fn emit_render_new(
                             path.set_extension("json");
 
                             // 🦀 This should be cached or something.
-                            let io_store = SarzakModel::load_cuckoo_model(&path)
-                                .context(LoadCuckooModelSnafu { path: &path })?;
+                            let io_store = SarzakModel::load_cuckoo_model(&path)?;
 
                             // debug!("made it past");
 
                             if sarzak_maybe_get_one_r_sup_across_r14!(&r.referent, io_store)
                                 .is_some()
                             {
-                                if store_is_ref {
+                                if internal {
                                     emitln!(
                                         context,
                                         "let {} = {}::test_default(store);",
@@ -1157,24 +2381,26 @@ pub(crate) fn emit_render_new(
                                 ));
                             }
 
-                            let lhs = r.referent.render(AS_RIDENT);
+                            let lhs = values.ident(r.referent.render(AS_IDENT));
                             let lhs_type = r.referent.render(AS_TYPE);
 
-                            if store_is_ref {
+                            if internal {
                                 emitln!(
                                     context,
-                                    "let {} = {}::new(store{});",
+                                    "let {} = {}::new(store{}){};",
                                     lhs,
                                     r.referent.render(AS_TYPE),
-                                    create_arg_string(&new_args, &context)
+                                    create_arg_string(&new_args, &context),
+                                    new_unwrap(r.referent, store)
                                 );
                             } else {
                                 emitln!(
                                     context,
-                                    "let {} = {}::new(&mut store{});",
+                                    "let {} = {}::new(&mut store{}){};",
                                     lhs,
                                     r.referent.render(AS_TYPE),
-                                    create_arg_string(&new_args, &context)
+                                    create_arg_string(&new_args, &context),
+                                    new_unwrap(r.referent, store)
                                 );
                             }
 
@@ -1197,6 +2423,32 @@ pub(crate) fn emit_render_new(
         }
     }
 
+    // Somebody has to come up with an id, and this time it's us.
+    if let Some(Identity {
+        policy: IdentityPolicy::Supplied,
+        ..
+    }) = Identity::of(object, store)
+    {
+        let ident = values.name();
+        emitln!(
+            context,
+            "let {} = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, b\"{}\");",
+            ident,
+            ident
+        );
+
+        let id = Uuid::new_v4();
+        context.inter_symbol(
+            id,
+            Symbol {
+                value: ident,
+                value_type: "Uuid".to_owned(),
+                is_reference: false,
+            },
+        );
+        args.push(id);
+    }
+
     Ok((context, args))
 }
 
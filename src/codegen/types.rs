@@ -1,6 +1,14 @@
 //! Things that will help when generating types
 //!
-use crate::sarzak::{Attribute, AttributeName, Object};
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::codegen::{
+    get_subtypes, sarzak_maybe_get_one_r_sub_across_r15, sarzak_maybe_get_one_r_sup_across_r14,
+    IdentityPolicy, SarzakModel,
+};
+use crate::sarzak::{Attribute, AttributeName, Isa, Object, RelSide, Type, UUID_NS};
 
 /// I called it Parameter...
 ///
@@ -54,3 +62,232 @@ pub struct Ref<'a> {
     /// in an `Option`.
     pub optional: bool,
 }
+
+/// How a Subtype is Represented
+///
+/// A subtype is a variant of it's supertype's enum. Most of them hold the `id`
+/// of an instance in the store. Some of them are more interesting than that.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    /// `Foo(Uuid)`, the id of a `Foo` in the store
+    Instance,
+    /// `Foo(Uuid)`, holding the `FOO` const
+    Singleton,
+    /// `Foo(Uuid)`, where `Foo` is a supertype too
+    ///
+    /// It holds `Foo::get_id()`, which is the id of the instance at the bottom
+    /// of the hierarchy. So ids resolve all the way down, no matter how many
+    /// levels there are.
+    Supertype,
+    /// `Foo`, for a subtype without any attributes, and thus no id
+    Unit,
+}
+
+impl Variant {
+    pub fn of(object: &Object, store: &SarzakModel) -> Self {
+        if sarzak_maybe_get_one_r_sup_across_r14!(object, store).is_some() {
+            Self::Supertype
+        } else if store.config.is_singleton(&object.id) {
+            Self::Singleton
+        } else if object.attributes.is_empty() {
+            Self::Unit
+        } else {
+            Self::Instance
+        }
+    }
+
+    /// Is `object` a subtype that is nothing but it's name?
+    ///
+    /// The variant in it's supertype is all there is to it. It doesn't get a
+    /// struct, or a `new`, or a place in the store.
+    pub fn is_unit(object: &Object, store: &SarzakModel) -> bool {
+        sarzak_maybe_get_one_r_sub_across_r15!(object, store).is_some()
+            && Self::of(object, store) == Self::Unit
+    }
+
+    /// Does the variant carry a value?
+    pub fn has_id(&self) -> bool {
+        *self != Self::Unit
+    }
+
+    /// Is it something in the store?
+    ///
+    /// It's a reference in a leaf enum if it is.
+    pub fn is_stored(&self) -> bool {
+        matches!(self, Self::Instance | Self::Supertype)
+    }
+}
+
+/// The isa relationships that `object` is the supertype of
+///
+/// Each comes with it's subtypes, sorted, and they are sorted by number, so
+/// that the output is stable.
+pub(crate) fn get_hierarchies<'a>(
+    object: &Object,
+    store: &'a SarzakModel,
+) -> Vec<(&'a Isa, Vec<&'a Object>)> {
+    let mut hierarchies = object
+        .rels
+        .iter()
+        .filter(|(_, r_ptr)| r_ptr.side == RelSide::Supertype)
+        .map(|(_, r_ptr)| {
+            let isa = store.sarzak.exhume_isa(&r_ptr.value).unwrap();
+            let mut subtypes = get_subtypes!(r_ptr.value, store.sarzak);
+            subtypes.sort();
+
+            (isa, subtypes)
+        })
+        .collect::<Vec<_>>();
+    hierarchies.sort_by_key(|(isa, _)| isa.number);
+
+    hierarchies
+}
+
+/// The subtypes of a supertype that's an enum, i.e., in just one hierarchy
+///
+/// Anything else, including a supertype that's a struct because it's in more than
+/// one, is `None`.
+pub(crate) fn get_enum_subtypes<'a>(
+    object: &Object,
+    store: &'a SarzakModel,
+) -> Option<Vec<&'a Object>> {
+    let mut hierarchies = get_hierarchies(object, store);
+    if hierarchies.len() == 1 {
+        Some(hierarchies.remove(0).1)
+    } else {
+        None
+    }
+}
+
+/// Is there another enum under an enum with these subtypes?
+pub(crate) fn is_multi_level(subtypes: &[&Object], store: &SarzakModel) -> bool {
+    subtypes
+        .iter()
+        .any(|s| get_enum_subtypes(s, store).is_some())
+}
+
+/// The subtypes at the bottom of the hierarchy under these subtypes
+///
+/// They come sorted by name, and just once each, even if there is more than one
+/// way to get there.
+pub(crate) fn get_leaves<'a>(subtypes: &[&'a Object], store: &'a SarzakModel) -> Vec<&'a Object> {
+    let mut leaves = Vec::new();
+    for sub in subtypes {
+        match get_enum_subtypes(sub, store) {
+            Some(subs) => leaves.extend(get_leaves(&subs, store)),
+            None => leaves.push(*sub),
+        }
+    }
+    leaves.sort_by(|a, b| a.name.cmp(&b.name));
+    leaves.dedup_by_key(|l| l.id);
+
+    leaves
+}
+
+/// The leaf enum for the supertype called `name`, e.g., `EdgeLeaf<'a>`
+///
+/// It only gets a lifetime if there is a reference in it.
+pub(crate) fn leaf_type(
+    name: &str,
+    leaves: &[&Object],
+    store: &SarzakModel,
+    lifetime: &str,
+) -> String {
+    if leaves.iter().any(|l| Variant::of(l, store).is_stored()) {
+        format!("{}Leaf<{}>", name, lifetime)
+    } else {
+        format!("{}Leaf", name)
+    }
+}
+
+/// The id for a subtype that doesn't have one
+///
+/// Same as a singleton would get, so it doesn't change if it turns into one.
+pub(crate) fn unit_id(object: &Object) -> Uuid {
+    Uuid::new_v5(&UUID_NS, object.name.as_bytes())
+}
+
+/// Can a struct with an attribute of this type derive `Default`?
+///
+/// `SystemTime` doesn't have a default. I suppose the epoch would do, but it's
+/// not up to me.
+pub(crate) fn has_default(ty: &Type) -> bool {
+    !matches!(ty, Type::Timestamp)
+}
+
+/// How an attribute of this type is formatted into the `id` in `new`
+///
+/// The originals all have a `Display`, and changing them would change every
+/// `id`. The rest get `Debug`.
+pub(crate) fn id_format_spec(ty: &Type) -> &'static str {
+    match ty {
+        Type::Timestamp
+        | Type::Duration
+        | Type::Bytes
+        | Type::Optional(_)
+        | Type::Enumeration(_) => "{:?}::",
+        _ => "{}::",
+    }
+}
+
+/// How `new` comes up with an `id`, for an object that cares
+///
+/// An object cares if it's declared identifiers, or has an
+/// [`IdentityPolicy`] in it's configuration. Those get a `new` that checks the
+/// store, and returns a `Result`. Everything else gets the same old `new`.
+pub(crate) struct Identity<'a> {
+    pub policy: IdentityPolicy,
+    /// The declared identifiers, by number, each with it's attributes sorted by name
+    pub identifiers: BTreeMap<u16, Vec<&'a Attribute>>,
+}
+
+impl<'a> Identity<'a> {
+    pub(crate) fn of(object: &Object, store: &'a SarzakModel) -> Option<Self> {
+        let mut identifiers: BTreeMap<u16, Vec<&Attribute>> = BTreeMap::new();
+        for id in object.attributes.values() {
+            if let Some(attr) = store.sarzak.exhume_attribute(id) {
+                // It's what we are making, so it can't be what we make it from.
+                if attr.name.inner() != "id" {
+                    for number in &attr.identifiers {
+                        identifiers.entry(*number).or_default().push(attr);
+                    }
+                }
+            }
+        }
+        for attrs in identifiers.values_mut() {
+            attrs.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        let policy = match store.config.get_identity(&object.id) {
+            Some(policy) => policy,
+            None if !identifiers.is_empty() => IdentityPolicy::Identifier,
+            None => return None,
+        };
+
+        Some(Self {
+            policy,
+            identifiers,
+        })
+    }
+
+    /// The identifier that the `id` is made out of, if there is one
+    pub(crate) fn primary(&self) -> Option<(u16, &[&'a Attribute])> {
+        match self.policy {
+            IdentityPolicy::Identifier => self
+                .identifiers
+                .iter()
+                .next()
+                .map(|(n, attrs)| (*n, attrs.as_slice())),
+            IdentityPolicy::Random | IdentityPolicy::Supplied => None,
+        }
+    }
+}
+
+/// What to tack on to a call to `new` to get the instance
+pub(crate) fn new_unwrap(object: &Object, store: &SarzakModel) -> &'static str {
+    if Identity::of(object, store).is_some() {
+        ".unwrap()"
+    } else {
+        ""
+    }
+}
@@ -1,7 +1,52 @@
+//! Generate Domain Code
+//!
+//! Types, an ObjectStore, and relationship navigation macros, for some domain.
 pub mod generate_macros;
 pub mod generate_store;
 pub mod generate_types;
 
-pub use generate_macros::generate_macros;
-pub use generate_store::generate_store;
-pub use generate_types::generate_types;
+use crate::codegen::Formatter;
+
+pub use generate_macros::{generate_macros, macros_context};
+pub use generate_store::{generate_store, store_context};
+pub use generate_types::{generate_types, types_context};
+
+/// How to generate a domain
+///
+/// These used to be arguments, one at a time, to each of the generators. There
+/// got to be too many of them to keep straight, what with all of the `false`s in
+/// a row. The flags are all off by default, so just turn on what you want:
+///
+/// ```ignore
+/// let options = GenerateOptions {
+///     doc_tests: true,
+///     new_impl: true,
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Not everything matters to every generator. The store doesn't care about
+/// `new_impl`, for instance.
+#[derive(Clone, Debug, Default)]
+pub struct GenerateOptions {
+    /// Generating for sarzak and drawing, which changes how imports work
+    pub meta: bool,
+    /// Generate doc tests for `new`, `test_default`, and the macros
+    pub doc_tests: bool,
+    /// Generate `new` for structs
+    pub new_impl: bool,
+    /// Generate `Extrude` implementations
+    pub extrude_impl: bool,
+    /// Output ignored blocks anyway
+    pub ignore_ignore: bool,
+    /// Don't write anything, just fail if the file is out of date
+    pub check: bool,
+    /// Fail rather than comment out user code in a critical block
+    pub fail_on_displaced: bool,
+    /// Overwrite files written by a newer nut
+    pub allow_newer: bool,
+    /// Write a source map next to the generated file
+    pub source_map: bool,
+    /// What to run the generated code through, `rustfmt` by default
+    pub formatter: Formatter,
+}
@@ -9,21 +9,46 @@ use crate::codegen::{
     emit_assoc_many, emit_assoc_maybe_get_one, emit_associative_main, emit_binary_main,
     emit_generated_code_comments, emit_many_conditional_lookup, emit_many_unconditional,
     emit_one_conditional, emit_one_conditional_lookup, emit_one_unconditional,
-    emit_one_unconditional_lookup, emitln, function, Context, Result, SarzakModel,
+    emit_one_unconditional_lookup, emitln, function, Construct, Context, Displaced, ElementKind,
+    Result, SarzakModel,
 };
+use crate::domain::GenerateOptions;
 use crate::sarzak::{Associative, Binary, Cardinality, Conditionality, Relationship};
 
 pub fn generate_macros(
     model: &SarzakModel,
     output_file: &PathBuf,
     package: &str,
-    _meta: bool,
-    doc_tests: bool,
-    ignore_ignore: bool,
-) -> Result<()> {
+    options: &GenerateOptions,
+) -> Result<Vec<Displaced>> {
+    macros_context(model, output_file, package, options)?.finish(options.check)
+}
+
+/// Generate macros.rs, without writing it
+///
+/// Hand the [`Context`] to a [`ContextGroup`](crate::codegen::ContextGroup) to write it
+/// along with the rest of the domain.
+pub fn macros_context(
+    model: &SarzakModel,
+    output_file: &PathBuf,
+    package: &str,
+    options: &GenerateOptions,
+) -> Result<Context> {
+    let GenerateOptions {
+        doc_tests,
+        ignore_ignore,
+        fail_on_displaced,
+        allow_newer,
+        source_map,
+        ..
+    } = *options;
     let domain = &model.domain;
 
     let mut context = Context::new(output_file, ignore_ignore)?;
+    context.fail_on_displaced(fail_on_displaced);
+    context.allow_newer(allow_newer);
+    context.source_map(source_map);
+    context.formatter(options.formatter.clone());
 
     // Generate code for all of the objects in the input_schema
     emitln!(
@@ -64,7 +89,7 @@ pub fn generate_macros(
         }
     }
 
-    context.commit()
+    Ok(context)
 }
 
 fn generate_associative_macros(
@@ -135,7 +160,14 @@ fn generate_associative_macros(
         one_ref.cardinality == Cardinality::Many,
     )?;
 
+    context.begin_element(
+        Construct::Macro,
+        ElementKind::Relationship,
+        ass.id,
+        format!("R{}", ass.number),
+    )?;
     *context += outer.into();
+    context.end_element(ass.id)?;
 
     // This takes care of the other direction.
     let (inner, name) = match other_ref.cardinality {
@@ -187,7 +219,14 @@ fn generate_associative_macros(
         other_ref.cardinality == Cardinality::Many,
     )?;
 
+    context.begin_element(
+        Construct::Macro,
+        ElementKind::Relationship,
+        ass.id,
+        format!("R{}", ass.number),
+    )?;
     *context += outer.into();
+    context.end_element(ass.id)?;
 
     Ok(())
 }
@@ -309,7 +348,14 @@ fn generate_binary_macro_referrer_to_referent_imp(
         false,
     )?;
 
+    context.begin_element(
+        Construct::Macro,
+        ElementKind::Relationship,
+        binary.id,
+        format!("R{}", binary.number),
+    )?;
     *context += body.into();
+    context.end_element(binary.id)?;
 
     Ok(())
 }
@@ -438,7 +484,14 @@ fn generate_binary_macro_referent_to_referrer_imp(
         many, // And then there is this
     )?;
 
+    context.begin_element(
+        Construct::Macro,
+        ElementKind::Relationship,
+        binary.id,
+        format!("R{}", binary.number),
+    )?;
     *context += body.into();
+    context.end_element(binary.id)?;
 
     Ok(())
 }
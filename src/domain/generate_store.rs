@@ -6,27 +6,57 @@ use std::path::PathBuf;
 use heck::{ToSnakeCase, ToTitleCase};
 use log::{debug, trace};
 
-use crate::codegen::{emit_generated_code_comments, emitln, Context, Result, SarzakModel};
+use crate::codegen::{
+    emit_generated_code_comments, emitln,
+    types::{get_enum_subtypes, get_hierarchies, get_leaves, is_multi_level, leaf_type},
+    Context, Displaced, Result, SarzakModel, Variant,
+};
+use crate::domain::GenerateOptions;
 use crate::sarzak::{Object, AS_IDENT, AS_TYPE};
 
 pub fn generate_store(
+    model: &SarzakModel,
+    out_file: &PathBuf,
+    package: &str,
+    options: &GenerateOptions,
+) -> Result<Vec<Displaced>> {
+    store_context(model, out_file, package, options)?.finish(options.check)
+}
+
+/// Generate store.rs, without writing it
+///
+/// Hand the [`Context`] to a [`ContextGroup`](crate::codegen::ContextGroup) to write it
+/// along with the rest of the domain.
+pub fn store_context(
     model: &SarzakModel,
     out_file: &PathBuf,
     _package: &str,
-    _meta: bool,
-    _doc_tests: bool,
-) -> Result<()> {
+    options: &GenerateOptions,
+) -> Result<Context> {
+    let GenerateOptions {
+        fail_on_displaced,
+        allow_newer,
+        source_map,
+        ..
+    } = *options;
     let domain = &model.domain;
 
     debug!("Generating ObjectStore for {}.", domain);
 
     let mut context = Context::new(out_file, false)?;
+    context.fail_on_displaced(fail_on_displaced);
+    context.allow_newer(allow_newer);
+    context.source_map(source_map);
+    context.formatter(options.formatter.clone());
 
     let mut objects: Vec<&Object> = model
         .sarzak
         .iter_object()
         .filter_map(|(id, obj)| {
-            if model.config.is_imported(&id) || model.config.is_singleton(&id) {
+            if model.config.is_imported(id)
+                || model.config.is_singleton(id)
+                || Variant::is_unit(obj, model)
+            {
                 None
             } else {
                 Some(obj)
@@ -67,7 +97,7 @@ pub fn generate_store(
     emitln!(context, "use uuid::Uuid;");
     emitln!(context, "");
 
-    emit_use_statement(domain, &objects, &mut context);
+    emit_use_statement(domain, &objects, model, &mut context);
 
     emitln!(context, "");
     emitln!(context, "#[derive(Clone, Debug, Deserialize, Serialize)]");
@@ -99,7 +129,7 @@ pub fn generate_store(
 
     emitln!(context, "}");
 
-    context.commit()
+    Ok(context)
 }
 
 fn generate_new_impl(objects: &Vec<&Object>, context: &mut Context) {
@@ -204,9 +234,161 @@ fn generate_type_impls(obj: &Object, model: &SarzakModel, context: &mut Context)
     );
     emitln!(context, "        self.{}.iter()", lower);
     emitln!(context, "    }");
+
+    if let Some(subtypes) = get_enum_subtypes(obj, model) {
+        if is_multi_level(&subtypes, model) {
+            generate_leaf_impl(obj, &subtypes, model, context);
+        }
+    }
+
+    let hierarchies = get_hierarchies(obj, model);
+    if hierarchies.len() > 1 {
+        let fields = hierarchies
+            .iter()
+            .map(|(isa, _)| format!("r{}", isa.number))
+            .collect::<Vec<_>>();
+        generate_subtype_lookup(obj, &fields, context);
+    }
+}
+
+/// Exhume a supertype in more than one hierarchy by the id of a subtype
+///
+/// A supertype like this is a struct, with an id of it's own. This finds
+/// the instance with a field holding the subtype.
+fn generate_subtype_lookup(obj: &Object, fields: &[String], context: &mut Context) {
+    let lower = obj.render(AS_IDENT);
+    let matches = fields
+        .iter()
+        .map(|f| format!("{}.{}.get_id() == *id", lower, f))
+        .collect::<Vec<_>>()
+        .join(" || ");
+
+    emitln!(context, "");
+    emitln!(
+        context,
+        "    /// Exhume the [`{}`] that a subtype is part of",
+        obj.render(AS_TYPE)
+    );
+    emitln!(context, "    ///");
+    emitln!(
+        context,
+        "    /// `id` is the id of a subtype, in any of it's hierarchies."
+    );
+    emitln!(context, "    ///");
+    emitln!(
+        context,
+        "    pub fn exhume_{}_by_subtype(&self, id: &Uuid) -> Option<&{}> {{",
+        lower,
+        obj.render(AS_TYPE)
+    );
+    emitln!(context, "        self.{}", lower);
+    emitln!(context, "            .values()");
+    emitln!(context, "            .find(|{}| {})", lower, matches);
+    emitln!(context, "    }");
 }
 
-fn emit_use_statement(domain: &str, objects: &Vec<&Object>, context: &mut Context) {
+/// Exhume the instance at the bottom of a supertype with more than one level
+///
+/// It's nested matches, all the way down. A variant that's another enum gets
+/// looked up, and matched on in turn.
+fn generate_leaf_impl(
+    obj: &Object,
+    subtypes: &[&Object],
+    model: &SarzakModel,
+    context: &mut Context,
+) {
+    let leaves = get_leaves(subtypes, model);
+    let leaf = format!("{}Leaf", obj.render(AS_TYPE));
+
+    emitln!(context, "");
+    emitln!(
+        context,
+        "    /// Exhume the instance at the bottom of the [`{}`] hierarchy",
+        obj.render(AS_TYPE)
+    );
+    emitln!(context, "    ///");
+    emitln!(
+        context,
+        "    /// Subtypes that are supertypes are followed down until there aren't any more."
+    );
+    emitln!(
+        context,
+        "    pub fn exhume_{}_leaf(&self, id: &Uuid) -> Option<{}> {{",
+        obj.render(AS_IDENT),
+        leaf_type(&obj.render(AS_TYPE), &leaves, model, "'_")
+    );
+    emitln!(
+        context,
+        "        let {} = self.exhume_{}(id)?;",
+        obj.render(AS_IDENT),
+        obj.render(AS_IDENT)
+    );
+    generate_leaf_match(obj, subtypes, &leaf, model, context, 2);
+    emitln!(context, "    }");
+}
+
+fn generate_leaf_match(
+    obj: &Object,
+    subtypes: &[&Object],
+    leaf: &str,
+    model: &SarzakModel,
+    context: &mut Context,
+    depth: usize,
+) {
+    let indent = "    ".repeat(depth);
+
+    emitln!(context, "{}match {} {{", indent, obj.render(AS_IDENT));
+    for sub in subtypes {
+        let variant = format!("{}::{}", obj.render(AS_TYPE), sub.render(AS_TYPE));
+        match (Variant::of(sub, model), get_enum_subtypes(sub, model)) {
+            (_, Some(subs)) => {
+                emitln!(context, "{}    {}(id) => {{", indent, variant);
+                emitln!(
+                    context,
+                    "{}        let {} = self.exhume_{}(id)?;",
+                    indent,
+                    sub.render(AS_IDENT),
+                    sub.render(AS_IDENT)
+                );
+                generate_leaf_match(sub, &subs, leaf, model, context, depth + 2);
+                emitln!(context, "{}    }}", indent);
+            }
+            (Variant::Instance | Variant::Supertype, None) => emitln!(
+                context,
+                "{}    {}(id) => self.exhume_{}(id).map({}::{}),",
+                indent,
+                variant,
+                sub.render(AS_IDENT),
+                leaf,
+                sub.render(AS_TYPE)
+            ),
+            (Variant::Singleton, None) => emitln!(
+                context,
+                "{}    {}(id) => Some({}::{}(*id)),",
+                indent,
+                variant,
+                leaf,
+                sub.render(AS_TYPE)
+            ),
+            (Variant::Unit, None) => emitln!(
+                context,
+                "{}    {} => Some({}::{}),",
+                indent,
+                variant,
+                leaf,
+                sub.render(AS_TYPE)
+            ),
+        }
+    }
+    emitln!(context, "{}}}", indent);
+}
+
+fn emit_use_statement(
+    domain: &str,
+    objects: &Vec<&Object>,
+    model: &SarzakModel,
+    context: &mut Context,
+) {
     let mut use_decl = format!("use crate::{}::types::{{", domain.to_snake_case());
 
     objects.iter().for_each(|obj| {
@@ -214,6 +396,15 @@ fn emit_use_statement(domain: &str, objects: &Vec<&Object>, context: &mut Contex
         use_decl += ", ";
     });
 
+    // The leaves of the supertypes that have them
+    objects.iter().for_each(|obj| {
+        if let Some(subtypes) = get_enum_subtypes(obj, model) {
+            if is_multi_level(&subtypes, model) {
+                use_decl += &format!("{}Leaf, ", obj.render(AS_TYPE));
+            }
+        }
+    });
+
     use_decl += "};";
 
     emitln!(context, "{}", use_decl);
@@ -7,28 +7,53 @@ use std::path::PathBuf;
 use heck::{ToSnakeCase, ToTitleCase};
 
 use crate::codegen::{
-    begin_crit, emit_enum_main, emit_generated_code_comments, emit_object_comments, emit_singleton,
-    emit_struct_main, emitln, end_crit, get_subtypes, Context, Result, SarzakModel,
+    begin_crit, emit_duplicate_identifier, emit_enum_main, emit_enumeration, emit_generalization,
+    emit_generated_code_comments, emit_object_comments, emit_singleton, emit_struct_main, emitln,
+    end_crit,
+    types::{get_hierarchies, Identity},
+    Context, Displaced, Result, SarzakModel, Variant,
 };
-use crate::sarzak::{Attribute, Object, RelPointer, RelSide, AS_CONST, AS_IDENT, AS_TYPE};
+use crate::domain::GenerateOptions;
+use crate::sarzak::{Attribute, Object, AS_CONST, AS_IDENT, AS_TYPE};
 
 pub fn generate_types(
     store: &SarzakModel,
     out_file: &PathBuf,
     package: &str,
-    meta: bool,
-    doc_tests: bool,
-    new_impl: bool,
-    extrude_impl: bool,
-    ignore_ignore: bool,
-) -> Result<()> {
+    options: &GenerateOptions,
+) -> Result<Vec<Displaced>> {
+    types_context(store, out_file, package, options)?.finish(options.check)
+}
+
+/// Generate types.rs, without writing it
+///
+/// Hand the [`Context`] to a [`ContextGroup`](crate::codegen::ContextGroup) to write it
+/// along with the rest of the domain.
+pub fn types_context(
+    store: &SarzakModel,
+    out_file: &PathBuf,
+    package: &str,
+    options: &GenerateOptions,
+) -> Result<Context> {
+    let GenerateOptions {
+        meta,
+        doc_tests,
+        new_impl,
+        extrude_impl,
+        ignore_ignore,
+        fail_on_displaced,
+        allow_newer,
+        source_map,
+        ..
+    } = *options;
     let domain = &store.domain;
 
     let mut objects: Vec<&Object> = store
         .sarzak
         .iter_object()
         .filter_map(|(id, obj)| {
-            if store.config.is_imported(&id) {
+            // A unit subtype is just a variant, so there's nothing to generate.
+            if store.config.is_imported(id) || Variant::is_unit(obj, store) {
                 None
             } else {
                 Some(obj)
@@ -40,6 +65,10 @@ pub fn generate_types(
     objects.sort_by(|a, b| a.name.cmp(&b.name));
 
     let mut context = Context::new(out_file, ignore_ignore)?;
+    context.fail_on_displaced(fail_on_displaced);
+    context.allow_newer(allow_newer);
+    context.source_map(source_map);
+    context.formatter(options.formatter.clone());
 
     // Generate code for all of the objects in the input_schema
     //
@@ -59,12 +88,40 @@ pub fn generate_types(
     emitln!(context, "//!");
     emitln!(context, "//! The following types are defined herein:");
 
+    let enumerations = store.sarzak.sorted_enumerations();
+
+    // Only a `new` that cares about identity can fail, so only then do we need
+    // something to fail with.
+    let identified = new_impl
+        && objects.iter().any(|obj| {
+            !store.config.is_singleton(&obj.id)
+                && get_hierarchies(obj, store).is_empty()
+                && Identity::of(obj, store).is_some()
+        });
+
     // Make some nice documentation
+    if identified {
+        emitln!(context, "//!    * [`DuplicateIdentifier`]");
+    }
+    for enumeration in &enumerations {
+        emitln!(context, "//!    * [`{}`]", enumeration.render(AS_TYPE));
+    }
     for obj in &objects {
         if store.config.is_singleton(&obj.id) {
             emitln!(context, "//!    * [`{}`]", obj.render(AS_CONST));
         } else {
             emitln!(context, "//!    * [`{}`]", obj.render(AS_TYPE));
+            let hierarchies = get_hierarchies(obj, store);
+            if hierarchies.len() > 1 {
+                for (isa, _) in hierarchies {
+                    emitln!(
+                        context,
+                        "//!    * [`{}R{}`]",
+                        obj.render(AS_TYPE),
+                        isa.number
+                    );
+                }
+            }
         }
     }
     emitln!(context, "//!");
@@ -154,6 +211,15 @@ pub fn generate_types(
         emitln!(context, "");
     }
 
+    if identified {
+        context += emit_duplicate_identifier()?.into();
+    }
+
+    // User defined enumerations go first, since they are used by what follows.
+    for enumeration in &enumerations {
+        context += emit_enumeration(enumeration)?.into();
+    }
+
     // Main code generation loop
     //
     for object in objects.iter() {
@@ -170,20 +236,10 @@ pub fn generate_types(
         } else {
             // We need to determine if we are writing a struct or an enum. If the object is a
             // supertype then we generate an enum.
-            let super_rel = object
-                .rels
-                .iter()
-                .filter_map(|(_, r_ptr)| {
-                    if r_ptr.side == RelSide::Supertype {
-                        Some(r_ptr)
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<&RelPointer>>();
+            let mut hierarchies = get_hierarchies(object, store);
 
             // We are not a supertype, so just do regular struct stuff.
-            if super_rel.len() == 0 {
+            if hierarchies.is_empty() {
                 let mut attrs: Vec<&Attribute> = object
                     .attributes
                     .iter()
@@ -205,28 +261,27 @@ pub fn generate_types(
                     extrude_impl,
                 )?
                 .into();
-            } else {
+            } else if hierarchies.len() == 1 {
                 // ✋✋✋✋ This next bit is important 🤚🤚🤚🤚
                 // Maybe not that important. I was just looking at how the Edge enum is generated.
                 // It looks weird. I was convinced that it was a terrible implementation, and now
                 // I think it may be optimal. I can't decide right now, so maybe look into it's
                 // repr at some point in the future.
-                //
-                // I'm doing this iteration, but it doesn't make sense. What do you do with an object
-                // with two super/sub relationships in Rust? We can work something out, I'm sure.
-                for rel in super_rel.iter() {
-                    let mut subtypes = get_subtypes!(rel.value, store.sarzak);
-                    subtypes.sort();
-
-                    context +=
-                        emit_enum_main(object, subtypes, store, domain, package, doc_tests)?.into();
-                }
+                let (_, subtypes) = hierarchies.remove(0);
+                context +=
+                    emit_enum_main(object, subtypes, store, domain, package, doc_tests)?.into();
+            } else {
+                // What do you do with an object with two super/sub relationships in Rust?
+                // An enum per relationship, and a struct to hold them.
+                context +=
+                    emit_generalization(object, hierarchies, store, domain, package, doc_tests)?
+                        .into();
             }
         }
     }
 
     // context.write("include!(\"drawing_impls.rs\");");
-    context.commit()
+    Ok(context)
 }
 
 // fn make_new_object(
@@ -1,10 +1,13 @@
 //! The Drawing Domain
 //!
 //! These are the bits that belong to Drawing, the domain of drawing boxen and lines!
+use std::collections::HashMap;
+
 use uuid::{uuid, Uuid};
 
 mod drawing;
 mod object_store;
+pub mod svg;
 
 #[macro_use]
 mod macros;
@@ -28,6 +31,7 @@ pub use drawing::{
     RelationshipUI, Right, Top,
 };
 pub use object_store::ObjectStore;
+pub use svg::to_svg;
 
 // ISO OID for "drawing".
 pub const UUID_NS: Uuid = uuid!("0d035b17-076b-5848-b20f-ee05ce7738a9");
@@ -71,8 +75,16 @@ pub const UUID_NS: Uuid = uuid!("0d035b17-076b-5848-b20f-ee05ce7738a9");
 /// Extrude implementations. That reminds me, the From impls need to be in the same
 /// file as the generated code. So, I can either copy and paste all of the impls
 /// into the code generation script. Or, what?
-use crate::codegen::{Extrude, SarzakObjectStore};
-use crate::model::{extract_ooa2, JSFormat};
+use crate::codegen::{CodeGenError, Error, Extrude, Result, SarzakObjectStore};
+use crate::model::{
+    extract_ooa2,
+    jsformat::{
+        AnchorPoint, Associative as FromAssociative, Direction, Isa as FromIsa, Paper,
+        Point as FromPoint, PointTuple, Rect, RelationshipUI as FromRelationshipUI,
+    },
+    JSFormat,
+};
+use crate::ooa_2::Relationship;
 
 struct Context<'a> {
     sarzak: &'a SarzakObjectStore,
@@ -80,10 +92,18 @@ struct Context<'a> {
     id: Option<Uuid>,
 }
 
-impl From<JSFormat> for ObjectStore {
-    fn from(input: JSFormat) -> Self {
+impl TryFrom<JSFormat> for ObjectStore {
+    type Error = Error;
+
+    fn try_from(input: JSFormat) -> Result<Self> {
         let mut store = ObjectStore::new();
-        let sarzak = extract_ooa2(&input);
+        let sarzak = extract_ooa2(&input)?;
+
+        // The extrusions below just assume that the paper is pointing at things that
+        // exist, and are what they claim to be. Make it so.
+        for paper in input.paper.entities.values() {
+            check_paper(paper, &sarzak)?;
+        }
 
         let mut context = Context {
             sarzak: &sarzak,
@@ -91,7 +111,7 @@ impl From<JSFormat> for ObjectStore {
             id: None,
         };
 
-        input.paper.entities.into_iter().next().map(|(_id, paper)| {
+        if let Some((_id, paper)) = input.paper.entities.into_iter().next() {
             paper.objects.into_iter().for_each(|(id, obj)| {
                 context.id = Some(id);
                 let obj = ObjectUI::extrude(obj, &mut context);
@@ -102,8 +122,189 @@ impl From<JSFormat> for ObjectStore {
                 let rel = RelationshipUI::extrude(rel, &mut context);
                 context.drawing.inter_relationship_ui(rel);
             });
-        });
+        }
+
+        Ok(store)
+    }
+}
+
+fn check_paper(paper: &Paper, sarzak: &SarzakObjectStore) -> Result<()> {
+    for id in paper.objects.keys() {
+        if sarzak.exhume_object(id).is_none() {
+            return Err(Error(CodeGenError::MissingEntity {
+                kind: "object".to_owned(),
+                id: *id,
+            }));
+        }
+    }
+
+    for (id, ui) in &paper.relationships {
+        let expected = match ui {
+            FromRelationshipUI::BinaryUI(_) => "binary",
+            FromRelationshipUI::IsaUI(_) => "isa",
+            FromRelationshipUI::AssociativeUI(_) => "associative",
+        };
+        let found = match sarzak.exhume_relationship(id) {
+            Some(Relationship::Binary(_)) => "binary",
+            Some(Relationship::Isa(_)) => "isa",
+            Some(Relationship::Associative(_)) => "associative",
+            None => {
+                return Err(Error(CodeGenError::MissingEntity {
+                    kind: "relationship".to_owned(),
+                    id: *id,
+                }))
+            }
+        };
+        if expected != found {
+            return Err(Error(CodeGenError::WrongEntityKind {
+                id: *id,
+                expected: expected.to_owned(),
+                found: found.to_owned(),
+            }));
+        }
+    }
+
+    Ok(())
+}
 
-        store
+/// Flatten the drawing back into what goes on Cuckoo's paper
+///
+/// This undoes the extrusion in `drawing_impls.rs`. It's nearly lossless. The one
+/// thing that doesn't survive is which object an anchor was attached to, since the
+/// drawing domain never modeled that. Most of the time the relationship tells us.
+/// For the subtype end of an isa it doesn't, so we pick the subtype whose box is
+/// closest to the anchor. Cuckoo puts anchors right on the edge of the box, so
+/// that's not much of a guess.
+pub(crate) fn flatten_paper(
+    drawing: &ObjectStore,
+    sarzak: &SarzakObjectStore,
+) -> (HashMap<Uuid, Rect>, HashMap<Uuid, FromRelationshipUI>) {
+    let mut boxes = HashMap::new();
+    let objects = drawing
+        .iter_object_ui()
+        .filter_map(|(_, ui)| {
+            let origin = drawing.exhume_point(&ui.origin)?;
+            let rect = Rect {
+                x: origin.x as i32,
+                y: origin.y as i32,
+                width: ui.width as u16,
+                height: ui.height as u16,
+            };
+            boxes.insert(ui.object, (origin.x, origin.y, ui.width, ui.height));
+            Some((ui.object, rect))
+        })
+        .collect();
+
+    let mut relationships = HashMap::new();
+    for (_, ui) in drawing.iter_binary_ui() {
+        let binary = sarzak.exhume_binary(&ui.binary);
+        let referrer = binary.and_then(|b| sarzak.exhume_referrer(&b.from));
+        let referent = binary.and_then(|b| sarzak.exhume_referent(&b.to));
+        if let (Some(referrer), Some(referent)) = (referrer, referent) {
+            if let (Some(from), Some(to)) = (
+                anchor_point(drawing, &ui.from, referrer.obj_id),
+                anchor_point(drawing, &ui.to, referent.obj_id),
+            ) {
+                relationships.insert(
+                    ui.binary,
+                    FromRelationshipUI::BinaryUI(PointTuple { from, to }),
+                );
+            }
+        }
+    }
+
+    for (_, ui) in drawing.iter_isa_ui() {
+        let isa = match sarzak.exhume_isa(&ui.isa) {
+            Some(isa) => isa,
+            None => continue,
+        };
+        let subtypes: Vec<Uuid> = isa
+            .subtypes
+            .iter()
+            .filter_map(|s| sarzak.exhume_subtype(s))
+            .map(|s| s.obj_id)
+            .collect();
+
+        let from = sarzak
+            .exhume_supertype(&isa.supertype)
+            .and_then(|s| anchor_point(drawing, &ui.from, s.obj_id));
+        let to = ui
+            .to
+            .iter()
+            .map(|id| {
+                let location = drawing
+                    .exhume_anchor(id)
+                    .and_then(|a| drawing.exhume_point(&a.location))?;
+                let nearest = subtypes.iter().min_by_key(|s| match boxes.get(*s) {
+                    Some(b) => distance(location.x, location.y, *b),
+                    None => i64::MAX,
+                })?;
+                anchor_point(drawing, id, *nearest)
+            })
+            .collect::<Option<Vec<_>>>();
+
+        if let (Some(from), Some(to)) = (from, to) {
+            relationships.insert(ui.isa, FromRelationshipUI::IsaUI(FromIsa { from, to }));
+        }
     }
+
+    for (_, ui) in drawing.iter_associative_ui() {
+        let assoc = sarzak.exhume_associative(&ui.associative_id);
+        let referrer = assoc.and_then(|a| sarzak.exhume_associative_referrer(&a.from));
+        let one = assoc.and_then(|a| sarzak.exhume_associative_referent(&a.one));
+        let other = assoc.and_then(|a| sarzak.exhume_associative_referent(&a.other));
+        if let (Some(referrer), Some(one), Some(other)) = (referrer, one, other) {
+            if let (Some(from), Some(middle), Some(one), Some(other)) = (
+                drawing.exhume_point(&ui.from),
+                anchor_point(drawing, &ui.middle, referrer.obj_id),
+                anchor_point(drawing, &ui.one, one.obj_id),
+                anchor_point(drawing, &ui.other, other.obj_id),
+            ) {
+                relationships.insert(
+                    ui.associative_id,
+                    FromRelationshipUI::AssociativeUI(FromAssociative {
+                        from: FromPoint {
+                            x: from.x as i32,
+                            y: from.y as i32,
+                        },
+                        middle,
+                        one,
+                        other,
+                    }),
+                );
+            }
+        }
+    }
+
+    (objects, relationships)
+}
+
+fn anchor_point(drawing: &ObjectStore, id: &Uuid, obj_id: Uuid) -> Option<AnchorPoint> {
+    let anchor = drawing.exhume_anchor(id)?;
+    let location = drawing.exhume_point(&anchor.location)?;
+    let offset = drawing.exhume_point(&anchor.offset)?;
+    let dir = match anchor.edge {
+        e if e == Top => Direction::North,
+        e if e == Right => Direction::East,
+        e if e == Bottom => Direction::South,
+        _ => Direction::West,
+    };
+
+    Some(AnchorPoint {
+        id: obj_id,
+        dir,
+        x: location.x as i32,
+        y: location.y as i32,
+        offset: FromPoint {
+            x: offset.x as i32,
+            y: offset.y as i32,
+        },
+    })
+}
+
+/// How far a point is from a box, zero if it's inside or on the edge
+fn distance(x: i64, y: i64, (bx, by, width, height): (i64, i64, i64, i64)) -> i64 {
+    let dx = (bx - x).max(x - (bx + width)).max(0);
+    let dy = (by - y).max(y - (by + height)).max(0);
+    dx * dx + dy * dy
 }
@@ -49,7 +49,8 @@ impl Extrude<from::AnchorPoint, Context<'_>> for to::Anchor {
         let edge_id = edge.get_id();
         context.drawing.inter_edge(edge);
 
-        let point = to::Point::new(value.x as i64, value.y as i64);
+        // The offset is relative to the anchor, and it's where the phrase goes.
+        let point: to::Point = value.offset.into();
         let point_id = point.id;
         context.drawing.inter_point(point);
 
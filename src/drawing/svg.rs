@@ -0,0 +1,429 @@
+//! SVG Rendering
+//!
+//! The drawing domain knows where everything goes, so we don't need a browser to
+//! draw a model. This walks the [`ObjectUI`]s, [`BinaryUI`]s, [`IsaUI`]s and
+//! [`AssociativeUI`]s and writes a standalone SVG document. It looks enough like
+//! Cuckoo that you'll recognize it.
+//!
+//! Boxes get the object name and attributes. Binary relationships are a line
+//! between their anchors, with a single arrowhead for one, a double for many, and
+//! the phrase and multiplicity at the anchor offset. Isa relationships fan out
+//! from the supertype, and associative relationships get a dashed line from the
+//! association down to the link class.
+//!
+//! Every group carries the id of the model element that it draws, which makes it
+//! easy to find things with a bit of CSS or script.
+//!
+//! The view box is fitted to what's drawn, not to the paper, so there's no acre
+//! of white space around the model.
+use std::{collections::HashMap, fmt::Write};
+
+use uuid::Uuid;
+
+use crate::codegen::SarzakModel;
+use crate::drawing::{
+    Anchor, AssociativeUI, BinaryUI, Bottom, IsaUI, Left, ObjectStore, Right, Top,
+};
+use crate::sarzak::export::{multiplicity, type_name};
+
+const MARGIN: i64 = 40;
+const HEADER: i64 = 24;
+const LINE: i64 = 16;
+/// How far the isa fan sticks out from the supertype
+const FAN: i64 = 40;
+
+const PREAMBLE: &str = r#"  <defs>
+    <marker id="one" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="10" markerHeight="10" orient="auto-start-reverse">
+      <path d="M 0 0 L 10 5 L 0 10" fill="none" stroke="black"/>
+    </marker>
+    <marker id="many" viewBox="0 0 20 10" refX="20" refY="5" markerWidth="20" markerHeight="10" orient="auto-start-reverse">
+      <path d="M 0 0 L 10 5 L 0 10 M 10 0 L 20 5 L 10 10" fill="none" stroke="black"/>
+    </marker>
+    <marker id="isa" viewBox="0 0 12 12" refX="12" refY="6" markerWidth="12" markerHeight="12" orient="auto-start-reverse">
+      <path d="M 0 0 L 12 6 L 0 12 Z" fill="white" stroke="black"/>
+    </marker>
+  </defs>
+  <style>
+    .object { fill: white; stroke: black; }
+    .name { font-weight: bold; text-anchor: middle; }
+    .relationship { fill: none; stroke: black; }
+    .associative { fill: none; stroke: black; stroke-dasharray: 4 4; }
+    .number { font-style: italic; }
+  </style>
+"#;
+
+/// Render the model as a standalone SVG document
+pub fn to_svg(model: &SarzakModel) -> String {
+    let mut svg = Svg::new(&model.drawing);
+
+    // Objects, sorted by name so that the output doesn't move around.
+    let mut objects: Vec<_> = model
+        .drawing
+        .iter_object_ui()
+        .filter_map(|(_, ui)| model.sarzak.exhume_object(&ui.object).map(|o| (o, ui)))
+        .collect();
+    objects.sort_by(|a, b| a.0.name.inner().cmp(b.0.name.inner()));
+
+    for (object, ui) in objects {
+        let origin = match model.drawing.exhume_point(&ui.origin) {
+            Some(p) => (p.x, p.y),
+            None => continue,
+        };
+
+        let mut attributes: Vec<(String, String)> = object
+            .attributes
+            .iter()
+            .filter_map(|(name, id)| model.sarzak.exhume_attribute(id).map(|a| (name, a)))
+            .map(|(name, attr)| (name.clone(), type_name(&attr.attr_t, model)))
+            .collect();
+        attributes.sort_by(|a, b| (a.0 != "id", &a.0).cmp(&(b.0 != "id", &b.0)));
+
+        svg.object(
+            &object.id,
+            object.name.inner(),
+            &attributes,
+            origin,
+            (ui.width, ui.height),
+        );
+    }
+
+    // Relationships, in numerical order.
+    let binaries: HashMap<Uuid, &BinaryUI> = model
+        .drawing
+        .iter_binary_ui()
+        .map(|(_, ui)| (ui.binary, ui))
+        .collect();
+    let isas: HashMap<Uuid, &IsaUI> = model
+        .drawing
+        .iter_isa_ui()
+        .map(|(_, ui)| (ui.isa, ui))
+        .collect();
+    let associatives: HashMap<Uuid, &AssociativeUI> = model
+        .drawing
+        .iter_associative_ui()
+        .map(|(_, ui)| (ui.associative_id, ui))
+        .collect();
+
+    let store = &model.sarzak;
+    for rel in store.sorted_relationships() {
+        let id = rel.get_id();
+        if let (Some(ui), Some(binary)) = (binaries.get(id), store.exhume_binary(id)) {
+            let referrer = store.exhume_referrer(&binary.from);
+            let referent = store.exhume_referent(&binary.to);
+            if let (Some(referrer), Some(referent)) = (referrer, referent) {
+                svg.binary(
+                    binary.number,
+                    ui,
+                    (
+                        &referrer.description,
+                        multiplicity(&referrer.cardinality, &referrer.conditionality),
+                    ),
+                    (
+                        &referent.description,
+                        multiplicity(&referent.cardinality, &referent.conditionality),
+                    ),
+                );
+            }
+        } else if let (Some(ui), Some(isa)) = (isas.get(id), store.exhume_isa(id)) {
+            svg.isa(isa.number, ui);
+        } else if let (Some(ui), Some(assoc)) = (associatives.get(id), store.exhume_associative(id))
+        {
+            let one = store.exhume_associative_referent(&assoc.one);
+            let other = store.exhume_associative_referent(&assoc.other);
+            if let (Some(one), Some(other)) = (one, other) {
+                svg.associative(
+                    assoc.number,
+                    ui,
+                    (
+                        &one.description,
+                        multiplicity(&one.cardinality, &one.conditionality),
+                    ),
+                    (
+                        &other.description,
+                        multiplicity(&other.cardinality, &other.conditionality),
+                    ),
+                );
+            }
+        }
+    }
+
+    svg.finish(&model.domain)
+}
+
+/// Accumulates the body, and keeps track of how big it's gotten
+///
+/// Writing to a String doesn't fail, hence all of the `let _`s.
+struct Svg<'a> {
+    drawing: &'a ObjectStore,
+    body: String,
+    min: (i64, i64),
+    max: (i64, i64),
+}
+
+impl<'a> Svg<'a> {
+    fn new(drawing: &'a ObjectStore) -> Self {
+        Self {
+            drawing,
+            body: String::new(),
+            min: (i64::MAX, i64::MAX),
+            max: (i64::MIN, i64::MIN),
+        }
+    }
+
+    fn grow(&mut self, (x, y): (i64, i64)) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    fn object(
+        &mut self,
+        id: &Uuid,
+        name: &str,
+        attributes: &[(String, String)],
+        (x, y): (i64, i64),
+        (width, height): (i64, i64),
+    ) {
+        self.grow((x, y));
+        self.grow((x + width, y + height));
+
+        let _ = writeln!(self.body, "  <g id=\"{}\">", id);
+        let _ = writeln!(
+            self.body,
+            "    <rect class=\"object\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"6\"/>",
+            x, y, width, height
+        );
+        let _ = writeln!(
+            self.body,
+            "    <text class=\"name\" x=\"{}\" y=\"{}\">{}</text>",
+            x + width / 2,
+            y + HEADER - 7,
+            escape(name)
+        );
+        let _ = writeln!(
+            self.body,
+            "    <line class=\"relationship\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+            x,
+            y + HEADER,
+            x + width,
+            y + HEADER
+        );
+        for (i, (name, ty)) in attributes.iter().enumerate() {
+            let _ = writeln!(
+                self.body,
+                "    <text x=\"{}\" y=\"{}\">{}: {}</text>",
+                x + 8,
+                y + HEADER + LINE * (i as i64 + 1),
+                escape(name),
+                escape(ty)
+            );
+        }
+        let _ = writeln!(self.body, "  </g>");
+    }
+
+    fn binary(&mut self, number: u16, ui: &BinaryUI, from: (&str, &str), to: (&str, &str)) {
+        let (from_anchor, to_anchor) = match (
+            self.drawing.exhume_anchor(&ui.from),
+            self.drawing.exhume_anchor(&ui.to),
+        ) {
+            (Some(f), Some(t)) => (f, t),
+            _ => return,
+        };
+        let (start, end) = (self.location(from_anchor), self.location(to_anchor));
+
+        let _ = writeln!(self.body, "  <g id=\"{}\">", ui.binary);
+        let _ = writeln!(
+            self.body,
+            "    <path class=\"relationship\" d=\"M {} {} L {} {}\" marker-start=\"url(#{})\" marker-end=\"url(#{})\"/>",
+            start.0,
+            start.1,
+            end.0,
+            end.1,
+            marker(from.1),
+            marker(to.1)
+        );
+        self.phrase(from_anchor, from);
+        self.phrase(to_anchor, to);
+        self.number(
+            number,
+            ((start.0 + end.0) / 2 + 4, (start.1 + end.1) / 2 - 4),
+        );
+        let _ = writeln!(self.body, "  </g>");
+    }
+
+    fn isa(&mut self, number: u16, ui: &IsaUI) {
+        let supertype = match self.drawing.exhume_anchor(&ui.from) {
+            Some(a) => a,
+            None => return,
+        };
+        let start = self.location(supertype);
+        let (dx, dy) = self.normal(supertype);
+        let junction = (start.0 + dx * FAN, start.1 + dy * FAN);
+
+        let _ = writeln!(self.body, "  <g id=\"{}\">", ui.isa);
+        let _ = writeln!(
+            self.body,
+            "    <path class=\"relationship\" d=\"M {} {} L {} {}\" marker-end=\"url(#isa)\"/>",
+            junction.0, junction.1, start.0, start.1
+        );
+        for id in &ui.to {
+            if let Some(subtype) = self.drawing.exhume_anchor(id) {
+                let end = self.location(subtype);
+                let _ = writeln!(
+                    self.body,
+                    "    <path class=\"relationship\" d=\"M {} {} L {} {}\"/>",
+                    junction.0, junction.1, end.0, end.1
+                );
+            }
+        }
+        self.number(number, (junction.0 + 6, junction.1 - 4));
+        let _ = writeln!(self.body, "  </g>");
+    }
+
+    fn associative(
+        &mut self,
+        number: u16,
+        ui: &AssociativeUI,
+        one: (&str, &str),
+        other: (&str, &str),
+    ) {
+        let anchors = (
+            self.drawing.exhume_anchor(&ui.one),
+            self.drawing.exhume_anchor(&ui.middle),
+            self.drawing.exhume_anchor(&ui.other),
+            self.drawing.exhume_point(&ui.from),
+        );
+        let (one_anchor, middle, other_anchor, from) = match anchors {
+            (Some(o), Some(m), Some(t), Some(f)) => (o, m, t, (f.x, f.y)),
+            _ => return,
+        };
+        let start = self.location(one_anchor);
+        let middle = self.location(middle);
+        let end = self.location(other_anchor);
+        self.grow(from);
+
+        let _ = writeln!(self.body, "  <g id=\"{}\">", ui.associative_id);
+        for (end, m) in [(start, one.1), (end, other.1)] {
+            let _ = writeln!(
+                self.body,
+                "    <path class=\"relationship\" d=\"M {} {} L {} {}\" marker-end=\"url(#{})\"/>",
+                middle.0,
+                middle.1,
+                end.0,
+                end.1,
+                marker(m)
+            );
+        }
+        let _ = writeln!(
+            self.body,
+            "    <path class=\"associative\" d=\"M {} {} L {} {}\"/>",
+            middle.0, middle.1, from.0, from.1
+        );
+        self.phrase(one_anchor, one);
+        self.phrase(other_anchor, other);
+        self.number(number, (middle.0 + 6, middle.1 - 4));
+        let _ = writeln!(self.body, "  </g>");
+    }
+
+    /// Where the line meets the box
+    fn location(&mut self, anchor: &Anchor) -> (i64, i64) {
+        let location = match self.drawing.exhume_point(&anchor.location) {
+            Some(p) => (p.x, p.y),
+            None => (0, 0),
+        };
+        self.grow(location);
+
+        location
+    }
+
+    /// Which way is out of the box
+    fn normal(&self, anchor: &Anchor) -> (i64, i64) {
+        match anchor.edge {
+            e if e == Top => (0, -1),
+            e if e == Bottom => (0, 1),
+            e if e == Left => (-1, 0),
+            e if e == Right => (1, 0),
+            _ => (0, 1),
+        }
+    }
+
+    /// The phrase and multiplicity go wherever the modeler dragged them
+    fn phrase(&mut self, anchor: &Anchor, (phrase, multiplicity): (&str, &str)) {
+        let location = self.location(anchor);
+        let offset = match self.drawing.exhume_point(&anchor.offset) {
+            Some(p) => (p.x, p.y),
+            None => (0, 0),
+        };
+        let (x, y) = (location.0 + offset.0, location.1 + offset.1);
+        self.grow((x, y));
+
+        let _ = writeln!(
+            self.body,
+            "    <text x=\"{}\" y=\"{}\">{}</text>",
+            x,
+            y,
+            escape(phrase)
+        );
+        let _ = writeln!(
+            self.body,
+            "    <text x=\"{}\" y=\"{}\">{}</text>",
+            x,
+            y + LINE,
+            multiplicity
+        );
+    }
+
+    fn number(&mut self, number: u16, (x, y): (i64, i64)) {
+        self.grow((x, y));
+        let _ = writeln!(
+            self.body,
+            "    <text class=\"number\" x=\"{}\" y=\"{}\">R{}</text>",
+            x, y, number
+        );
+    }
+
+    fn finish(self, title: &str) -> String {
+        // An empty model is a small, empty picture.
+        let (min, max) = if self.min.0 > self.max.0 {
+            ((0, 0), (0, 0))
+        } else {
+            (self.min, self.max)
+        };
+        let (x, y) = (min.0 - MARGIN, min.1 - MARGIN);
+        let (width, height) = (max.0 - min.0 + 2 * MARGIN, max.1 - min.1 + 2 * MARGIN);
+
+        let mut out = String::new();
+        let _ = writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\" font-family=\"Helvetica, Arial, sans-serif\" font-size=\"12\">",
+            x, y, width, height, width, height
+        );
+        let _ = writeln!(out, "  <title>{}</title>", escape(title));
+        out.push_str(PREAMBLE);
+        let _ = writeln!(
+            out,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>",
+            x, y, width, height
+        );
+        out.push_str(&self.body);
+        let _ = writeln!(out, "</svg>");
+
+        out
+    }
+}
+
+/// A single arrowhead for one, two for many
+fn marker(multiplicity: &str) -> &'static str {
+    if multiplicity.ends_with('*') {
+        "many"
+    } else {
+        "one"
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -9,14 +9,34 @@
 //! It just occurred to me that I should be able to generate the raw structure that I'm
 //! extruding into! Now, that's exciting!
 //!
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
 pub mod jsformat;
+pub mod merge;
+pub mod text;
 
 pub use jsformat::{JSFormat, ReadModel, WriteModel};
+pub use merge::{merge_models, Conflict, Merge};
+pub use text::{parse_model, to_text, TextModel};
 
 /// Extract the ooa model information from the overarching Cuckoo Model.
 ///
-use crate::codegen::SarzakObjectStore;
-pub fn extract_ooa2(input: &JSFormat) -> SarzakObjectStore {
+/// Every id that Cuckoo lists for objects and relationships needs to have an
+/// entity behind it. If not, the file is busted, and we say so.
+use crate::codegen::{CodeGenError, Error, Result, SarzakObjectStore};
+use crate::ooa_0::{
+    relationship::{Associative, Binary, Dependent, Independent, Isa},
+    Attribute, Object, Relationship,
+};
+use crate::ooa_2::{AssociativeReferent, Relationship as Ooa2Relationship};
+use jsformat::EntityFormat;
+
+pub fn extract_ooa2(input: &JSFormat) -> Result<SarzakObjectStore> {
+    check_ids("object", &input.objects)?;
+    check_ids("relationship", &input.relationships)?;
+
     let schema = crate::ooa_0::Schema {
         version: "ooa_1".to_owned(),
         objects: input.objects.entities.clone(),
@@ -27,5 +47,139 @@ pub fn extract_ooa2(input: &JSFormat) -> SarzakObjectStore {
     let ooa_1: crate::ooa_1::Schema = ooa_0.into();
     let store: crate::ooa_2::ObjectStore = ooa_1.into();
 
-    store
+    Ok(store)
+}
+
+fn check_ids<T>(kind: &str, format: &jsformat::EntityFormat<T>) -> Result<()> {
+    match format
+        .ids
+        .iter()
+        .find(|id| !format.entities.contains_key(id))
+    {
+        Some(id) => Err(Error(CodeGenError::MissingEntity {
+            kind: kind.to_owned(),
+            id: *id,
+        })),
+        None => Ok(()),
+    }
+}
+
+/// Flatten an ooa_2 store back into the entities that Cuckoo keeps
+///
+/// This is [extract_ooa2] run backwards. The ids all survive the trip through
+/// ooa_1 and ooa_2, so Cuckoo won't notice a thing. Objects come out sorted by name,
+/// and relationships by number, so that writing the same model twice gives the
+/// same file.
+pub(crate) fn flatten_ooa2(
+    store: &SarzakObjectStore,
+) -> (EntityFormat<Object>, EntityFormat<Relationship>) {
+    let mut objects: Vec<_> = store.objects().collect();
+    objects.sort_by(|a, b| a.name.inner().cmp(b.name.inner()));
+
+    let objects = EntityFormat {
+        ids: objects.iter().map(|o| o.id).collect(),
+        entities: objects
+            .iter()
+            .map(|o| {
+                let attributes = o
+                    .attributes
+                    .values()
+                    .filter_map(|id| store.exhume_attribute(id))
+                    .map(|a| (a.id, a.clone()))
+                    .collect::<HashMap<Uuid, Attribute>>();
+
+                (
+                    o.id,
+                    Object {
+                        id: o.id,
+                        key_letter: o.key_letter.clone(),
+                        name: o.name.clone(),
+                        description: o.description.clone(),
+                        attributes,
+                    },
+                )
+            })
+            .collect(),
+    };
+
+    let mut relationships = EntityFormat {
+        ids: Vec::new(),
+        entities: HashMap::new(),
+    };
+    for rel in store.sorted_relationships() {
+        let flat = match rel {
+            Ooa2Relationship::Binary(id) => {
+                let binary = store.exhume_binary(id);
+                let from = binary.and_then(|b| store.exhume_referrer(&b.from));
+                let to = binary.and_then(|b| store.exhume_referent(&b.to));
+                match (binary, from, to) {
+                    (Some(binary), Some(from), Some(to)) => Relationship::Binary(Binary {
+                        id: binary.id,
+                        number: binary.number,
+                        from: Independent {
+                            obj_id: from.obj_id,
+                            description: from.description.clone(),
+                            cardinality: from.cardinality.clone(),
+                            conditionality: from.conditionality,
+                            formalizing_attribute_name: from.referential_attribute.clone(),
+                        },
+                        to: Dependent {
+                            obj_id: to.obj_id,
+                            description: to.description.clone(),
+                            cardinality: to.cardinality.clone(),
+                            conditionality: to.conditionality,
+                        },
+                    }),
+                    _ => continue,
+                }
+            }
+            Ooa2Relationship::Isa(id) => {
+                let isa = store.exhume_isa(id);
+                let supertype = isa.and_then(|i| store.exhume_supertype(&i.supertype));
+                match (isa, supertype) {
+                    (Some(isa), Some(supertype)) => Relationship::Isa(Isa {
+                        id: isa.id,
+                        number: isa.number,
+                        obj_id: supertype.obj_id,
+                        subtypes: isa
+                            .subtypes
+                            .iter()
+                            .filter_map(|s| store.exhume_subtype(s))
+                            .map(|s| s.obj_id)
+                            .collect(),
+                    }),
+                    _ => continue,
+                }
+            }
+            Ooa2Relationship::Associative(id) => {
+                let assoc = store.exhume_associative(id);
+                let from = assoc.and_then(|a| store.exhume_associative_referrer(&a.from));
+                let one = assoc.and_then(|a| store.exhume_associative_referent(&a.one));
+                let other = assoc.and_then(|a| store.exhume_associative_referent(&a.other));
+                match (assoc, from, one, other) {
+                    (Some(assoc), Some(from), Some(one), Some(other)) => {
+                        let dependent = |r: &AssociativeReferent| Dependent {
+                            obj_id: r.obj_id,
+                            description: r.description.clone(),
+                            cardinality: r.cardinality.clone(),
+                            conditionality: r.conditionality,
+                        };
+                        Relationship::Associative(Associative {
+                            id: assoc.id,
+                            number: assoc.number,
+                            from: from.clone(),
+                            one: dependent(one),
+                            other: dependent(other),
+                        })
+                    }
+                    _ => continue,
+                }
+            }
+        };
+
+        relationships.ids.push(flat.get_id());
+        relationships.entities.insert(flat.get_id(), flat);
+    }
+
+    (objects, relationships)
 }
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::codegen::Config;
+use crate::codegen::{CodeGenError, Config, Error, Result};
 use crate::ooa_0::{Object, Relationship};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -94,15 +94,24 @@ pub struct EntityFormat<T> {
 /// Read a Cuckoo Model file
 ///
 /// That's the file that contains both the model, and the ui elements.
+///
+/// Broken JSON is reported with the line and column where serde gave up, which
+/// beats staring at a few thousand lines of UUIDs.
 pub trait ReadModel {
-    fn from_json(&mut self) -> std::io::Result<JSFormat>;
+    fn from_json(&mut self) -> Result<JSFormat>;
 }
 
 impl<R: std::io::Read> ReadModel for R {
-    fn from_json(&mut self) -> std::io::Result<JSFormat> {
+    fn from_json(&mut self) -> Result<JSFormat> {
         let mut deserializer = serde_json::Deserializer::from_reader(self);
 
-        Ok(JSFormat::deserialize(&mut deserializer)?)
+        JSFormat::deserialize(&mut deserializer).map_err(|source| {
+            Error(CodeGenError::ParseCuckooModel {
+                line: source.line(),
+                column: source.column(),
+                source,
+            })
+        })
     }
 }
 
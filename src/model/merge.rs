@@ -0,0 +1,413 @@
+//! Three-way Merge of Cuckoo Models
+//!
+//! Two people editing the same domain in Cuckoo means two versions of one JSON
+//! file, and git has no idea what to do with UUID-keyed maps. This merges a base,
+//! ours and theirs [`JSFormat`] the way you would by hand: objects, attributes,
+//! relationships and paper layout entries are lined up by UUID, and everything
+//! that only one side touched just comes along for the ride.
+//!
+//! When both sides changed the same thing differently, that's a [`Conflict`]. We
+//! keep our version in the merged model, so that it always loads, and report what
+//! they wanted instead.
+use std::{collections::HashSet, fmt};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::codegen::{CodeGenError, Error, Result};
+use crate::model::JSFormat;
+use crate::sarzak::{validate_model, Diagnostic, SarzakModel};
+
+/// Where a conflict happened
+///
+/// Things are named as they were in the base model, when possible.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Location {
+    /// The paper, i.e., the domain name, description, size and whatnot.
+    Paper,
+    Object {
+        name: String,
+    },
+    Attribute {
+        object: String,
+        name: String,
+    },
+    Relationship {
+        number: String,
+    },
+    /// Where an object or relationship is drawn on the paper.
+    Layout {
+        element: String,
+    },
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Paper => write!(f, "paper"),
+            Self::Object { name } => write!(f, "object `{}`", name),
+            Self::Attribute { object, name } => write!(f, "attribute `{}.{}`", object, name),
+            Self::Relationship { number } => write!(f, "{}", number),
+            Self::Layout { element } => write!(f, "layout of {}", element),
+        }
+    }
+}
+
+/// Both sides changed the same thing, differently
+///
+/// `field` is the path inside of the thing at `location`, e.g. `type` for an
+/// attribute. If it's `None` then the whole thing conflicted, which is usually a
+/// change on one side, and a delete on the other. A value of `None` means that
+/// side doesn't have it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Conflict {
+    pub location: Location,
+    pub field: Option<String>,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<Value>| match v {
+            Some(v) => v.to_string(),
+            None => "(deleted)".to_owned(),
+        };
+
+        write!(f, "{}", self.location)?;
+        if let Some(field) = &self.field {
+            write!(f, " {}", field)?;
+        }
+        write!(
+            f,
+            ": base {}, ours {}, theirs {}",
+            show(&self.base),
+            show(&self.ours),
+            show(&self.theirs)
+        )
+    }
+}
+
+/// The result of a merge
+///
+/// `diagnostics` are model problems that neither side had on its own. Think
+/// "they deleted the object that we just related to".
+#[derive(Debug)]
+pub struct Merge {
+    pub merged: JSFormat,
+    pub conflicts: Vec<Conflict>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// The sections of a Cuckoo file
+const SECTIONS: [&str; 3] = ["paper", "objects", "relationships"];
+
+/// Merge theirs into ours
+pub fn merge_models(base: &JSFormat, ours: &JSFormat, theirs: &JSFormat) -> Result<Merge> {
+    let base = to_value(base)?;
+    let ours = to_value(ours)?;
+    let theirs = to_value(theirs)?;
+
+    // The id lists just mirror the entity maps. Merging them as values would make
+    // every addition a conflict, so set them aside and rebuild them afterwards.
+    let strip = |v: &Value| {
+        let mut v = v.clone();
+        for section in SECTIONS {
+            if let Some(s) = v.get_mut(section).and_then(|s| s.as_object_mut()) {
+                s.remove("ids");
+            }
+        }
+        v
+    };
+
+    let mut raw = Vec::new();
+    let mut merged = merge3(
+        Some(&strip(&base)),
+        Some(&strip(&ours)),
+        Some(&strip(&theirs)),
+        &mut Vec::new(),
+        &mut raw,
+    )
+    .unwrap_or(Value::Null);
+
+    drop_orphaned_layout(&mut merged);
+
+    for section in SECTIONS {
+        let ids = merged_ids(section, &merged, &ours, &theirs);
+        if let Some(s) = merged.get_mut(section).and_then(|s| s.as_object_mut()) {
+            s.insert("ids".to_owned(), Value::Array(ids));
+        }
+    }
+
+    let conflicts = raw
+        .into_iter()
+        .map(|r| r.into_conflict([&base, &ours, &theirs]))
+        .collect();
+
+    // Make sure that what we made actually loads, and see if it's any good.
+    let model: SarzakModel = from_value(&merged)?.try_into()?;
+    let ours: SarzakModel = from_value(&ours)?.try_into()?;
+    let theirs: SarzakModel = from_value(&theirs)?.try_into()?;
+    let known: Vec<Diagnostic> = validate_model(&ours)
+        .into_iter()
+        .chain(validate_model(&theirs))
+        .collect();
+    let diagnostics = validate_model(&model)
+        .into_iter()
+        .filter(|d| !known.contains(d))
+        .collect();
+
+    Ok(Merge {
+        merged: from_value(&merged)?,
+        conflicts,
+        diagnostics,
+    })
+}
+
+fn to_value(model: &JSFormat) -> Result<Value> {
+    serde_json::to_value(model).map_err(|source| Error(CodeGenError::SerdeJsonBombed { source }))
+}
+
+fn from_value(value: &Value) -> Result<JSFormat> {
+    serde_json::from_value(value.clone())
+        .map_err(|source| Error(CodeGenError::SerdeJsonBombed { source }))
+}
+
+/// A conflict, before we've figured out what to call it.
+struct RawConflict {
+    path: Vec<String>,
+    base: Option<Value>,
+    ours: Option<Value>,
+    theirs: Option<Value>,
+}
+
+/// The heart of the matter
+///
+/// If the sides agree, or only one side changed, there's nothing to think about.
+/// Otherwise, if both are maps, we go key by key. Anything else is a conflict,
+/// and we keep ours.
+fn merge3(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    path: &mut Vec<String>,
+    conflicts: &mut Vec<RawConflict>,
+) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+
+    match (ours, theirs) {
+        (Some(Value::Object(o)), Some(Value::Object(t))) => {
+            let b = base.and_then(|b| b.as_object());
+            let mut result = Map::new();
+
+            let mut keys: Vec<&String> = o.keys().collect();
+            keys.extend(t.keys().filter(|k| !o.contains_key(*k)));
+            if let Some(b) = b {
+                keys.extend(
+                    b.keys()
+                        .filter(|k| !o.contains_key(*k) && !t.contains_key(*k)),
+                );
+            }
+
+            for key in keys {
+                path.push(key.clone());
+                if let Some(v) = merge3(
+                    b.and_then(|b| b.get(key)),
+                    o.get(key),
+                    t.get(key),
+                    path,
+                    conflicts,
+                ) {
+                    result.insert(key.clone(), v);
+                }
+                path.pop();
+            }
+
+            Some(Value::Object(result))
+        }
+        _ => {
+            conflicts.push(RawConflict {
+                path: path.clone(),
+                base: base.cloned(),
+                ours: ours.cloned(),
+                theirs: theirs.cloned(),
+            });
+            ours.cloned()
+        }
+    }
+}
+
+/// Layout for things that no longer exist
+///
+/// If they deleted an object that we moved, we end up with a box for something
+/// that isn't there. The loader rightly refuses those.
+fn drop_orphaned_layout(merged: &mut Value) {
+    let existing = |section: &str| -> HashSet<String> {
+        merged
+            .get(section)
+            .and_then(|s| s.get("entities"))
+            .and_then(|e| e.as_object())
+            .map(|e| e.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+    let objects = existing("objects");
+    let relationships = existing("relationships");
+
+    if let Some(papers) = merged
+        .get_mut("paper")
+        .and_then(|p| p.get_mut("entities"))
+        .and_then(|e| e.as_object_mut())
+    {
+        for paper in papers.values_mut() {
+            for (section, keep) in [("objects", &objects), ("relationships", &relationships)] {
+                if let Some(layout) = paper.get_mut(section).and_then(|l| l.as_object_mut()) {
+                    layout.retain(|id, _| keep.contains(id));
+                }
+            }
+        }
+    }
+}
+
+/// Rebuild the id list for a section
+///
+/// Our order first, then whatever they added, then anything else that survived.
+fn merged_ids(section: &str, merged: &Value, ours: &Value, theirs: &Value) -> Vec<Value> {
+    let entities = match merged
+        .get(section)
+        .and_then(|s| s.get("entities"))
+        .and_then(|e| e.as_object())
+    {
+        Some(e) => e,
+        None => return Vec::new(),
+    };
+
+    let listed = |v: &Value| -> Vec<String> {
+        v.get(section)
+            .and_then(|s| s.get("ids"))
+            .and_then(|i| i.as_array())
+            .map(|i| {
+                i.iter()
+                    .filter_map(|id| id.as_str().map(|s| s.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut ids: Vec<String> = Vec::new();
+    for id in listed(ours)
+        .into_iter()
+        .chain(listed(theirs))
+        .chain(entities.keys().cloned())
+    {
+        if entities.contains_key(&id) && !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    ids.into_iter().map(Value::String).collect()
+}
+
+impl RawConflict {
+    /// Turn a path into something a person can read
+    ///
+    /// Names are looked up in each of `models`, in order, until one turns up.
+    fn into_conflict(self, models: [&Value; 3]) -> Conflict {
+        let p: Vec<&str> = self.path.iter().map(|s| s.as_str()).collect();
+
+        let object_name = |id: &str| {
+            lookup(&models, &["objects", "entities", id, "name"]).unwrap_or_else(|| id.to_owned())
+        };
+        let attribute_name = |obj: &str, id: &str| {
+            lookup(
+                &models,
+                &["objects", "entities", obj, "attributes", id, "name"],
+            )
+            .unwrap_or_else(|| id.to_owned())
+        };
+        let relationship_name = |id: &str| {
+            models
+                .iter()
+                .filter_map(|m| m.pointer(&format!("/relationships/entities/{}", id)))
+                .filter_map(|r| r.as_object())
+                .flat_map(|r| r.values())
+                .find_map(|v| v.get("number"))
+                .map(|n| format!("R{}", n))
+                .unwrap_or_else(|| id.to_owned())
+        };
+        let field = |rest: &[&str]| {
+            if rest.is_empty() {
+                None
+            } else {
+                Some(rest.join("."))
+            }
+        };
+
+        let (location, field) = match p[..] {
+            ["objects", "entities", obj, "attributes", attr, ref rest @ ..] => (
+                Location::Attribute {
+                    object: object_name(obj),
+                    name: attribute_name(obj, attr),
+                },
+                field(rest),
+            ),
+            ["objects", "entities", obj, ref rest @ ..] => (
+                Location::Object {
+                    name: object_name(obj),
+                },
+                field(rest),
+            ),
+            // Skip over the relationship variant, e.g., "Binary".
+            ["relationships", "entities", rel, _, ref rest @ ..] => (
+                Location::Relationship {
+                    number: relationship_name(rel),
+                },
+                field(rest),
+            ),
+            ["relationships", "entities", rel] => (
+                Location::Relationship {
+                    number: relationship_name(rel),
+                },
+                None,
+            ),
+            ["paper", "entities", _, "objects", obj, ref rest @ ..] => (
+                Location::Layout {
+                    element: format!("object `{}`", object_name(obj)),
+                },
+                field(rest),
+            ),
+            ["paper", "entities", _, "relationships", rel, _, ref rest @ ..]
+            | ["paper", "entities", _, "relationships", rel, ref rest @ ..] => (
+                Location::Layout {
+                    element: relationship_name(rel),
+                },
+                field(rest),
+            ),
+            ["paper", "entities", _, ref rest @ ..] => (Location::Paper, field(rest)),
+            ref rest => (Location::Paper, field(rest)),
+        };
+
+        Conflict {
+            location,
+            field,
+            base: self.base,
+            ours: self.ours,
+            theirs: self.theirs,
+        }
+    }
+}
+
+fn lookup(models: &[&Value; 3], path: &[&str]) -> Option<String> {
+    let pointer = format!("/{}", path.join("/"));
+    models
+        .iter()
+        .find_map(|m| m.pointer(&pointer))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_owned())
+}
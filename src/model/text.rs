@@ -0,0 +1,531 @@
+//! Text Models
+//!
+//! A plain text way to write a domain, for when firing up Cuckoo is too much, or
+//! when you'd like to review a model change like it was code. Which it is. The
+//! alternative is building instances by hand, like `bin/ooa_0/gen_schema.rs`
+//! does, and nobody wants that.
+//!
+//! It looks like this:
+//!
+//! ```text
+//! /// Cats and dogs, living together.
+//! domain cat_dog
+//!
+//! object Animal (ANI) {
+//!     id: Uuid,
+//! }
+//!
+//! object Dog (DOG) {
+//!     id: Uuid,
+//!     name: String,
+//! }
+//!
+//! object Cat (CAT) {
+//!     id: Uuid,
+//!     lives: Unsigned8,
+//!     mood: Mood?,
+//! }
+//!
+//! /// Enumerations are declared like this.
+//! enum Mood { Aloof, Hungry, "Up To No Good" }
+//!
+//! object "Dog Fight" (DF) {
+//!     id: Uuid,
+//! }
+//!
+//! /// Doc comments become descriptions. Dogs howl at it.
+//! ///
+//! /// ❗️ {"singleton_object": true}
+//! object Moon (MN) {
+//! }
+//!
+//! isa R1 Animal { Cat, Dog }
+//!
+//! binary R2 {
+//!     referrer Dog 1 "is chased by" via chases
+//!     referent Cat 1c "chases"
+//! }
+//!
+//! associative R3 {
+//!     link "Dog Fight" M via dog, cat
+//!     one Dog Mc "picks on"
+//!     other Cat M "fights back against"
+//! }
+//! ```
+//!
+//! Names that aren't identifiers go in quotes. Types are `Uuid`, `Integer`,
+//! `Float`, `String`, and `Boolean`. There are sized numbers too: `Integer8`
+//! through `Integer32`, `Unsigned8` through `Unsigned64`, and `Float32`. Then
+//! there's `Timestamp`, `Duration`, `Bytes`, and any enumeration in the model. A
+//! `?` on the end makes an attribute optional.
+//!
+//! Referential attributes aren't declared on the object. The `via` on the
+//! relationship names them, and the code generator takes it from there. An
+//! optional reference is a conditional relationship.
+//! Multiplicity is Shlaer-Mellor style: `1`, `1c`, `M` and `Mc`, where the `c`
+//! means conditional. Each end of a relationship has the object, the multiplicity,
+//! and the phrase that you read going _to_ that object.
+//!
+//! Attributes that identify an instance are marked the OOA way, with `*1`, `*2`,
+//! and so on, before the name. `*` on its own is `*1`. Mark more than one
+//! attribute with the same number for a compound identifier:
+//!
+//! ```text
+//! object Seat (ST) {
+//!     id: Uuid,
+//!     *1 row: Unsigned8,
+//!     *1 number: Unsigned8,
+//!     *2 ticket: String,
+//! }
+//! ```
+//!
+//! Compiler options go in the description, after a `❗️`, just like they do in
+//! Cuckoo. See [`IdentityPolicy`](crate::codegen::IdentityPolicy) for what
+//! identifiers do to the generated code.
+//!
+//! Parse errors, and things that parse but make no sense, come back as a
+//! [`CodeGenError::ParseTextModel`] with the line, the column, and a snippet of
+//! the offending bit. [`to_text`] goes the other way.
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    str::FromStr,
+};
+
+use uuid::Uuid;
+
+use crate::codegen::{CodeGenError, Error, Result, SarzakModel};
+use crate::model::jsformat::{EntityFormat, JSFormat, Paper, Point};
+use crate::ooa_0::{
+    relationship::{Associative, AssociativeReferrer, Dependent},
+    Attribute, AttributeName, Enumeration, Object, Relationship, Schema, Type, UUID_NS,
+};
+
+mod lexer;
+mod parser;
+mod printer;
+
+pub use printer::to_text;
+
+use parser::{Ast, End, Item, Name, Parser, TypeDecl};
+
+/// A parsed text model
+///
+/// The [`Schema`] is the interesting part. The rest is what it takes to make a
+/// [`SarzakModel`] out of it.
+#[derive(Clone, Debug)]
+pub struct TextModel {
+    pub domain: String,
+    pub description: String,
+    /// Objects and relationships, in the order they were written
+    pub order: Vec<Uuid>,
+    pub schema: Schema,
+}
+
+impl FromStr for TextModel {
+    type Err = Error;
+
+    fn from_str(src: &str) -> Result<Self> {
+        parse(src)
+    }
+}
+
+/// Parse a text model into an ooa_0 [`Schema`]
+pub fn parse(src: &str) -> Result<TextModel> {
+    let tokens = lexer::lex(src)?;
+    let ast = Parser::new(src, tokens).parse()?;
+
+    Builder::new(src).build(ast)
+}
+
+/// Parse a text model all of the way into a [`SarzakModel`]
+///
+/// There's no drawing, since there's nothing to say where anything goes.
+pub fn parse_model(src: &str) -> Result<SarzakModel> {
+    parse(src)?.try_into()
+}
+
+/// Dress a text model up as though Cuckoo wrote it
+///
+/// The paper is blank, and sized the way Cuckoo sizes a new one. The ids are the
+/// ones Cuckoo uses for every paper, no matter the domain.
+impl From<TextModel> for JSFormat {
+    fn from(model: TextModel) -> Self {
+        let paper = Paper {
+            id: Uuid::new_v5(&UUID_NS, "Paper::sarzak_ooa_0".as_bytes()),
+            description: model.description,
+            domain_ns: UUID_NS.to_string(),
+            domain_name: model.domain,
+            width: 4000,
+            height: 3200,
+            offset: Point { x: 0, y: 0 },
+            objects: HashMap::new(),
+            relationships: HashMap::new(),
+            config: None,
+        };
+
+        let (object_ids, relationship_ids) = model
+            .order
+            .iter()
+            .partition(|id| model.schema.objects.contains_key(id));
+
+        JSFormat {
+            paper: EntityFormat {
+                ids: vec![paper.id],
+                entities: HashMap::from([(paper.id, paper)]),
+            },
+            objects: EntityFormat {
+                ids: object_ids,
+                entities: model.schema.objects,
+            },
+            relationships: EntityFormat {
+                ids: relationship_ids,
+                entities: model.schema.relationships,
+            },
+        }
+    }
+}
+
+impl TryFrom<TextModel> for SarzakModel {
+    type Error = Error;
+
+    fn try_from(model: TextModel) -> Result<Self> {
+        JSFormat::from(model).try_into()
+    }
+}
+
+/// Build an error, with a snippet of the source pointing at the span
+pub(crate) fn error<S: Into<String>>(src: &str, span: Range<usize>, message: S) -> Error {
+    let start = span.start.min(src.len());
+    let line_start = src[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = src[start..]
+        .find('\n')
+        .map(|i| start + i)
+        .unwrap_or(src.len());
+
+    let line = src[..start].matches('\n').count() + 1;
+    let column = src[line_start..start].chars().count() + 1;
+    let text = &src[line_start..line_end];
+    let width = src[start..span.end.clamp(start, line_end)]
+        .chars()
+        .count()
+        .max(1);
+
+    let gutter = " ".repeat(line.to_string().len());
+    let snippet = format!(
+        "{} |\n{} | {}\n{} | {}{}",
+        gutter,
+        line,
+        text,
+        gutter,
+        " ".repeat(column - 1),
+        "^".repeat(width)
+    );
+
+    Error(CodeGenError::ParseTextModel {
+        line,
+        column,
+        message: message.into(),
+        snippet,
+    })
+}
+
+/// Turns the syntax into a schema, checking that it all hangs together
+struct Builder<'a> {
+    src: &'a str,
+    objects: HashMap<String, Uuid>,
+    enumerations: HashMap<String, Enumeration>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            objects: HashMap::new(),
+            enumerations: HashMap::new(),
+        }
+    }
+
+    fn build(mut self, ast: Ast) -> Result<TextModel> {
+        let mut schema = Schema {
+            version: "ooa_0".to_owned(),
+            objects: HashMap::new(),
+            relationships: HashMap::new(),
+        };
+        let mut order = Vec::new();
+
+        // Objects first, so that relationships and references can point forward.
+        for item in &ast.items {
+            if let Item::Object(decl) = item {
+                if self.objects.contains_key(&decl.name.text) {
+                    return Err(self.error(
+                        &decl.name.span,
+                        format!("object `{}` is already defined", decl.name.text),
+                    ));
+                }
+                let object = Object::new(&decl.name.text, &decl.key_letter.text);
+                self.objects.insert(decl.name.text.clone(), object.id);
+                order.push(object.id);
+                schema.objects.insert(object.id, object);
+            }
+        }
+
+        // Enumerations don't go anywhere on their own. They ride along in the type
+        // of the attributes that use them.
+        for item in &ast.items {
+            if let Item::Enumeration(decl) = item {
+                if self.enumerations.contains_key(&decl.name.text)
+                    || self.objects.contains_key(&decl.name.text)
+                {
+                    return Err(self.error(
+                        &decl.name.span,
+                        format!("`{}` is already defined", decl.name.text),
+                    ));
+                }
+                if decl.variants.is_empty() {
+                    return Err(self.error(
+                        &decl.name.span,
+                        format!(
+                            "enumeration `{}` needs at least one variant",
+                            decl.name.text
+                        ),
+                    ));
+                }
+                let mut variants = HashSet::new();
+                for variant in &decl.variants {
+                    if !variants.insert(&variant.text) {
+                        return Err(self.error(
+                            &variant.span,
+                            format!(
+                                "variant `{}` is already defined on `{}`",
+                                variant.text, decl.name.text
+                            ),
+                        ));
+                    }
+                }
+
+                let variants: Vec<&String> = decl.variants.iter().map(|v| &v.text).collect();
+                let mut enumeration = Enumeration::new(&decl.name.text, &variants);
+                enumeration.description = decl.doc.join("\n");
+                self.enumerations
+                    .insert(decl.name.text.clone(), enumeration);
+            }
+        }
+
+        let mut declared = Vec::new();
+        let mut used = HashSet::new();
+        let mut numbers = HashSet::new();
+        for item in ast.items {
+            let relationship = match item {
+                Item::Enumeration(decl) => {
+                    declared.push(decl.name);
+                    continue;
+                }
+                Item::Object(decl) => {
+                    let id = self.objects[&decl.name.text];
+                    let mut object = schema.objects.remove(&id).unwrap();
+                    object.description = decl.doc.join("\n");
+
+                    let mut names = HashSet::new();
+                    for attr in decl.attributes {
+                        if !names.insert(attr.name.text.clone()) {
+                            return Err(self.error(
+                                &attr.name.span,
+                                format!(
+                                    "attribute `{}` is already defined on `{}`",
+                                    attr.name.text, decl.name.text
+                                ),
+                            ));
+                        }
+                        let ty = self.attribute_type(&attr.ty)?;
+                        if let Some(e) = ty.enumeration() {
+                            used.insert(e.name.inner().clone());
+                        }
+                        let mut attribute = Attribute::new(&attr.name.text, ty);
+                        for (number, span) in attr.identifiers {
+                            if attr.name.text == "id" {
+                                return Err(self.error(
+                                    &span,
+                                    "`id` is made out of the identifiers, it can't be one",
+                                ));
+                            }
+                            if let TypeDecl::Optional(..) = attr.ty {
+                                return Err(self.error(
+                                    &span,
+                                    "an optional attribute can't be part of an identifier",
+                                ));
+                            }
+                            if number == 0 {
+                                return Err(self.error(&span, "identifiers start at `*1`"));
+                            }
+                            if attribute.identifiers.contains(&number) {
+                                return Err(self.error(
+                                    &span,
+                                    format!(
+                                        "`{}` is already part of identifier `*{}`",
+                                        attr.name.text, number
+                                    ),
+                                ));
+                            }
+                            attribute.identifiers.push(number);
+                        }
+                        attribute.identifiers.sort();
+                        object = object.add_attribute(attribute);
+                    }
+
+                    schema.objects.insert(id, object);
+                    continue;
+                }
+                Item::Binary(decl) => {
+                    self.number(&mut numbers, &decl.number)?;
+                    Relationship::new_binary(
+                        decl.number.0,
+                        self.object(&decl.referrer.object)?,
+                        decl.referrer.cardinality,
+                        decl.referrer.conditionality,
+                        &decl.referrer.phrase,
+                        &decl.via.text,
+                        self.object(&decl.referent.object)?,
+                        decl.referent.cardinality,
+                        decl.referent.conditionality,
+                        &decl.referent.phrase,
+                    )
+                }
+                Item::Isa(decl) => {
+                    self.number(&mut numbers, &decl.number)?;
+                    if decl.subtypes.is_empty() {
+                        return Err(self.error(
+                            &decl.supertype.span,
+                            format!("R{} needs at least one subtype", decl.number.0),
+                        ));
+                    }
+                    let subtypes = decl
+                        .subtypes
+                        .iter()
+                        .map(|s| self.object(s))
+                        .collect::<Result<Vec<_>>>()?;
+                    Relationship::new_isa(decl.number.0, self.object(&decl.supertype)?, subtypes)
+                }
+                Item::Associative(decl) => {
+                    self.number(&mut numbers, &decl.number)?;
+                    let link = self.object(&decl.link)?;
+                    let one = self.dependent(&decl.one)?;
+                    let other = self.dependent(&decl.other)?;
+
+                    let id = Uuid::new_v5(
+                        &UUID_NS,
+                        format!(
+                            "{}::{}::{}::{}",
+                            link, one.obj_id, other.obj_id, decl.number.0
+                        )
+                        .as_bytes(),
+                    );
+                    Relationship::Associative(Associative {
+                        id,
+                        number: decl.number.0,
+                        from: AssociativeReferrer {
+                            id: Uuid::new_v5(&UUID_NS, format!("{}::from", id).as_bytes()),
+                            obj_id: link,
+                            cardinality: decl.cardinality,
+                            one_referential_attribute: AttributeName::new(decl.one_via.text),
+                            other_referential_attribute: AttributeName::new(decl.other_via.text),
+                        },
+                        one,
+                        other,
+                    })
+                }
+            };
+
+            order.push(relationship.get_id());
+            schema
+                .relationships
+                .insert(relationship.get_id(), relationship);
+        }
+
+        if let Some(name) = declared.iter().find(|n| !used.contains(&n.text)) {
+            return Err(self.error(
+                &name.span,
+                format!(
+                    "enumeration `{}` isn't the type of any attribute, so there's nowhere \
+                     to keep it",
+                    name.text
+                ),
+            ));
+        }
+
+        Ok(TextModel {
+            domain: ast.domain.text,
+            description: ast.doc.join("\n"),
+            order,
+            schema,
+        })
+    }
+
+    fn attribute_type(&self, ty: &TypeDecl) -> Result<Type> {
+        match ty {
+            TypeDecl::Optional(ty) => Ok(Type::Optional(Box::new(self.attribute_type(ty)?))),
+            TypeDecl::Simple(name) => match name.text.as_str() {
+                "Uuid" => Ok(Type::Uuid),
+                "Integer" => Ok(Type::Integer),
+                "Float" => Ok(Type::Float),
+                "String" => Ok(Type::String),
+                "Boolean" => Ok(Type::Boolean),
+                "Integer8" => Ok(Type::Integer8),
+                "Integer16" => Ok(Type::Integer16),
+                "Integer32" => Ok(Type::Integer32),
+                "Unsigned8" => Ok(Type::Unsigned8),
+                "Unsigned16" => Ok(Type::Unsigned16),
+                "Unsigned32" => Ok(Type::Unsigned32),
+                "Unsigned64" => Ok(Type::Unsigned64),
+                "Float32" => Ok(Type::Float32),
+                "Timestamp" => Ok(Type::Timestamp),
+                "Duration" => Ok(Type::Duration),
+                "Bytes" => Ok(Type::Bytes),
+                t => match self.enumerations.get(t) {
+                    Some(e) => Ok(Type::Enumeration(e.clone())),
+                    None => Err(self.error(
+                        &name.span,
+                        format!(
+                            "unknown type `{}`, expected one of Uuid, Integer, Float, String, \
+                             Boolean, a sized number like Unsigned8 or Float32, Timestamp, \
+                             Duration, Bytes, or an enumeration",
+                            t
+                        ),
+                    )),
+                },
+            },
+        }
+    }
+
+    fn dependent(&self, end: &End) -> Result<Dependent> {
+        Ok(Dependent {
+            obj_id: self.object(&end.object)?,
+            description: end.phrase.clone(),
+            cardinality: end.cardinality.clone(),
+            conditionality: end.conditionality,
+        })
+    }
+
+    fn object(&self, name: &Name) -> Result<Uuid> {
+        self.objects
+            .get(&name.text)
+            .copied()
+            .ok_or_else(|| self.error(&name.span, format!("no such object `{}`", name.text)))
+    }
+
+    fn number(
+        &self,
+        numbers: &mut HashSet<u16>,
+        (number, span): &(u16, Range<usize>),
+    ) -> Result<()> {
+        if numbers.insert(*number) {
+            Ok(())
+        } else {
+            Err(self.error(span, format!("R{} is already defined", number)))
+        }
+    }
+
+    fn error<S: Into<String>>(&self, span: &Range<usize>, message: S) -> Error {
+        error(self.src, span.clone(), message)
+    }
+}
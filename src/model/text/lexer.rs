@@ -0,0 +1,125 @@
+//! Text Model Lexer
+//!
+//! Nothing fancy. Words, strings, a handful of punctuation, and doc comments. Doc
+//! comments are tokens because they end up as descriptions. Plain comments are
+//! dropped on the floor.
+use std::ops::Range;
+
+use crate::codegen::Result;
+use crate::model::text::error;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Token {
+    /// Identifiers, numbers, and things like `1c`
+    Word(String),
+    /// A quoted string, with the escapes dealt with
+    Str(String),
+    /// `/// like this`, without the slashes
+    Doc(String),
+    Punct(char),
+    Eof,
+}
+
+impl Token {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Word(w) => format!("`{}`", w),
+            Self::Str(s) => format!("\"{}\"", s),
+            Self::Doc(_) => "a doc comment".to_owned(),
+            Self::Punct(c) => format!("`{}`", c),
+            Self::Eof => "the end of the file".to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Spanned {
+    pub token: Token,
+    pub span: Range<usize>,
+}
+
+pub(crate) fn lex(src: &str) -> Result<Vec<Spanned>> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                chars.next();
+                let doc = matches!(chars.peek(), Some((_, '/')));
+                if doc {
+                    chars.next();
+                }
+
+                let mut text = String::new();
+                while let Some((_, c)) = chars.next_if(|(_, c)| *c != '\n') {
+                    text.push(c);
+                }
+
+                if !doc {
+                    continue;
+                }
+                // One space after the slashes is formatting, the rest is content.
+                Token::Doc(text.strip_prefix(' ').unwrap_or(&text).to_owned())
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((i, '\\')) => match chars.next() {
+                            Some((_, '"')) => text.push('"'),
+                            Some((_, '\\')) => text.push('\\'),
+                            Some((_, 'n')) => text.push('\n'),
+                            Some((_, 't')) => text.push('\t'),
+                            Some((j, c)) => {
+                                return Err(error(
+                                    src,
+                                    i..j + c.len_utf8(),
+                                    format!("unknown escape `\\{}`", c),
+                                ))
+                            }
+                            None => {
+                                return Err(error(src, start..start + 1, "unterminated string"))
+                            }
+                        },
+                        Some((_, '\n')) | None => {
+                            return Err(error(src, start..start + 1, "unterminated string"))
+                        }
+                        Some((_, c)) => text.push(c),
+                    }
+                }
+                Token::Str(text)
+            }
+            '{' | '}' | '(' | ')' | ',' | ':' | '&' | '?' | '*' => Token::Punct(c),
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+            c => {
+                return Err(error(
+                    src,
+                    start..start + c.len_utf8(),
+                    format!("unexpected character `{}`", c),
+                ))
+            }
+        };
+
+        let end = chars.peek().map(|(i, _)| *i).unwrap_or(src.len());
+        tokens.push(Spanned {
+            token,
+            span: start..end,
+        });
+    }
+
+    tokens.push(Spanned {
+        token: Token::Eof,
+        span: src.len()..src.len(),
+    });
+
+    Ok(tokens)
+}
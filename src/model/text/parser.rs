@@ -0,0 +1,429 @@
+//! Text Model Parser
+//!
+//! Recursive descent, one token of lookahead, and it gives up at the first thing it
+//! doesn't like. Everything keeps its span so that the next stage can complain
+//! about things that parse fine, but don't make sense.
+use std::ops::Range;
+
+use crate::codegen::Result;
+use crate::model::text::{
+    error,
+    lexer::{Spanned, Token},
+};
+use crate::ooa_0::{Cardinality, Conditionality};
+
+/// Something with a name, and where the name was
+#[derive(Clone, Debug)]
+pub(crate) struct Name {
+    pub text: String,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Ast {
+    pub doc: Vec<String>,
+    pub domain: Name,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug)]
+pub(crate) enum Item {
+    Enumeration(EnumDecl),
+    Object(ObjectDecl),
+    Binary(BinaryDecl),
+    Isa(IsaDecl),
+    Associative(AssociativeDecl),
+}
+
+#[derive(Debug)]
+pub(crate) struct EnumDecl {
+    pub doc: Vec<String>,
+    pub name: Name,
+    pub variants: Vec<Name>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ObjectDecl {
+    pub doc: Vec<String>,
+    pub name: Name,
+    pub key_letter: Name,
+    pub attributes: Vec<AttributeDecl>,
+}
+
+#[derive(Debug)]
+pub(crate) struct AttributeDecl {
+    /// `*1`, `*2`, and where they were
+    pub identifiers: Vec<(u16, Range<usize>)>,
+    pub name: Name,
+    pub ty: TypeDecl,
+}
+
+#[derive(Debug)]
+pub(crate) enum TypeDecl {
+    Simple(Name),
+    Optional(Box<TypeDecl>),
+}
+
+/// One end of a relationship: `Cat 1c "chases"`
+#[derive(Debug)]
+pub(crate) struct End {
+    pub object: Name,
+    pub cardinality: Cardinality,
+    pub conditionality: Conditionality,
+    pub phrase: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct BinaryDecl {
+    pub number: (u16, Range<usize>),
+    pub referrer: End,
+    pub via: Name,
+    pub referent: End,
+}
+
+#[derive(Debug)]
+pub(crate) struct IsaDecl {
+    pub number: (u16, Range<usize>),
+    pub supertype: Name,
+    pub subtypes: Vec<Name>,
+}
+
+#[derive(Debug)]
+pub(crate) struct AssociativeDecl {
+    pub number: (u16, Range<usize>),
+    pub link: Name,
+    pub cardinality: Cardinality,
+    pub one_via: Name,
+    pub other_via: Name,
+    pub one: End,
+    pub other: End,
+}
+
+pub(crate) struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Spanned>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(src: &'a str, tokens: Vec<Spanned>) -> Self {
+        Self {
+            src,
+            tokens,
+            position: 0,
+        }
+    }
+
+    pub(crate) fn parse(mut self) -> Result<Ast> {
+        let doc = self.docs();
+        self.keyword("domain")?;
+        let domain = self.name("a domain name")?;
+
+        let mut items = Vec::new();
+        loop {
+            let doc = self.docs();
+            let item = match &self.peek().token {
+                Token::Word(w) if w == "enum" => Item::Enumeration(self.enumeration(doc)?),
+                Token::Word(w) if w == "object" => Item::Object(self.object(doc)?),
+                Token::Word(w) if w == "binary" => Item::Binary(self.binary()?),
+                Token::Word(w) if w == "isa" => Item::Isa(self.isa()?),
+                Token::Word(w) if w == "associative" => Item::Associative(self.associative()?),
+                Token::Eof if doc.is_empty() => break,
+                _ => {
+                    return Err(
+                        self.unexpected("`enum`, `object`, `binary`, `isa` or `associative`")
+                    )
+                }
+            };
+            items.push(item);
+        }
+
+        Ok(Ast { doc, domain, items })
+    }
+
+    fn object(&mut self, doc: Vec<String>) -> Result<ObjectDecl> {
+        self.keyword("object")?;
+        let name = self.name("an object name")?;
+        self.punct('(')?;
+        let key_letter = self.name("a key letter")?;
+        self.punct(')')?;
+        self.punct('{')?;
+
+        let mut attributes = Vec::new();
+        while !self.eat_punct('}') {
+            let identifiers = self.identifiers()?;
+            let name = self.name("an attribute name")?;
+            self.punct(':')?;
+            // A referential attribute is whatever the relationship says it is
+            // `via`. Declaring it here as well just makes two of them.
+            let amp = self.peek().span.clone();
+            if self.eat_punct('&') {
+                return Err(error(
+                    self.src,
+                    amp,
+                    "referential attributes come from `via` on the relationship, leave them out \
+                     of the object",
+                ));
+            }
+            let mut ty = TypeDecl::Simple(self.name("a type")?);
+            if self.eat_punct('?') {
+                ty = TypeDecl::Optional(Box::new(ty));
+            }
+            attributes.push(AttributeDecl {
+                identifiers,
+                name,
+                ty,
+            });
+
+            if !self.eat_punct(',') {
+                self.punct('}')?;
+                break;
+            }
+        }
+
+        Ok(ObjectDecl {
+            doc,
+            name,
+            key_letter,
+            attributes,
+        })
+    }
+
+    fn enumeration(&mut self, doc: Vec<String>) -> Result<EnumDecl> {
+        self.keyword("enum")?;
+        let name = self.name("an enumeration name")?;
+        self.punct('{')?;
+
+        let mut variants = Vec::new();
+        while !self.eat_punct('}') {
+            variants.push(self.name("a variant")?);
+            if !self.eat_punct(',') {
+                self.punct('}')?;
+                break;
+            }
+        }
+
+        Ok(EnumDecl {
+            doc,
+            name,
+            variants,
+        })
+    }
+
+    fn binary(&mut self) -> Result<BinaryDecl> {
+        self.keyword("binary")?;
+        let number = self.number()?;
+        self.punct('{')?;
+        self.keyword("referrer")?;
+        let referrer = self.end()?;
+        self.keyword("via")?;
+        let via = self.name("a referential attribute")?;
+        self.keyword("referent")?;
+        let referent = self.end()?;
+        self.punct('}')?;
+
+        Ok(BinaryDecl {
+            number,
+            referrer,
+            via,
+            referent,
+        })
+    }
+
+    fn isa(&mut self) -> Result<IsaDecl> {
+        self.keyword("isa")?;
+        let number = self.number()?;
+        let supertype = self.name("a supertype")?;
+        self.punct('{')?;
+
+        let mut subtypes = Vec::new();
+        while !self.eat_punct('}') {
+            subtypes.push(self.name("a subtype")?);
+            if !self.eat_punct(',') {
+                self.punct('}')?;
+                break;
+            }
+        }
+
+        Ok(IsaDecl {
+            number,
+            supertype,
+            subtypes,
+        })
+    }
+
+    fn associative(&mut self) -> Result<AssociativeDecl> {
+        self.keyword("associative")?;
+        let number = self.number()?;
+        self.punct('{')?;
+        self.keyword("link")?;
+        let link = self.name("an object name")?;
+        let (cardinality, conditionality, span) = self.multiplicity()?;
+        if conditionality == Conditionality::Conditional {
+            return Err(error(
+                self.src,
+                span,
+                "an associative object can't be conditional, use `1` or `M`",
+            ));
+        }
+        self.keyword("via")?;
+        let one_via = self.name("a referential attribute")?;
+        self.punct(',')?;
+        let other_via = self.name("a referential attribute")?;
+        self.keyword("one")?;
+        let one = self.end()?;
+        self.keyword("other")?;
+        let other = self.end()?;
+        self.punct('}')?;
+
+        Ok(AssociativeDecl {
+            number,
+            link,
+            cardinality,
+            one_via,
+            other_via,
+            one,
+            other,
+        })
+    }
+
+    fn end(&mut self) -> Result<End> {
+        let object = self.name("an object name")?;
+        let (cardinality, conditionality, _) = self.multiplicity()?;
+        let phrase = match &self.peek().token {
+            Token::Str(s) => s.clone(),
+            _ => return Err(self.unexpected("a phrase, in quotes")),
+        };
+        self.position += 1;
+
+        Ok(End {
+            object,
+            cardinality,
+            conditionality,
+            phrase,
+        })
+    }
+
+    /// `1`, `1c`, `M`, or `Mc`
+    fn multiplicity(&mut self) -> Result<(Cardinality, Conditionality, Range<usize>)> {
+        let token = self.peek().clone();
+        let result = match &token.token {
+            Token::Word(w) => match w.as_str() {
+                "1" => (Cardinality::One, Conditionality::Unconditional),
+                "1c" => (Cardinality::One, Conditionality::Conditional),
+                "M" => (Cardinality::Many, Conditionality::Unconditional),
+                "Mc" => (Cardinality::Many, Conditionality::Conditional),
+                _ => return Err(self.unexpected("a multiplicity: `1`, `1c`, `M` or `Mc`")),
+            },
+            _ => return Err(self.unexpected("a multiplicity: `1`, `1c`, `M` or `Mc`")),
+        };
+        self.position += 1;
+
+        Ok((result.0, result.1, token.span))
+    }
+
+    /// `*1 *2`, or just `*`, which is `*1`
+    fn identifiers(&mut self) -> Result<Vec<(u16, Range<usize>)>> {
+        let mut identifiers = Vec::new();
+        loop {
+            let start = self.peek().span.clone();
+            if !self.eat_punct('*') {
+                break;
+            }
+
+            let token = self.peek().clone();
+            match &token.token {
+                // No space allowed, otherwise `* 2fast: Integer` is confusing.
+                Token::Word(w)
+                    if token.span.start == start.end && w.chars().all(|c| c.is_ascii_digit()) =>
+                {
+                    let number = w.parse::<u16>().map_err(|_| {
+                        error(self.src, token.span.clone(), "that's a lot of identifiers")
+                    })?;
+                    self.position += 1;
+                    identifiers.push((number, start.start..token.span.end));
+                }
+                _ => identifiers.push((1, start)),
+            }
+        }
+
+        Ok(identifiers)
+    }
+
+    /// `R42`
+    fn number(&mut self) -> Result<(u16, Range<usize>)> {
+        let token = self.peek().clone();
+        if let Token::Word(w) = &token.token {
+            if let Some(Ok(n)) = w.strip_prefix('R').map(str::parse::<u16>) {
+                self.position += 1;
+                return Ok((n, token.span));
+            }
+        }
+
+        Err(self.unexpected("a relationship number, like `R1`"))
+    }
+
+    fn name(&mut self, what: &str) -> Result<Name> {
+        let token = self.peek().clone();
+        match token.token {
+            Token::Word(text) | Token::Str(text) => {
+                self.position += 1;
+                Ok(Name {
+                    text,
+                    span: token.span,
+                })
+            }
+            _ => Err(self.unexpected(what)),
+        }
+    }
+
+    fn docs(&mut self) -> Vec<String> {
+        let mut docs = Vec::new();
+        while let Token::Doc(d) = &self.peek().token {
+            docs.push(d.clone());
+            self.position += 1;
+        }
+        docs
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<()> {
+        match &self.peek().token {
+            Token::Word(w) if w == keyword => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(self.unexpected(&format!("`{}`", keyword))),
+        }
+    }
+
+    fn punct(&mut self, c: char) -> Result<()> {
+        if self.eat_punct(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", c)))
+        }
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        if self.peek().token == Token::Punct(c) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> &Spanned {
+        // The lexer always ends with Eof, and we never move past it.
+        &self.tokens[self.position.min(self.tokens.len() - 1)]
+    }
+
+    fn unexpected(&self, expected: &str) -> crate::codegen::Error {
+        let found = self.peek();
+        error(
+            self.src,
+            found.span.clone(),
+            format!("expected {}, found {}", expected, found.token.describe()),
+        )
+    }
+}
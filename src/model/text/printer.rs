@@ -0,0 +1,275 @@
+//! Text Model Pretty-Printer
+//!
+//! The inverse of the parser, give or take formatting and comments. Objects come
+//! out sorted by name, and relationships by number, so that the same model always
+//! prints the same way. That's what makes the text diffable.
+use std::fmt::Write;
+
+use uuid::Uuid;
+
+use crate::codegen::SarzakModel;
+use crate::sarzak::{export, Cardinality, Conditionality, Relationship, Type};
+
+/// Print a model as text that [`parse`][super::parse] will read back
+///
+/// Compiler options that came from the paper, rather than an object description,
+/// are written into the description, so that they survive the round trip.
+pub fn to_text(model: &SarzakModel) -> String {
+    let store = &model.sarzak;
+    let mut out = String::new();
+
+    // Writing to a String doesn't fail, hence all of the `let _`s.
+    doc(&mut out, &model.description);
+    let _ = writeln!(out, "domain {}", name(&model.domain));
+
+    for enumeration in store.sorted_enumerations() {
+        let _ = writeln!(out);
+        doc(&mut out, &enumeration.description);
+        let variants: Vec<String> = enumeration
+            .variants
+            .iter()
+            .map(|v| name(v.inner()))
+            .collect();
+        let _ = writeln!(
+            out,
+            "enum {} {{ {} }}",
+            name(enumeration.name.inner()),
+            variants.join(", ")
+        );
+    }
+
+    let mut objects: Vec<_> = store.objects().collect();
+    objects.sort_by(|a, b| a.name.inner().cmp(b.name.inner()));
+
+    for object in objects {
+        let _ = writeln!(out);
+
+        let mut description = object.description.clone();
+        if !description.contains("❗️") {
+            if let Some(config) = model.config.get(&object.id) {
+                if let Ok(json) = serde_json::to_string(config) {
+                    if !description.is_empty() {
+                        description.push_str("\n\n");
+                    }
+                    let _ = write!(description, "❗️ {}", json);
+                }
+            }
+        }
+        doc(&mut out, &description);
+
+        let _ = writeln!(
+            out,
+            "object {} ({}) {{",
+            name(object.name.inner()),
+            name(&object.key_letter)
+        );
+
+        let mut attributes: Vec<_> = object
+            .attributes
+            .iter()
+            .filter_map(|(name, id)| store.exhume_attribute(id).map(|a| (name, a)))
+            // There's no writing these down, the `via` on the relationship covers it.
+            .filter(|(_, a)| !matches!(a.attr_t, Type::ForeignKey(_)))
+            .collect();
+        attributes.sort_by(|a, b| (a.0 != "id", a.0).cmp(&(b.0 != "id", b.0)));
+
+        for (attr_name, attr) in attributes {
+            let identifiers: String = attr
+                .identifiers
+                .iter()
+                .map(|i| format!("*{} ", i))
+                .collect();
+            let _ = writeln!(
+                out,
+                "    {}{}: {},",
+                identifiers,
+                name(attr_name),
+                type_name(model, &attr.attr_t)
+            );
+        }
+        let _ = writeln!(out, "}}");
+    }
+
+    for rel in store.sorted_relationships() {
+        match rel {
+            Relationship::Binary(id) => {
+                let binary = store.exhume_binary(id);
+                let referrer = binary.and_then(|b| store.exhume_referrer(&b.from));
+                let referent = binary.and_then(|b| store.exhume_referent(&b.to));
+                if let (Some(binary), Some(referrer), Some(referent)) = (binary, referrer, referent)
+                {
+                    let _ = writeln!(out);
+                    let _ = writeln!(out, "binary R{} {{", binary.number);
+                    let _ = writeln!(
+                        out,
+                        "    referrer {} via {}",
+                        end(
+                            model,
+                            &referrer.obj_id,
+                            &referrer.cardinality,
+                            &referrer.conditionality,
+                            &referrer.description
+                        ),
+                        name(referrer.referential_attribute.inner())
+                    );
+                    let _ = writeln!(
+                        out,
+                        "    referent {}",
+                        end(
+                            model,
+                            &referent.obj_id,
+                            &referent.cardinality,
+                            &referent.conditionality,
+                            &referent.description
+                        )
+                    );
+                    let _ = writeln!(out, "}}");
+                }
+            }
+            Relationship::Isa(id) => {
+                let isa = store.exhume_isa(id);
+                let supertype = isa.and_then(|i| store.exhume_supertype(&i.supertype));
+                if let (Some(isa), Some(supertype)) = (isa, supertype) {
+                    let mut subtypes: Vec<String> = isa
+                        .subtypes
+                        .iter()
+                        .filter_map(|s| store.exhume_subtype(s))
+                        .map(|s| object_name(model, &s.obj_id))
+                        .collect();
+                    subtypes.sort();
+
+                    let _ = writeln!(out);
+                    let _ = writeln!(
+                        out,
+                        "isa R{} {} {{ {} }}",
+                        isa.number,
+                        object_name(model, &supertype.obj_id),
+                        subtypes.join(", ")
+                    );
+                }
+            }
+            Relationship::Associative(id) => {
+                let assoc = store.exhume_associative(id);
+                let from = assoc.and_then(|a| store.exhume_associative_referrer(&a.from));
+                let one = assoc.and_then(|a| store.exhume_associative_referent(&a.one));
+                let other = assoc.and_then(|a| store.exhume_associative_referent(&a.other));
+                if let (Some(assoc), Some(from), Some(one), Some(other)) = (assoc, from, one, other)
+                {
+                    let _ = writeln!(out);
+                    let _ = writeln!(out, "associative R{} {{", assoc.number);
+                    let _ = writeln!(
+                        out,
+                        "    link {} {} via {}, {}",
+                        object_name(model, &from.obj_id),
+                        multiplicity(&from.cardinality, &Conditionality::Unconditional),
+                        name(from.one_referential_attribute.inner()),
+                        name(from.other_referential_attribute.inner())
+                    );
+                    let _ = writeln!(
+                        out,
+                        "    one {}",
+                        end(
+                            model,
+                            &one.obj_id,
+                            &one.cardinality,
+                            &one.conditionality,
+                            &one.description
+                        )
+                    );
+                    let _ = writeln!(
+                        out,
+                        "    other {}",
+                        end(
+                            model,
+                            &other.obj_id,
+                            &other.cardinality,
+                            &other.conditionality,
+                            &other.description
+                        )
+                    );
+                    let _ = writeln!(out, "}}");
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn end(
+    model: &SarzakModel,
+    obj_id: &Uuid,
+    cardinality: &Cardinality,
+    conditionality: &Conditionality,
+    phrase: &str,
+) -> String {
+    format!(
+        "{} {} {}",
+        object_name(model, obj_id),
+        multiplicity(cardinality, conditionality),
+        quote(phrase)
+    )
+}
+
+fn multiplicity(cardinality: &Cardinality, conditionality: &Conditionality) -> &'static str {
+    match (cardinality, conditionality) {
+        (Cardinality::One, Conditionality::Unconditional) => "1",
+        (Cardinality::One, Conditionality::Conditional) => "1c",
+        (Cardinality::Many, Conditionality::Unconditional) => "M",
+        (Cardinality::Many, Conditionality::Conditional) => "Mc",
+    }
+}
+
+fn type_name(model: &SarzakModel, ty: &Type) -> String {
+    match ty {
+        Type::Optional(ty) => format!("{}?", type_name(model, ty)),
+        Type::Enumeration(e) => name(e.name.inner()),
+        // Everything else is spelled the same as when it's exported.
+        ty => export::type_name(ty, model),
+    }
+}
+
+fn object_name(model: &SarzakModel, id: &Uuid) -> String {
+    match model.sarzak.exhume_object(id) {
+        Some(o) => name(o.name.inner()),
+        // Something is broken, but let's not make it worse by printing junk.
+        None => quote(&id.to_string()),
+    }
+}
+
+/// Bare if it can be, quoted if it must be
+fn name(name: &str) -> String {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        name.to_owned()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn doc(out: &mut String, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    for line in text.split('\n') {
+        if line.is_empty() {
+            let _ = writeln!(out, "///");
+        } else {
+            let _ = writeln!(out, "/// {}", line);
+        }
+    }
+}
@@ -3,11 +3,13 @@ use std::{collections::HashMap, io};
 use serde::{Deserialize, Serialize};
 use uuid::{uuid, Uuid};
 
+use crate::codegen::{CodeGenError, Error, Result};
+
 pub mod attribute;
 pub mod object;
 pub mod relationship;
 
-pub use attribute::{Attribute, AttributeName, Type};
+pub use attribute::{Attribute, AttributeName, Enumeration, Type};
 pub use object::Object;
 pub use relationship::{Cardinality, Conditionality, Relationship};
 
@@ -74,48 +76,56 @@ pub struct Schema {
     pub relationships: HashMap<Uuid, Relationship>,
 }
 
-impl From<CuckooModel> for Schema {
-    fn from(value: CuckooModel) -> Self {
+/// Cuckoo files that are missing one of the two keys, or that have the wrong sort
+/// of thing in one of the arrays are reported, rather than panicking.
+impl TryFrom<CuckooModel> for Schema {
+    type Error = Error;
+
+    fn try_from(value: CuckooModel) -> Result<Self> {
         // Unwrap the Vec<ObjectType::Object> into Vec<Object> to make access more pleasant.
-        let objects = value
-            .get("Objects")
-            .expect("Invalid Schema")
+        let objects = get_key(&value, "Objects")?
             .iter()
-            .map(|x| {
-                if let ObjectType::Object(o) = x {
-                    (o.id, o.clone())
-                } else {
-                    panic!("Invalid Schema")
+            .map(|x| match x {
+                ObjectType::Object(o) => Ok((o.id, o.clone())),
+                ObjectType::Relationship(r) => {
+                    Err(wrong_kind(r.get_id(), "object", "relationship"))
                 }
             })
-            .collect::<Vec<(Uuid, Object)>>()
-            .into_iter()
-            .collect::<HashMap<Uuid, Object>>();
+            .collect::<Result<HashMap<Uuid, Object>>>()?;
 
         // Here we put Relationships into a hashmap where the key is the relationship id.
-        let relationships = value
-            .get("Relationships")
-            .expect("Invalid Schema")
+        let relationships = get_key(&value, "Relationships")?
             .iter()
-            .map(|x| {
-                if let ObjectType::Relationship(r) = x {
-                    (r.get_id(), r.clone())
-                } else {
-                    panic!("Invalid Schema")
-                }
+            .map(|x| match x {
+                ObjectType::Relationship(r) => Ok((r.get_id(), r.clone())),
+                ObjectType::Object(o) => Err(wrong_kind(o.id, "relationship", "object")),
             })
-            .collect::<Vec<(Uuid, Relationship)>>()
-            .into_iter()
-            .collect::<HashMap<Uuid, Relationship>>();
+            .collect::<Result<HashMap<Uuid, Relationship>>>()?;
 
-        Self {
+        Ok(Self {
             version: "ooa_0".to_owned(),
             objects,
             relationships,
-        }
+        })
     }
 }
 
+fn get_key<'a>(value: &'a CuckooModel, key: &str) -> Result<&'a Vec<ObjectType>> {
+    value.get(key).ok_or_else(|| {
+        Error(CodeGenError::MissingSchemaKey {
+            key: key.to_owned(),
+        })
+    })
+}
+
+fn wrong_kind(id: Uuid, expected: &str, found: &str) -> Error {
+    Error(CodeGenError::WrongEntityKind {
+        id,
+        expected: expected.to_owned(),
+        found: found.to_owned(),
+    })
+}
+
 pub trait ReadSchema {
     fn from_json(&mut self) -> io::Result<Schema>;
 }
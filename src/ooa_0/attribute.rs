@@ -1,5 +1,6 @@
 use std::fmt;
 
+use heck::ToUpperCamelCase;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -48,8 +49,20 @@ pub struct Attribute {
     pub name: AttributeName,
     #[serde(rename = "type")]
     pub attr_t: Type,
+    /// The identifiers that this attribute is a part of
+    ///
+    /// `*1` is `1`, `*2` is `2`, and so on. An identifier with more than one
+    /// attribute is compound. Most attributes aren't part of any, and they don't
+    /// bother writing an empty list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identifiers: Vec<u16>,
 }
 
+/// The type of an [`Attribute`]
+///
+/// The first handful are the originals, and are what Cuckoo knows about. The rest
+/// came later, and are written into the model by hand, or by a text model. They
+/// serialize as they are named, so older models load just as they always have.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum Type {
     Uuid,
@@ -59,6 +72,25 @@ pub enum Type {
     Boolean,
     #[serde(rename = "foreign_key")]
     ForeignKey(Uuid),
+    Integer8,
+    Integer16,
+    Integer32,
+    Unsigned8,
+    Unsigned16,
+    Unsigned32,
+    Unsigned64,
+    Float32,
+    /// A point in time, as a `SystemTime`
+    Timestamp,
+    Duration,
+    /// A blob of bytes
+    Bytes,
+    /// An attribute that may not have a value
+    #[serde(rename = "optional")]
+    Optional(Box<Type>),
+    /// One of a fixed set of values, declared in the model
+    #[serde(rename = "enumeration")]
+    Enumeration(Enumeration),
 }
 
 impl fmt::Display for Type {
@@ -70,6 +102,60 @@ impl fmt::Display for Type {
             Type::String => write!(f, "std::string::String"),
             Type::Boolean => write!(f, "bool"),
             Type::ForeignKey(u) => write!(f, "&{}", u),
+            Type::Integer8 => write!(f, "i8"),
+            Type::Integer16 => write!(f, "i16"),
+            Type::Integer32 => write!(f, "i32"),
+            Type::Unsigned8 => write!(f, "u8"),
+            Type::Unsigned16 => write!(f, "u16"),
+            Type::Unsigned32 => write!(f, "u32"),
+            Type::Unsigned64 => write!(f, "u64"),
+            Type::Float32 => write!(f, "f32"),
+            Type::Timestamp => write!(f, "std::time::SystemTime"),
+            Type::Duration => write!(f, "std::time::Duration"),
+            Type::Bytes => write!(f, "Vec<u8>"),
+            Type::Optional(ty) => write!(f, "Option<{}>", ty),
+            Type::Enumeration(e) => write!(f, "{}", e.name.inner().to_upper_camel_case()),
+        }
+    }
+}
+
+impl Type {
+    /// The [`Enumeration`], if that's what this is, optional or not
+    pub fn enumeration(&self) -> Option<&Enumeration> {
+        match self {
+            Type::Enumeration(e) => Some(e),
+            Type::Optional(ty) => ty.enumeration(),
+            _ => None,
+        }
+    }
+}
+
+/// A user defined enumeration
+///
+/// It's carried around inside of the [`Type`] of every attribute that uses it,
+/// rather than on it's own someplace. That way it goes everywhere that an
+/// attribute goes, Cuckoo files included, without anyone having to know about it.
+/// The flip side is that two attributes could disagree about what the variants
+/// are. Model validation complains about that.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Enumeration {
+    pub id: Uuid,
+    pub name: AttributeName,
+    #[serde(default)]
+    pub description: String,
+    pub variants: Vec<AttributeName>,
+}
+
+impl Enumeration {
+    pub fn new<S: AsRef<str>>(name: &str, variants: &[S]) -> Self {
+        Enumeration {
+            id: Uuid::new_v5(&UUID_NS, format!("enumeration::{}", name).as_bytes()),
+            name: AttributeName(name.to_owned()),
+            description: String::new(),
+            variants: variants
+                .iter()
+                .map(|v| AttributeName(v.as_ref().to_owned()))
+                .collect(),
         }
     }
 }
@@ -84,6 +170,7 @@ impl Attribute {
             id,
             name: AttributeName(name.to_owned()),
             attr_t: ty,
+            identifiers: Vec::new(),
         }
     }
 }
@@ -18,6 +18,9 @@ pub use crate::ooa_1::{
 };
 
 // Macro re-exports
+pub use get_assoc_from;
+pub use get_assoc_one;
+pub use get_assoc_other;
 pub use get_referent;
 pub use get_referrer;
 pub use get_subtypes;
@@ -192,6 +192,131 @@ macro_rules! get_subtypes {
     }};
 }
 
+/// Macro to get the referrer, the `from` side, of an Associative relationship
+///
+/// This is the link class. We are given a reference to the store, a reference to an
+/// Object, and the name of the relationship to traverse. E.g.,
+///
+/// ```ignore
+/// let ack = get_assoc_from!(event("R20"), store);
+/// ```
+///
+/// We also support the form wherein a [RelPointer][rp] is passed in:
+///
+/// ```ignore
+///     let event = store.get_obj("Event").unwrap();
+///     let r20 = event.rels.get("R20").unwrap();
+///     let ack = get_assoc_from!(r20, store);
+/// ```
+///
+/// [rp]: crate::ooa_1::object::RelPointer
+#[macro_export]
+macro_rules! get_assoc_from {
+    ($obj:ident($rel:expr), $store:expr) => {
+        $store
+            .exhume_object(
+                &$store
+                    .exhume_associative_referrer(
+                        &$store
+                            .exhume_associative(&$obj.rels.get($rel).unwrap().value)
+                            .unwrap()
+                            .from,
+                    )
+                    .unwrap()
+                    .obj_id,
+            )
+            .unwrap()
+    };
+    ($rel:expr, $store:expr) => {
+        $store
+            .exhume_object(
+                &$store
+                    .exhume_associative_referrer(
+                        &$store.exhume_associative(&$rel.value).unwrap().from,
+                    )
+                    .unwrap()
+                    .obj_id,
+            )
+            .unwrap()
+    };
+}
+
+/// Macro to get the `one` side of an Associative relationship
+///
+/// Works just like [`get_assoc_from`], but lands on the `one` referent.
+///
+/// ```ignore
+/// let event = get_assoc_one!(ack("R20"), store);
+/// ```
+#[macro_export]
+macro_rules! get_assoc_one {
+    ($obj:ident($rel:expr), $store:expr) => {
+        $store
+            .exhume_object(
+                &$store
+                    .exhume_associative_referent(
+                        &$store
+                            .exhume_associative(&$obj.rels.get($rel).unwrap().value)
+                            .unwrap()
+                            .one,
+                    )
+                    .unwrap()
+                    .obj_id,
+            )
+            .unwrap()
+    };
+    ($rel:expr, $store:expr) => {
+        $store
+            .exhume_object(
+                &$store
+                    .exhume_associative_referent(
+                        &$store.exhume_associative(&$rel.value).unwrap().one,
+                    )
+                    .unwrap()
+                    .obj_id,
+            )
+            .unwrap()
+    };
+}
+
+/// Macro to get the `other` side of an Associative relationship
+///
+/// Works just like [`get_assoc_from`], but lands on the `other` referent.
+///
+/// ```ignore
+/// let state = get_assoc_other!(ack("R20"), store);
+/// ```
+#[macro_export]
+macro_rules! get_assoc_other {
+    ($obj:ident($rel:expr), $store:expr) => {
+        $store
+            .exhume_object(
+                &$store
+                    .exhume_associative_referent(
+                        &$store
+                            .exhume_associative(&$obj.rels.get($rel).unwrap().value)
+                            .unwrap()
+                            .other,
+                    )
+                    .unwrap()
+                    .obj_id,
+            )
+            .unwrap()
+    };
+    ($rel:expr, $store:expr) => {
+        $store
+            .exhume_object(
+                &$store
+                    .exhume_associative_referent(
+                        &$store.exhume_associative(&$rel.value).unwrap().other,
+                    )
+                    .unwrap()
+                    .obj_id,
+            )
+            .unwrap()
+    };
+}
+
 /// Macro to traverse [`Object`][🦀] ➡ [`Supertype`][🦞], via _R14(c)_
 ///
 /// This macro expects a &[`Object`][🦀], and returns an Option<&[`Supertype`][🦞]>.
@@ -7,8 +7,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::sarzak::{
-    Associative, AssociativeReferent, AssociativeReferrer, Attribute, Binary, Isa, Object,
-    Referent, Referrer, Relationship, Subtype, Supertype,
+    Associative, AssociativeReferent, AssociativeReferrer, Attribute, Binary, Enumeration, Isa,
+    Object, Referent, Referrer, Relationship, Subtype, Supertype,
 };
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -90,7 +90,14 @@ impl ObjectStore {
     ///
     /// This one is tricky, because [Relationship] doesn't have an id -- it's an enum.
     /// So the id that we will be forced to use is the id of the variant.
+    ///
+    /// If the variant is already in the store we also index the relationship by
+    /// name, e.g., "R12", so that [`get_rel`](Self::get_rel) works.
     pub fn inter_relationship(&mut self, relationship: Relationship) {
+        if let Some(number) = relationship.get_number(self) {
+            self.rel
+                .insert(format!("R{}", number), *relationship.get_id());
+        }
         self.relationship
             .insert(relationship.get_id().clone(), relationship);
     }
@@ -99,12 +106,34 @@ impl ObjectStore {
         self.relationship.iter()
     }
 
-    // pub fn sorted_relationship(&self) -> Box<dyn Fn() -> Iter<Relationship>> {
-    //     let mut values: Vec<Relationship> = self.relationship.values().cloned().collect();
-    //     values.sort();
-    //     let foo = move || values.iter();
-    //     Box::new(foo)
-    // }
+    /// Relationships, ordered by number
+    ///
+    /// This is the order a person reading the model expects. Relationships that
+    /// are missing their variant, and hence their number, go at the end.
+    pub fn sorted_relationships(&self) -> Vec<&Relationship> {
+        let mut values: Vec<&Relationship> = self.relationship.values().collect();
+        values.sort_by_key(|r| (r.get_number(self).unwrap_or(u16::MAX), *r.get_id()));
+        values
+    }
+
+    /// Every enumeration that an attribute uses, sorted by name
+    ///
+    /// Enumerations live in the attribute types, so the same one is usually in
+    /// here more than once. We take the first one we find, going by attribute id.
+    /// If they don't all agree, validation will say so.
+    pub fn sorted_enumerations(&self) -> Vec<&Enumeration> {
+        let mut attributes: Vec<&Attribute> = self.attribute.values().collect();
+        attributes.sort_by_key(|a| a.id);
+
+        let mut values: Vec<&Enumeration> = Vec::new();
+        for e in attributes.iter().filter_map(|a| a.attr_t.enumeration()) {
+            if !values.iter().any(|v| v.name == e.name) {
+                values.push(e);
+            }
+        }
+        values.sort_by(|a, b| a.name.cmp(&b.name));
+        values
+    }
 
     pub fn exhume_relationship(&self, id: &Uuid) -> Option<&Relationship> {
         self.relationship.get(id)
@@ -142,6 +171,10 @@ impl ObjectStore {
         self.referrer.get(id)
     }
 
+    pub fn iter_referrer(&self) -> impl Iterator<Item = (&Uuid, &Referrer)> + '_ {
+        self.referrer.iter()
+    }
+
     pub fn inter_referent(&mut self, referent: Referent) {
         self.referent.insert(referent.id, referent);
     }
@@ -150,6 +183,10 @@ impl ObjectStore {
         self.referent.get(id)
     }
 
+    pub fn iter_referent(&self) -> impl Iterator<Item = (&Uuid, &Referent)> + '_ {
+        self.referent.iter()
+    }
+
     pub fn inter_isa(&mut self, isa: Isa) {
         self.isa.insert(isa.id, isa);
     }
@@ -197,6 +234,12 @@ impl ObjectStore {
         self.associative_referrer.get(id)
     }
 
+    pub fn iter_associative_referrer(
+        &self,
+    ) -> impl Iterator<Item = (&Uuid, &AssociativeReferrer)> + '_ {
+        self.associative_referrer.iter()
+    }
+
     pub fn inter_associative_referent(&mut self, associative_referent: AssociativeReferent) {
         self.associative_referent
             .insert(associative_referent.id, associative_referent);
@@ -206,6 +249,12 @@ impl ObjectStore {
         self.associative_referent.get(id)
     }
 
+    pub fn iter_associative_referent(
+        &self,
+    ) -> impl Iterator<Item = (&Uuid, &AssociativeReferent)> + '_ {
+        self.associative_referent.iter()
+    }
+
     pub fn inter_associative(&mut self, associative: Associative) {
         self.associative.insert(associative.id, associative);
     }
@@ -213,6 +262,10 @@ impl ObjectStore {
     pub fn exhume_associative(&self, id: &Uuid) -> Option<&Associative> {
         self.associative.get(id)
     }
+
+    pub fn iter_associative(&self) -> impl Iterator<Item = (&Uuid, &Associative)> + '_ {
+        self.associative.iter()
+    }
 }
 
 pub trait WriteObjectStore
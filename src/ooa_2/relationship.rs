@@ -20,12 +20,14 @@ use crate::ooa_2::{Context, Extrude, ObjectStore};
 ///
 /// A binary relationship connects two objects. An isa relationship connects two
 /// or more objects. One objects is the [Supertype][sup], and the others are [Subtype][sub]s.
-/// An associative relationship associates _three_ objects. TODO: I'll explain once it's
-/// actually implemented.
+/// An associative relationship associates _three_ objects. The `from` side is the
+/// [AssociativeReferrer][ar], which formalizes the relationship between the `one` and
+/// `other` [AssociativeReferent]s. Think link class.
 ///
 /// A relationship has a number, that is unique in the model.
 ///
 /// [ass]: crate::ooa_0::relationship::Associative
+/// [ar]: crate::ooa_0::relationship::AssociativeReferrer
 /// [sup]: crate::ooa_1::relationship::Supertype
 /// [sub]: crate::ooa_1::relationship::Subtype
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -65,7 +67,7 @@ impl Relationship {
         match self {
             Self::Binary(b) => store.exhume_binary(b).map(|b| b.number),
             Self::Isa(i) => store.exhume_isa(i).map(|i| i.number),
-            Self::Associative(_) => todo!(),
+            Self::Associative(a) => store.exhume_associative(a).map(|a| a.number),
         }
     }
 
@@ -173,7 +175,12 @@ impl Extrude<ooa_0::relationship::Associative, Context<'_>> for Associative {
         let one_id = one.id;
         context.0.inter_associative_referent(one);
 
-        let other = AssociativeReferent::from_with_assoc_id(input.other, input.id);
+        let mut other = AssociativeReferent::from_with_assoc_id(input.other, input.id);
+        // Reflexive associations point both sides at the same object, which would
+        // make the ids collide, and we'd lose one side.
+        if other.id == one_id {
+            other.id = Uuid::new_v5(&UUID_NS, format!("{}::other", other.id).as_bytes());
+        }
         let other_id = other.id;
         context.0.inter_associative_referent(other);
 
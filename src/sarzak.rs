@@ -6,13 +6,13 @@
 //! I'm trying to be careful to not use re-exports from the ooa_* crates.
 use std::{
     cmp::{Ord, PartialOrd},
+    collections::HashMap,
     fs::File,
     io,
     path::Path,
 };
 
 use log::{error, trace};
-use random_string;
 use serde::{Deserialize, Serialize};
 use uuid::{uuid, Uuid};
 
@@ -20,16 +20,23 @@ use crate::codegen::{Config, ConfigValue};
 
 #[macro_use]
 mod macros;
+pub mod diff;
+pub mod export;
 pub mod mc;
+pub mod migrate;
+pub mod validate;
 
+pub use diff::{diff_models, ModelDiff};
+pub use export::{export_model, to_dot, to_mermaid, to_plantuml, Format};
 pub use mc::*;
+pub use validate::{validate_imports, validate_model, Diagnostic};
 
 pub use get_obj_across_r17;
 pub use get_r_from_across_r6;
 
 pub use crate::ooa_0::{
     relationship::{AssociativeReferrer, Cardinality, Conditionality},
-    Attribute, AttributeName, Type,
+    Attribute, AttributeName, Enumeration, Type,
 };
 pub use crate::ooa_1::{
     object::{RelPointer, RelSide},
@@ -37,8 +44,13 @@ pub use crate::ooa_1::{
 };
 pub use crate::ooa_2::{Associative, AssociativeReferent, Binary, Isa, Object, Relationship};
 
-use crate::codegen::{DrawingObjectStore, SarzakObjectStore};
-use crate::model::{extract_ooa2, ReadModel};
+use crate::codegen::{CodeGenError, DrawingObjectStore, Error, Result, SarzakObjectStore};
+use crate::drawing::flatten_paper;
+use crate::model::{
+    extract_ooa2, flatten_ooa2,
+    jsformat::{EntityFormat, Paper, Point},
+    text, JSFormat, ReadModel, WriteModel,
+};
 
 // sarzak
 pub const UUID_NS: Uuid = uuid!("daccabb9-eb3a-5cde-ba7c-19a3f22ab649");
@@ -66,14 +78,149 @@ pub struct SarzakModel {
     pub view: [i32; 2],
 }
 
-// TODO: I don't want to think about error handling just yet, but this needs
-// a custom error type.
 impl SarzakModel {
-    pub fn load_cuckoo_model<P: AsRef<Path>>(path: P) -> std::io::Result<SarzakModel> {
-        let cuckoo = ReadModel::from_json(&mut File::open(path.as_ref())?)?;
+    /// Load a model file, as written by Cuckoo
+    ///
+    /// Anything wrong with the file, beyond not being able to open it, is reported
+    /// as a [`CodeGenError::BadCuckooModel`], wrapping what actually went wrong, and
+    /// where.
+    pub fn load_cuckoo_model<P: AsRef<Path>>(path: P) -> Result<SarzakModel> {
+        let path = path.as_ref();
+        let mut file = File::open(path).map_err(|source| {
+            Error(CodeGenError::LoadCuckooModel {
+                path: path.to_path_buf(),
+                source,
+            })
+        })?;
+
+        Self::from_cuckoo(&mut file).map_err(|e| {
+            Error(CodeGenError::BadCuckooModel {
+                path: path.to_path_buf(),
+                source: Box::new(e),
+            })
+        })
+    }
+
+    /// Load a model file, written in the text format
+    ///
+    /// See [`crate::model::text`] for what that looks like. Parse errors are
+    /// wrapped in a [`CodeGenError::BadTextModel`], so that the path comes along
+    /// with the line and column.
+    pub fn load_text_model<P: AsRef<Path>>(path: P) -> Result<SarzakModel> {
+        let path = path.as_ref();
+        let src = std::fs::read_to_string(path).map_err(|source| {
+            Error(CodeGenError::LoadTextModel {
+                path: path.to_path_buf(),
+                source,
+            })
+        })?;
+
+        text::parse_model(&src).map_err(|e| {
+            Error(CodeGenError::BadTextModel {
+                path: path.to_path_buf(),
+                source: Box::new(e),
+            })
+        })
+    }
+
+    /// Save the model as a Cuckoo model file
+    ///
+    /// This is how you get a model that you've been messing with programmatically
+    /// back into the tool. See the [`JSFormat`] conversion for what that entails.
+    pub fn save_cuckoo_model<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut file = File::create(path).map_err(|source| {
+            Error(CodeGenError::FileCreate {
+                path: path.to_path_buf(),
+                source,
+            })
+        })?;
 
-        let paper_id = cuckoo.paper.ids[0];
-        let paper = cuckoo.paper.entities.get(&paper_id).unwrap();
+        WriteModel::to_json(&mut file, &JSFormat::from(self)).map_err(|source| {
+            Error(CodeGenError::FileWrite {
+                path: path.to_path_buf(),
+                source,
+            })
+        })
+    }
+
+    fn from_cuckoo<R: io::Read>(reader: &mut R) -> Result<SarzakModel> {
+        ReadModel::from_json(reader)?.try_into()
+    }
+}
+
+/// Turn a model back into something that Cuckoo can open
+///
+/// The paper is rebuilt from the `extents`, the `view`, and whatever is in the
+/// drawing store. Objects that aren't drawn just don't show up on the paper, which
+/// is what Cuckoo does with them too.
+///
+/// Compiler options that came from an object description stay in the description.
+/// Anything else in the config goes on the paper, which is where it came from.
+impl From<&SarzakModel> for JSFormat {
+    fn from(model: &SarzakModel) -> Self {
+        let (objects, relationships) = flatten_ooa2(&model.sarzak);
+        let (paper_objects, paper_relationships) = flatten_paper(&model.drawing, &model.sarzak);
+
+        let mut config = Config::new();
+        for (id, value) in model.config.iter() {
+            let described = match model.sarzak.exhume_object(id) {
+                Some(obj) => obj.description.contains("❗️"),
+                None => false,
+            };
+            if !described {
+                config.insert(*id, value.clone());
+            }
+        }
+
+        let paper = Paper {
+            id: model.id,
+            description: model.description.clone(),
+            domain_name: model.domain.clone(),
+            domain_ns: crate::ooa_0::UUID_NS.to_string(),
+            width: model.extents[0],
+            height: model.extents[1],
+            offset: Point {
+                x: model.view[0],
+                y: model.view[1],
+            },
+            objects: paper_objects,
+            relationships: paper_relationships,
+            config: if config.iter().next().is_some() {
+                Some(config)
+            } else {
+                None
+            },
+        };
+
+        JSFormat {
+            paper: EntityFormat {
+                ids: vec![paper.id],
+                entities: HashMap::from([(paper.id, paper)]),
+            },
+            objects,
+            relationships,
+        }
+    }
+}
+
+/// Build a model from an in-memory Cuckoo file
+///
+/// This is what [`SarzakModel::load_cuckoo_model`] does once it's read the file.
+impl TryFrom<JSFormat> for SarzakModel {
+    type Error = Error;
+
+    fn try_from(cuckoo: JSFormat) -> Result<SarzakModel> {
+        let paper_id = *cuckoo
+            .paper
+            .ids
+            .first()
+            .ok_or(Error(CodeGenError::EmptyPaperIds))?;
+        let paper = cuckoo
+            .paper
+            .entities
+            .get(&paper_id)
+            .ok_or(Error(CodeGenError::MissingPaper { id: paper_id }))?;
 
         let mut config = match &paper.config {
             Some(c) => c.clone(),
@@ -83,7 +230,7 @@ impl SarzakModel {
         // Load the compiler options from object descriptions.
         // This is until we have a new tool. Until then, and maybe afterwards too,
         // parse the object descriptions for config options.
-        let sarzak = extract_ooa2(&cuckoo);
+        let sarzak = extract_ooa2(&cuckoo)?;
         sarzak.iter_object().for_each(|(id, obj)| {
             if obj.description.contains("❗️") {
                 let mut iter = obj.description.split("❗️");
@@ -117,14 +264,18 @@ impl SarzakModel {
         // Damn, I'm doing some swizzling here...
         // I guess I'm doing this because of the borrow checker? Not really sure.
         model.sarzak = sarzak;
-        model.drawing = cuckoo.into();
+        model.drawing = cuckoo.try_into()?;
 
         Ok(model)
     }
 }
 
+/// Read a serialized [`SarzakModel`]
+///
+/// Models written by older versions of nut are migrated on the way in. See
+/// [`migrate`].
 pub trait ReadSarzakModel {
-    fn from_json(&mut self) -> io::Result<SarzakModel>;
+    fn from_json(&mut self) -> Result<SarzakModel>;
 }
 
 pub trait WriteSarzakModel
@@ -135,9 +286,14 @@ where
 }
 
 impl<R: io::Read> ReadSarzakModel for R {
-    fn from_json(&mut self) -> io::Result<SarzakModel> {
-        let mut deserializer = serde_json::Deserializer::from_reader(self);
-        Ok(SarzakModel::deserialize(&mut deserializer)?)
+    fn from_json(&mut self) -> Result<SarzakModel> {
+        let mut value: serde_json::Value = serde_json::from_reader(self)
+            .map_err(|source| Error(CodeGenError::SerdeJsonBombed { source }))?;
+
+        migrate::migrate(&mut value)?;
+
+        serde_json::from_value(value)
+            .map_err(|source| Error(CodeGenError::SerdeJsonBombed { source }))
     }
 }
 
@@ -154,11 +310,8 @@ impl<W: io::Write> WriteSarzakModel for W {
 use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 pub const AS_TYPE: &str = "type";
 pub const AS_IDENT: &str = "ident";
-pub const AS_RIDENT: &str = "rando";
 pub const AS_CONST: &str = "const";
 
-pub const VAR_CHARS: &str = "abcdefghijklmnopqrstuvwxyz";
-
 impl Object {
     pub fn render(&self, which: &str) -> String {
         self.name.render(which)
@@ -171,6 +324,12 @@ impl Attribute {
     }
 }
 
+impl Enumeration {
+    pub fn render(&self, which: &str) -> String {
+        self.name.render(which)
+    }
+}
+
 impl AttributeName {
     pub fn render(&self, which: &str) -> String {
         // This is a bit of a hack. I want identifiers to be changed from 'type'
@@ -188,11 +347,6 @@ impl AttributeName {
         match which {
             AS_TYPE => name.to_upper_camel_case(),
             AS_IDENT => name.to_snake_case(),
-            AS_RIDENT => format!(
-                "{}_{}",
-                name.to_snake_case(),
-                random_string::generate(3, VAR_CHARS)
-            ),
             AS_CONST => name.to_shouty_snake_case(),
             _ => format!("unknown render type requested '{}'", which),
         }
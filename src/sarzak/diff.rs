@@ -0,0 +1,532 @@
+//! Semantic Model Diff
+//!
+//! Diffing Cuckoo files with `diff` is hopeless. Everything is keyed by UUID, and
+//! the maps come out in whatever order they feel like. This compares two
+//! [`SarzakModel`]s the way a person would: objects, attributes and relationships,
+//! all by name (or number), and it tells you what changed about them.
+//!
+//! Objects and relationships are matched by id first. If that fails we try the
+//! name, or the number, so that something recreated in the tool still lines up
+//! with its old self.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
+};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::sarzak::{Object, Relationship, SarzakModel, Type};
+
+/// The thing that changed
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Element {
+    Model { domain: String },
+    Object { name: String },
+    Attribute { object: String, name: String },
+    Relationship { number: u16 },
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Model { domain } => write!(f, "model `{}`", domain),
+            Self::Object { name } => write!(f, "object `{}`", name),
+            Self::Attribute { object, name } => write!(f, "attribute `{}.{}`", object, name),
+            Self::Relationship { number } => write!(f, "R{}", number),
+        }
+    }
+}
+
+/// A single difference between two models
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        element: Element,
+    },
+    Removed {
+        element: Element,
+    },
+    Modified {
+        element: Element,
+        property: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { element } => write!(f, "+ {}", element),
+            Self::Removed { element } => write!(f, "- {}", element),
+            Self::Modified {
+                element,
+                property,
+                old,
+                new,
+            } => {
+                // Descriptions go on for lines. Give them room.
+                if old.contains('\n') || new.contains('\n') {
+                    write!(f, "~ {} {}:", element, property)?;
+                    for line in old.lines() {
+                        write!(f, "\n    - {}", line)?;
+                    }
+                    for line in new.lines() {
+                        write!(f, "\n    + {}", line)?;
+                    }
+                    Ok(())
+                } else {
+                    write!(f, "~ {} {}: {} → {}", element, property, old, new)
+                }
+            }
+        }
+    }
+}
+
+/// Everything that changed between two models
+///
+/// `Display` is the human readable form. Serialize it for the JSON form.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ModelDiff {
+    pub old: String,
+    pub new: String,
+    pub changes: Vec<Change>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- {}", self.old)?;
+        writeln!(f, "+++ {}", self.new)?;
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// What goes in a property slot when one side doesn't have it.
+const NONE: &str = "(none)";
+
+/// Compare two models
+///
+/// Changes are ordered: the model itself, then objects by name, then relationships
+/// by number.
+pub fn diff_models(old: &SarzakModel, new: &SarzakModel) -> ModelDiff {
+    let mut changes = Vec::new();
+
+    let model = Element::Model {
+        domain: new.domain.clone(),
+    };
+    compare_properties(
+        &model,
+        &BTreeMap::from([
+            ("domain".to_owned(), old.domain.clone()),
+            ("description".to_owned(), old.description.clone()),
+        ]),
+        &BTreeMap::from([
+            ("domain".to_owned(), new.domain.clone()),
+            ("description".to_owned(), new.description.clone()),
+        ]),
+        &mut changes,
+    );
+
+    diff_objects(old, new, &mut changes);
+    diff_relationships(old, new, &mut changes);
+
+    ModelDiff {
+        old: old.domain.clone(),
+        new: new.domain.clone(),
+        changes,
+    }
+}
+
+fn diff_objects(old: &SarzakModel, new: &SarzakModel, changes: &mut Vec<Change>) {
+    let mut olds: Vec<&Object> = old.sarzak.objects().collect();
+    olds.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut news: Vec<&Object> = new.sarzak.objects().collect();
+    news.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let pairs = pair_up(&olds, &news, |o| o.id, |a, b| a.name == b.name);
+
+    for (o, n) in pairs {
+        match (o, n) {
+            (Some(o), None) => changes.push(Change::Removed {
+                element: Element::Object {
+                    name: o.name.inner().clone(),
+                },
+            }),
+            (None, Some(n)) => changes.push(Change::Added {
+                element: Element::Object {
+                    name: n.name.inner().clone(),
+                },
+            }),
+            (Some(o), Some(n)) => {
+                if o.name != n.name {
+                    changes.push(Change::Modified {
+                        element: Element::Object {
+                            name: o.name.inner().clone(),
+                        },
+                        property: "name".to_owned(),
+                        old: o.name.inner().clone(),
+                        new: n.name.inner().clone(),
+                    });
+                }
+
+                let element = Element::Object {
+                    name: n.name.inner().clone(),
+                };
+                compare_properties(
+                    &element,
+                    &object_properties(o, old),
+                    &object_properties(n, new),
+                    changes,
+                );
+
+                diff_attributes(o, old, n, new, changes);
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn object_properties(object: &Object, model: &SarzakModel) -> BTreeMap<String, String> {
+    let mut props = BTreeMap::from([
+        ("key letter".to_owned(), object.key_letter.clone()),
+        ("description".to_owned(), object.description.clone()),
+    ]);
+
+    if let Some(config) = model.config.get(&object.id) {
+        props.insert(
+            "config".to_owned(),
+            serde_json::to_string(config).unwrap_or_default(),
+        );
+    }
+
+    props
+}
+
+fn diff_attributes(
+    o: &Object,
+    old: &SarzakModel,
+    n: &Object,
+    new: &SarzakModel,
+    changes: &mut Vec<Change>,
+) {
+    let old_attrs = attribute_types(o, old);
+    let new_attrs = attribute_types(n, new);
+    let object = n.name.inner();
+
+    let element = |name: &str| Element::Attribute {
+        object: object.clone(),
+        name: name.to_owned(),
+    };
+
+    for (name, ty) in &old_attrs {
+        match new_attrs.get(name) {
+            None => changes.push(Change::Removed {
+                element: element(name),
+            }),
+            Some(new_ty) if new_ty != ty => changes.push(Change::Modified {
+                element: element(name),
+                property: "type".to_owned(),
+                old: ty.clone(),
+                new: new_ty.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    let old_ids = attribute_identifiers(o, old);
+    let new_ids = attribute_identifiers(n, new);
+    for (name, ids) in &old_ids {
+        match new_ids.get(name) {
+            Some(new_ids) if new_ids != ids => changes.push(Change::Modified {
+                element: element(name),
+                property: "identifiers".to_owned(),
+                old: ids.clone(),
+                new: new_ids.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    for name in new_attrs.keys() {
+        if !old_attrs.contains_key(name) {
+            changes.push(Change::Added {
+                element: element(name),
+            });
+        }
+    }
+}
+
+fn attribute_types(object: &Object, model: &SarzakModel) -> BTreeMap<String, String> {
+    object
+        .attributes
+        .iter()
+        .map(|(name, id)| {
+            let ty = match model.sarzak.exhume_attribute(id) {
+                // Foreign keys point at UUIDs, which don't mean much to anyone.
+                Some(attr) => match &attr.attr_t {
+                    Type::ForeignKey(id) => format!("&{}", object_name(model, id)),
+                    ty => ty.to_string(),
+                },
+                None => NONE.to_owned(),
+            };
+            (name.clone(), ty)
+        })
+        .collect()
+}
+
+/// The identifiers each attribute is part of, written the way a modeler would
+fn attribute_identifiers(object: &Object, model: &SarzakModel) -> BTreeMap<String, String> {
+    object
+        .attributes
+        .iter()
+        .map(|(name, id)| {
+            let ids = match model.sarzak.exhume_attribute(id) {
+                Some(attr) if !attr.identifiers.is_empty() => attr
+                    .identifiers
+                    .iter()
+                    .map(|i| format!("*{}", i))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => NONE.to_owned(),
+            };
+            (name.clone(), ids)
+        })
+        .collect()
+}
+
+fn diff_relationships(old: &SarzakModel, new: &SarzakModel, changes: &mut Vec<Change>) {
+    let olds = numbered(old);
+    let news = numbered(new);
+
+    let pairs = pair_up(&olds, &news, |r| *r.1.get_id(), |a, b| a.0 == b.0);
+
+    for (o, n) in pairs {
+        match (o, n) {
+            (Some((number, _)), None) => changes.push(Change::Removed {
+                element: Element::Relationship { number: *number },
+            }),
+            (None, Some((number, _))) => changes.push(Change::Added {
+                element: Element::Relationship { number: *number },
+            }),
+            (Some((old_number, o)), Some((new_number, n))) => {
+                if old_number != new_number {
+                    changes.push(Change::Modified {
+                        element: Element::Relationship {
+                            number: *old_number,
+                        },
+                        property: "number".to_owned(),
+                        old: format!("R{}", old_number),
+                        new: format!("R{}", new_number),
+                    });
+                }
+
+                let element = Element::Relationship {
+                    number: *new_number,
+                };
+                let old_props = relationship_properties(o, old);
+                let new_props = relationship_properties(n, new);
+
+                // A different kind of relationship is a different relationship.
+                if old_props.get("kind") != new_props.get("kind") {
+                    changes.push(Change::Removed {
+                        element: element.clone(),
+                    });
+                    changes.push(Change::Added { element });
+                } else {
+                    compare_properties(&element, &old_props, &new_props, changes);
+                }
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn numbered(model: &SarzakModel) -> Vec<(u16, &Relationship)> {
+    model
+        .sarzak
+        .sorted_relationships()
+        .into_iter()
+        .filter_map(|r| r.get_number(&model.sarzak).map(|n| (n, r)))
+        .collect()
+}
+
+/// Flatten a relationship into named properties
+///
+/// This is what makes the comparison easy: both sides of the relationship, and
+/// everything about them, get a name.
+fn relationship_properties(rel: &Relationship, model: &SarzakModel) -> BTreeMap<String, String> {
+    let store = &model.sarzak;
+    let mut props = BTreeMap::new();
+    let mut put = |k: &str, v: String| {
+        props.insert(k.to_owned(), v);
+    };
+
+    match rel {
+        Relationship::Binary(id) => {
+            put("kind", "binary".to_owned());
+            if let Some(binary) = store.exhume_binary(id) {
+                if let Some(r) = store.exhume_referrer(&binary.from) {
+                    put("referrer", object_name(model, &r.obj_id));
+                    put("referrer cardinality", format!("{:?}", r.cardinality));
+                    put("referrer conditionality", format!("{:?}", r.conditionality));
+                    put("referrer description", r.description.clone());
+                    put(
+                        "referential attribute",
+                        r.referential_attribute.inner().clone(),
+                    );
+                }
+                if let Some(r) = store.exhume_referent(&binary.to) {
+                    put("referent", object_name(model, &r.obj_id));
+                    put("referent cardinality", format!("{:?}", r.cardinality));
+                    put("referent conditionality", format!("{:?}", r.conditionality));
+                    put("referent description", r.description.clone());
+                }
+            }
+        }
+        Relationship::Isa(id) => {
+            put("kind", "isa".to_owned());
+            if let Some(isa) = store.exhume_isa(id) {
+                if let Some(s) = store.exhume_supertype(&isa.supertype) {
+                    put("supertype", object_name(model, &s.obj_id));
+                }
+                let mut subtypes: Vec<String> = isa
+                    .subtypes
+                    .iter()
+                    .filter_map(|s| store.exhume_subtype(s))
+                    .map(|s| object_name(model, &s.obj_id))
+                    .collect();
+                subtypes.sort();
+                put("subtypes", subtypes.join(", "));
+            }
+        }
+        Relationship::Associative(id) => {
+            put("kind", "associative".to_owned());
+            if let Some(assoc) = store.exhume_associative(id) {
+                if let Some(r) = store.exhume_associative_referrer(&assoc.from) {
+                    put("from", object_name(model, &r.obj_id));
+                    put("from cardinality", format!("{:?}", r.cardinality));
+                    put(
+                        "one referential attribute",
+                        r.one_referential_attribute.inner().clone(),
+                    );
+                    put(
+                        "other referential attribute",
+                        r.other_referential_attribute.inner().clone(),
+                    );
+                }
+                for (side, id) in [("one", &assoc.one), ("other", &assoc.other)] {
+                    if let Some(r) = store.exhume_associative_referent(id) {
+                        put(side, object_name(model, &r.obj_id));
+                        put(
+                            &format!("{} cardinality", side),
+                            format!("{:?}", r.cardinality),
+                        );
+                        put(
+                            &format!("{} conditionality", side),
+                            format!("{:?}", r.conditionality),
+                        );
+                        put(&format!("{} description", side), r.description.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    props
+}
+
+fn object_name(model: &SarzakModel, id: &Uuid) -> String {
+    model
+        .sarzak
+        .exhume_object(id)
+        .map(|o| o.name.inner().clone())
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn compare_properties(
+    element: &Element,
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+    changes: &mut Vec<Change>,
+) {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    for key in keys {
+        let o = old.get(key).map(|s| s.as_str()).unwrap_or(NONE);
+        let n = new.get(key).map(|s| s.as_str()).unwrap_or(NONE);
+        if o != n {
+            changes.push(Change::Modified {
+                element: element.clone(),
+                property: key.clone(),
+                old: o.to_owned(),
+                new: n.to_owned(),
+            });
+        }
+    }
+}
+
+/// Line up the old things with the new things
+///
+/// First by id, and then with `same` for the leftovers. The result follows the
+/// order of `new`, with removals from `old` first.
+fn pair_up<'a, T, I, S>(
+    old: &'a [T],
+    new: &'a [T],
+    id: I,
+    same: S,
+) -> Vec<(Option<&'a T>, Option<&'a T>)>
+where
+    I: Fn(&T) -> Uuid,
+    S: Fn(&T, &T) -> bool,
+{
+    let mut taken = HashSet::new();
+    let mut matches: Vec<Option<usize>> = old
+        .iter()
+        .map(|o| {
+            let found = new.iter().position(|n| id(n) == id(o));
+            if let Some(i) = found {
+                taken.insert(i);
+            }
+            found
+        })
+        .collect();
+
+    for (o, m) in old.iter().zip(matches.iter_mut()) {
+        if m.is_none() {
+            *m = new
+                .iter()
+                .enumerate()
+                .position(|(i, n)| !taken.contains(&i) && same(o, n));
+            if let Some(i) = m {
+                taken.insert(*i);
+            }
+        }
+    }
+
+    let mut result: Vec<(Option<&T>, Option<&T>)> = old
+        .iter()
+        .zip(matches.iter())
+        .filter(|(_, m)| m.is_none())
+        .map(|(o, _)| (Some(o), None))
+        .collect();
+
+    for (i, n) in new.iter().enumerate() {
+        let o = matches.iter().position(|m| *m == Some(i)).map(|j| &old[j]);
+        result.push((o, Some(n)));
+    }
+
+    result
+}
@@ -0,0 +1,647 @@
+//! Diagram Export
+//!
+//! Screenshots of Cuckoo don't belong in a README. This turns the sarzak half of a
+//! [`SarzakModel`] into text that a diagram tool can render: Graphviz DOT, Mermaid
+//! class diagrams, and PlantUML.
+//!
+//! All three show the same things:
+//!  * objects, with their attributes and types
+//!  * binary relationships, with the multiplicity and phrase at each end
+//!  * isa hierarchies, drawn as generalizations
+//!  * associative relationships, with the link class hanging off of the association
+//!
+//! Layout is the renderer's problem. What we guarantee is that the output is
+//! deterministic, so that checking it in doesn't make noise.
+//!
+//! If you'd rather have the picture that's in Cuckoo, with everything where the
+//! modeler put it, [`Format::Svg`] draws that, by way of [`crate::drawing::to_svg`].
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    fmt::Write,
+    str::FromStr,
+};
+
+use heck::ToUpperCamelCase;
+use uuid::Uuid;
+
+use crate::drawing::to_svg;
+use crate::sarzak::{Cardinality, Conditionality, Object, Relationship, SarzakModel, Type};
+
+/// The kinds of diagrams that we know how to write
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    Mermaid,
+    PlantUml,
+    Svg,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            "plantuml" | "puml" => Ok(Self::PlantUml),
+            "svg" => Ok(Self::Svg),
+            _ => Err(format!(
+                "unknown diagram format `{}`, expected one of dot, mermaid, plantuml or svg",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dot => write!(f, "dot"),
+            Self::Mermaid => write!(f, "mermaid"),
+            Self::PlantUml => write!(f, "plantuml"),
+            Self::Svg => write!(f, "svg"),
+        }
+    }
+}
+
+/// Export a model in the given format
+pub fn export_model(model: &SarzakModel, format: Format) -> String {
+    match format {
+        Format::Dot => to_dot(model),
+        Format::Mermaid => to_mermaid(model),
+        Format::PlantUml => to_plantuml(model),
+        Format::Svg => to_svg(model),
+    }
+}
+
+/// An object, as far as a diagram cares
+struct Class {
+    name: String,
+    /// Something safe to use as an identifier in Mermaid and PlantUML
+    ident: String,
+    /// (name, type), with `id` first and the rest sorted
+    attributes: Vec<(String, String)>,
+}
+
+/// One end of a line between two classes
+struct End {
+    class: String,
+    multiplicity: &'static str,
+    phrase: String,
+}
+
+enum Edge {
+    Binary {
+        number: u16,
+        referrer: End,
+        referent: End,
+    },
+    Isa {
+        number: u16,
+        supertype: String,
+        subtypes: Vec<String>,
+    },
+    Associative {
+        number: u16,
+        link: String,
+        one: End,
+        other: End,
+    },
+}
+
+/// Everything we need to draw, pulled out of the store and sorted
+///
+/// The classes and edges refer to each other by the class `ident`.
+struct Diagram {
+    title: String,
+    classes: Vec<Class>,
+    edges: Vec<Edge>,
+}
+
+impl Diagram {
+    fn new(model: &SarzakModel) -> Self {
+        let store = &model.sarzak;
+        let idents = idents(store.objects());
+
+        let mut classes: Vec<Class> = store
+            .objects()
+            .map(|object| {
+                let mut attributes: Vec<(String, String)> = object
+                    .attributes
+                    .iter()
+                    .filter_map(|(name, id)| store.exhume_attribute(id).map(|a| (name, a)))
+                    .map(|(name, attr)| (name.clone(), type_name(&attr.attr_t, model)))
+                    .collect();
+                attributes.sort_by(|a, b| (a.0 != "id", &a.0).cmp(&(b.0 != "id", &b.0)));
+
+                Class {
+                    name: object.name.inner().clone(),
+                    ident: idents[&object.id].clone(),
+                    attributes,
+                }
+            })
+            .collect();
+        classes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let class = |id: &Uuid| match idents.get(id) {
+            Some(ident) => ident.clone(),
+            None => ident(&id.to_string()),
+        };
+
+        let mut edges = Vec::new();
+        for rel in store.sorted_relationships() {
+            match rel {
+                Relationship::Binary(id) => {
+                    let binary = store.exhume_binary(id);
+                    let referrer = binary.and_then(|b| store.exhume_referrer(&b.from));
+                    let referent = binary.and_then(|b| store.exhume_referent(&b.to));
+                    if let (Some(binary), Some(referrer), Some(referent)) =
+                        (binary, referrer, referent)
+                    {
+                        edges.push(Edge::Binary {
+                            number: binary.number,
+                            referrer: End {
+                                class: class(&referrer.obj_id),
+                                multiplicity: multiplicity(
+                                    &referrer.cardinality,
+                                    &referrer.conditionality,
+                                ),
+                                phrase: referrer.description.clone(),
+                            },
+                            referent: End {
+                                class: class(&referent.obj_id),
+                                multiplicity: multiplicity(
+                                    &referent.cardinality,
+                                    &referent.conditionality,
+                                ),
+                                phrase: referent.description.clone(),
+                            },
+                        });
+                    }
+                }
+                Relationship::Isa(id) => {
+                    let isa = store.exhume_isa(id);
+                    let supertype = isa.and_then(|i| store.exhume_supertype(&i.supertype));
+                    if let (Some(isa), Some(supertype)) = (isa, supertype) {
+                        let mut subtypes: Vec<String> = isa
+                            .subtypes
+                            .iter()
+                            .filter_map(|s| store.exhume_subtype(s))
+                            .map(|s| class(&s.obj_id))
+                            .collect();
+                        subtypes.sort();
+
+                        edges.push(Edge::Isa {
+                            number: isa.number,
+                            supertype: class(&supertype.obj_id),
+                            subtypes,
+                        });
+                    }
+                }
+                Relationship::Associative(id) => {
+                    let assoc = store.exhume_associative(id);
+                    let from = assoc.and_then(|a| store.exhume_associative_referrer(&a.from));
+                    let one = assoc.and_then(|a| store.exhume_associative_referent(&a.one));
+                    let other = assoc.and_then(|a| store.exhume_associative_referent(&a.other));
+                    if let (Some(assoc), Some(from), Some(one), Some(other)) =
+                        (assoc, from, one, other)
+                    {
+                        edges.push(Edge::Associative {
+                            number: assoc.number,
+                            link: class(&from.obj_id),
+                            one: End {
+                                class: class(&one.obj_id),
+                                multiplicity: multiplicity(&one.cardinality, &one.conditionality),
+                                phrase: one.description.clone(),
+                            },
+                            other: End {
+                                class: class(&other.obj_id),
+                                multiplicity: multiplicity(
+                                    &other.cardinality,
+                                    &other.conditionality,
+                                ),
+                                phrase: other.description.clone(),
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        Diagram {
+            title: model.domain.clone(),
+            classes,
+            edges,
+        }
+    }
+}
+
+/// Graphviz DOT
+///
+/// Objects are records, with the name on top and the attributes underneath.
+/// Associative relationships get a little point in the middle of the association
+/// so that there's something to hang the link class from.
+pub fn to_dot(model: &SarzakModel) -> String {
+    let diagram = Diagram::new(model);
+    let mut out = String::new();
+
+    // Writing to a String doesn't fail, hence all of the `let _`s.
+    let _ = writeln!(out, "digraph \"{}\" {{", dot_escape(&diagram.title));
+    let _ = writeln!(out, "    label=\"{}\";", dot_escape(&diagram.title));
+    let _ = writeln!(out, "    node [shape=record, fontname=\"Helvetica\"];");
+    let _ = writeln!(out, "    edge [fontname=\"Helvetica\", fontsize=10];");
+
+    for class in &diagram.classes {
+        let attributes: String = class
+            .attributes
+            .iter()
+            .map(|(name, ty)| format!("{}: {}\\l", record_escape(name), record_escape(ty)))
+            .collect();
+        let _ = writeln!(
+            out,
+            "    {} [label=\"{{{}|{}}}\"];",
+            class.ident,
+            record_escape(&class.name),
+            attributes
+        );
+    }
+
+    for edge in &diagram.edges {
+        match edge {
+            Edge::Binary {
+                number,
+                referrer,
+                referent,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "    {} -> {} [label=\"R{}\", taillabel=\"{}\", headlabel=\"{}\"];",
+                    referrer.class,
+                    referent.class,
+                    number,
+                    dot_end(referrer),
+                    dot_end(referent)
+                );
+            }
+            Edge::Isa {
+                number,
+                supertype,
+                subtypes,
+            } => {
+                for subtype in subtypes {
+                    let _ = writeln!(
+                        out,
+                        "    {} -> {} [label=\"R{}\", arrowhead=empty];",
+                        subtype, supertype, number
+                    );
+                }
+            }
+            Edge::Associative {
+                number,
+                link,
+                one,
+                other,
+            } => {
+                let _ = writeln!(out, "    R{} [shape=point, label=\"\"];", number);
+                let _ = writeln!(
+                    out,
+                    "    {} -> R{} [label=\"R{}\", taillabel=\"{}\", arrowhead=none];",
+                    one.class,
+                    number,
+                    number,
+                    dot_end(one)
+                );
+                let _ = writeln!(
+                    out,
+                    "    R{} -> {} [headlabel=\"{}\", arrowhead=none];",
+                    number,
+                    other.class,
+                    dot_end(other)
+                );
+                let _ = writeln!(
+                    out,
+                    "    R{} -> {} [style=dashed, arrowhead=none];",
+                    number, link
+                );
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid class diagram
+///
+/// Mermaid only has room for a multiplicity at each end of a line, so the phrases
+/// go in the label, referrer to referent, and then back again.
+pub fn to_mermaid(model: &SarzakModel) -> String {
+    let diagram = Diagram::new(model);
+    let mut out = String::new();
+
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out, "title: {}", yaml_quote(&diagram.title));
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out, "classDiagram");
+
+    for class in &diagram.classes {
+        if class.name != class.ident {
+            let _ = writeln!(
+                out,
+                "    class {}[\"{}\"]",
+                class.ident,
+                mermaid_escape(&class.name)
+            );
+        }
+        let _ = writeln!(out, "    class {} {{", class.ident);
+        for (name, ty) in &class.attributes {
+            let _ = writeln!(out, "        {} {}", ty, name);
+        }
+        let _ = writeln!(out, "    }}");
+    }
+
+    for edge in &diagram.edges {
+        match edge {
+            Edge::Binary {
+                number,
+                referrer,
+                referent,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "    {} \"{}\" --> \"{}\" {} : R{} {} / {}",
+                    referrer.class,
+                    referrer.multiplicity,
+                    referent.multiplicity,
+                    referent.class,
+                    number,
+                    mermaid_escape(&referent.phrase),
+                    mermaid_escape(&referrer.phrase)
+                );
+            }
+            Edge::Isa {
+                number,
+                supertype,
+                subtypes,
+            } => {
+                for subtype in subtypes {
+                    let _ = writeln!(out, "    {} <|-- {} : R{}", supertype, subtype, number);
+                }
+            }
+            Edge::Associative {
+                number,
+                link,
+                one,
+                other,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "    {} \"{}\" -- \"{}\" {} : R{} {} / {}",
+                    one.class,
+                    one.multiplicity,
+                    other.multiplicity,
+                    other.class,
+                    number,
+                    mermaid_escape(&other.phrase),
+                    mermaid_escape(&one.phrase)
+                );
+                // No association classes in Mermaid, so we make do with dotted lines.
+                for end in [one, other] {
+                    let _ = writeln!(out, "    {} .. {} : R{}", link, end.class, number);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// PlantUML class diagram
+///
+/// PlantUML lets us put the phrase next to the multiplicity, and it knows what an
+/// association class is.
+pub fn to_plantuml(model: &SarzakModel) -> String {
+    let diagram = Diagram::new(model);
+    let mut out = String::new();
+
+    let _ = writeln!(out, "@startuml");
+    let _ = writeln!(out, "title {}", plantuml_escape(&diagram.title));
+    let _ = writeln!(out, "hide empty methods");
+    let _ = writeln!(out);
+
+    for class in &diagram.classes {
+        if class.name == class.ident {
+            let _ = writeln!(out, "class {} {{", class.ident);
+        } else {
+            let _ = writeln!(
+                out,
+                "class \"{}\" as {} {{",
+                plantuml_escape(&class.name),
+                class.ident
+            );
+        }
+        for (name, ty) in &class.attributes {
+            let _ = writeln!(out, "    {}: {}", name, ty);
+        }
+        let _ = writeln!(out, "}}");
+    }
+
+    if !diagram.edges.is_empty() {
+        let _ = writeln!(out);
+    }
+
+    for edge in &diagram.edges {
+        match edge {
+            Edge::Binary {
+                number,
+                referrer,
+                referent,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "{} \"{}\" --> \"{}\" {} : R{}",
+                    referrer.class,
+                    plantuml_end(referrer),
+                    plantuml_end(referent),
+                    referent.class,
+                    number
+                );
+            }
+            Edge::Isa {
+                number,
+                supertype,
+                subtypes,
+            } => {
+                for subtype in subtypes {
+                    let _ = writeln!(out, "{} <|-- {} : R{}", supertype, subtype, number);
+                }
+            }
+            Edge::Associative {
+                number,
+                link,
+                one,
+                other,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "{} \"{}\" -- \"{}\" {} : R{}",
+                    one.class,
+                    plantuml_end(one),
+                    plantuml_end(other),
+                    other.class,
+                    number
+                );
+                let _ = writeln!(out, "({}, {}) .. {}", one.class, other.class, link);
+            }
+        }
+    }
+
+    let _ = writeln!(out, "@enduml");
+    out
+}
+
+/// UML multiplicity for one end of a relationship
+pub(crate) fn multiplicity(
+    cardinality: &Cardinality,
+    conditionality: &Conditionality,
+) -> &'static str {
+    match (cardinality, conditionality) {
+        (Cardinality::One, Conditionality::Unconditional) => "1",
+        (Cardinality::One, Conditionality::Conditional) => "0..1",
+        (Cardinality::Many, Conditionality::Unconditional) => "1..*",
+        (Cardinality::Many, Conditionality::Conditional) => "0..*",
+    }
+}
+
+/// Types as the modeler sees them, not as rustc does
+pub(crate) fn type_name(ty: &Type, model: &SarzakModel) -> String {
+    match ty {
+        Type::Uuid => "Uuid".to_owned(),
+        Type::Integer => "Integer".to_owned(),
+        Type::Float => "Float".to_owned(),
+        Type::String => "String".to_owned(),
+        Type::Boolean => "Boolean".to_owned(),
+        Type::ForeignKey(id) => match model.sarzak.exhume_object(id) {
+            Some(o) => format!("&{}", o.name.inner()),
+            None => format!("&{}", id),
+        },
+        Type::Integer8 => "Integer8".to_owned(),
+        Type::Integer16 => "Integer16".to_owned(),
+        Type::Integer32 => "Integer32".to_owned(),
+        Type::Unsigned8 => "Unsigned8".to_owned(),
+        Type::Unsigned16 => "Unsigned16".to_owned(),
+        Type::Unsigned32 => "Unsigned32".to_owned(),
+        Type::Unsigned64 => "Unsigned64".to_owned(),
+        Type::Float32 => "Float32".to_owned(),
+        Type::Timestamp => "Timestamp".to_owned(),
+        Type::Duration => "Duration".to_owned(),
+        Type::Bytes => "Bytes".to_owned(),
+        Type::Optional(ty) => format!("{}?", type_name(ty, model)),
+        Type::Enumeration(e) => e.name.inner().clone(),
+    }
+}
+
+/// Turn an object name into something every format will take as an identifier
+fn ident(name: &str) -> String {
+    let ident: String = name
+        .to_upper_camel_case()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+
+    match ident.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => ident,
+        _ => format!("_{}", ident),
+    }
+}
+
+/// Identifiers for every object, no two the same
+///
+/// Names that only differ in case or spacing, "Foo Bar" and "FooBar" say, come out
+/// of [`ident`] the same. Drawn like that they'd be one class, so each of them gets
+/// it's key letter tacked on.
+fn idents<'a>(objects: impl Iterator<Item = &'a Object>) -> HashMap<Uuid, String> {
+    let mut objects: Vec<&Object> = objects.collect();
+    objects.sort_by(|a, b| (a.name.inner(), a.id).cmp(&(b.name.inner(), b.id)));
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for object in &objects {
+        *counts.entry(ident(object.name.inner())).or_default() += 1;
+    }
+
+    let mut taken = HashSet::new();
+    let mut idents = HashMap::new();
+    for object in objects {
+        let mut base = ident(object.name.inner());
+        if counts[&base] > 1 {
+            let key_letter: String = object
+                .key_letter
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            base = format!("{}_{}", base, key_letter);
+        }
+
+        // Key letters ought to be unique as well, but we don't count on it.
+        let mut ident = base.clone();
+        let mut n = 2;
+        while !taken.insert(ident.clone()) {
+            ident = format!("{}_{}", base, n);
+            n += 1;
+        }
+        idents.insert(object.id, ident);
+    }
+
+    idents
+}
+
+fn dot_end(end: &End) -> String {
+    format!("{}\\n{}", dot_escape(&end.phrase), end.multiplicity)
+}
+
+fn plantuml_end(end: &End) -> String {
+    format!("{}\\n{}", plantuml_escape(&end.phrase), end.multiplicity)
+}
+
+/// PlantUML has no way to escape a `"`, and a newline ends whatever it's in
+fn plantuml_escape(s: &str) -> String {
+    s.replace('"', "'").replace(['\r', '\n'], " ")
+}
+
+/// A double quoted YAML string, for the Mermaid front matter
+fn yaml_quote(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace(['\r', '\n'], " ")
+    )
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Mermaid labels run to the end of the line, and don't care for `:` or `"`
+///
+/// It does know entity codes though, so that's what they become. A `#` has to
+/// go first, or we'd be escaping our own escapes.
+fn mermaid_escape(s: &str) -> String {
+    s.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace(':', "#58;")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Records are fussier than plain labels
+fn record_escape(s: &str) -> String {
+    let mut out = String::new();
+    for c in dot_escape(s).chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
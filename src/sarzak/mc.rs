@@ -23,12 +23,16 @@ pub enum ModelCompilerError {
     },
     #[snafu(display("Compiler Error: {}", description))]
     CompilerError { description: String },
+    /// Generated code that doesn't match what's on disk, found in check mode
+    #[snafu(display("Stale Output: {}", description))]
+    StaleOutput { description: String },
 }
 
 impl From<Error> for ModelCompilerError {
     fn from(error: Error) -> Self {
         match error.0 {
             CodeGenError::LoadCuckooModel { path, source } => Self::IOError { path, source },
+            CodeGenError::LoadTextModel { path, source } => Self::IOError { path, source },
             CodeGenError::FileWrite { path, source } => Self::IOError { path, source },
             CodeGenError::FileCreate { path, source } => Self::IOError { path, source },
             CodeGenError::ContextNoPath => Self::CompilerError {
@@ -43,11 +47,41 @@ impl From<Error> for ModelCompilerError {
             CodeGenError::SerdeJsonBombed { source } => Self::CompilerError {
                 description: format!("Serde failed: {}", source),
             },
-            CodeGenError::SpawnRustfmt { source } => Self::CompilerError {
-                description: source.to_string(),
+            e @ CodeGenError::StaleOutput { .. } | e @ CodeGenError::StaleOutputs { .. } => {
+                Self::StaleOutput {
+                    description: e.to_string(),
+                }
+            }
+            e @ CodeGenError::DisplacedCode { .. }
+            | e @ CodeGenError::MismatchedBlock { .. }
+            | e @ CodeGenError::UnclosedBlock { .. }
+            | e @ CodeGenError::SymbolCollision { .. }
+            | e @ CodeGenError::PoppedGlobalScope
+            | e @ CodeGenError::NewerOutput { .. }
+            | e @ CodeGenError::SpawnFormatter { .. }
+            | e @ CodeGenError::Format { .. }
+            | e @ CodeGenError::BadFormatter { .. } => Self::CompilerError {
+                description: e.to_string(),
             },
-            CodeGenError::RustFmt { exit_code } => Self::CompilerError {
-                description: format!("RustFmt failed with exit {:?}", exit_code),
+            e @ CodeGenError::InvalidModel { .. } | e @ CodeGenError::NoExampleValue { .. } => {
+                Self::ModelError {
+                    description: e.to_string(),
+                }
+            }
+            e @ CodeGenError::BadCuckooModel { .. }
+            | e @ CodeGenError::ParseCuckooModel { .. }
+            | e @ CodeGenError::EmptyPaperIds
+            | e @ CodeGenError::MissingPaper { .. }
+            | e @ CodeGenError::MissingSchemaKey { .. }
+            | e @ CodeGenError::MissingEntity { .. }
+            | e @ CodeGenError::WrongEntityKind { .. }
+            | e @ CodeGenError::BadModelVersion { .. }
+            | e @ CodeGenError::NewerModel { .. }
+            | e @ CodeGenError::Migration { .. }
+            | e @ CodeGenError::MigrationPath { .. }
+            | e @ CodeGenError::BadTextModel { .. }
+            | e @ CodeGenError::ParseTextModel { .. } => Self::ModelError {
+                description: e.to_string(),
             },
         }
     }
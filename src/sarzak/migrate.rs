@@ -0,0 +1,201 @@
+//! Model Migrations
+//!
+//! [`SarzakModel`]s are stamped with the version of nut that wrote them. As the
+//! stores change shape, older files stop deserializing. Rather than leaving our
+//! archived models to rot, each change to the serialized layout registers a
+//! [`Migration`] that knows how to bring the JSON up to date. When reading a model
+//! we look at the version, run every step newer than that, in order, and only then
+//! hand it to serde.
+//!
+//! Models written by a newer nut are refused outright. We have no idea what they
+//! look like.
+//!
+//! [`SarzakModel`]: crate::sarzak::SarzakModel
+use std::{fmt, str::FromStr};
+
+use serde_json::Value;
+
+use crate::codegen::{CodeGenError, Error, Result};
+use crate::sarzak::VERSION;
+
+/// The version of nut that serialized a model
+///
+/// Just enough semver to order things. Anything after a `-` or `+` is ignored.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ModelVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl ModelVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// The version of this nut.
+    pub fn current() -> Self {
+        VERSION.parse().expect("our own version is valid")
+    }
+}
+
+impl FromStr for ModelVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || {
+            Error(CodeGenError::BadModelVersion {
+                version: s.to_owned(),
+            })
+        };
+
+        let core = s.split(['-', '+']).next().unwrap_or_default();
+        let parts = core
+            .split('.')
+            .map(|p| p.parse::<u64>().map_err(|_| bad()))
+            .collect::<Result<Vec<_>>>()?;
+
+        match parts[..] {
+            [major, minor, patch] => Ok(Self::new(major, minor, patch)),
+            _ => Err(bad()),
+        }
+    }
+}
+
+impl fmt::Display for ModelVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A single upgrade step
+///
+/// `version` is the version of nut that introduced the change. Models older than
+/// that get `upgrade` run on them.
+pub struct Migration {
+    pub version: ModelVersion,
+    pub description: &'static str,
+    pub upgrade: fn(&mut Value) -> Result<()>,
+}
+
+/// Every serialization change since 1.0.0 goes here, oldest first.
+///
+/// 1.0.0 is where we started stamping models, and it's the only release so far.
+/// Nothing it wrote needs changing: what's been added since, identifiers on
+/// attributes and enumerations as types, has a serde default, and no field in the
+/// `ObjectStore` or the `DrawingObjectStore` has been renamed. So the list is empty.
+/// `models/archive` holds models as 1.0.0 wrote them, and `test_migrate` makes
+/// sure that they still load.
+static MIGRATIONS: &[Migration] = &[];
+
+/// The registered migrations
+pub fn migrations() -> &'static [Migration] {
+    MIGRATIONS
+}
+
+/// Bring a serialized model up to the current version
+///
+/// Returns the version that the model was written with.
+pub fn migrate(model: &mut Value) -> Result<ModelVersion> {
+    migrate_with(model, migrations(), ModelVersion::current())
+}
+
+/// Bring a serialized model up to `current`, using `migrations`
+///
+/// This is [`migrate`] with the knobs exposed. The steps must be in version order.
+pub fn migrate_with(
+    model: &mut Value,
+    migrations: &[Migration],
+    current: ModelVersion,
+) -> Result<ModelVersion> {
+    let found: ModelVersion = match model.get("version").and_then(|v| v.as_str()) {
+        Some(v) => v.parse()?,
+        None => {
+            return Err(Error(CodeGenError::BadModelVersion {
+                version: "<missing>".to_owned(),
+            }))
+        }
+    };
+
+    if found > current {
+        return Err(Error(CodeGenError::NewerModel {
+            found: found.to_string(),
+            supported: current.to_string(),
+        }));
+    }
+
+    for step in migrations
+        .iter()
+        .filter(|m| m.version > found && m.version <= current)
+    {
+        log::debug!(
+            "migrating model from {} to {}: {}",
+            found,
+            step.version,
+            step.description
+        );
+        (step.upgrade)(model).map_err(|e| {
+            Error(CodeGenError::Migration {
+                version: step.version.to_string(),
+                description: step.description.to_owned(),
+                source: Box::new(e),
+            })
+        })?;
+    }
+
+    model["version"] = Value::String(current.to_string());
+
+    Ok(found)
+}
+
+/// Rename a field
+///
+/// `path` leads from the root of the model to the object containing the field.
+/// A path element of `"*"` visits every value in a map, or every element of an
+/// array, which is what you want for the insides of an `ObjectStore`. For example,
+/// renaming `key_letter` on every object looks like this:
+///
+/// ```ignore
+/// rename_field(model, &["sarzak", "object", "*"], "key_letter", "kl")
+/// ```
+///
+/// It's not an error if the field is already gone, so that a step may be run
+/// over a model that's partially up to date.
+pub fn rename_field(model: &mut Value, path: &[&str], from: &str, to: &str) -> Result<()> {
+    match path.split_first() {
+        None => match model {
+            Value::Object(map) => {
+                if let Some(value) = map.remove(from) {
+                    map.insert(to.to_owned(), value);
+                }
+                Ok(())
+            }
+            _ => Err(not_an_object(from)),
+        },
+        Some((&"*", rest)) => match model {
+            Value::Object(map) => map
+                .values_mut()
+                .try_for_each(|v| rename_field(v, rest, from, to)),
+            Value::Array(vec) => vec
+                .iter_mut()
+                .try_for_each(|v| rename_field(v, rest, from, to)),
+            _ => Err(not_an_object(from)),
+        },
+        Some((key, rest)) => match model.get_mut(*key) {
+            Some(v) => rename_field(v, rest, from, to),
+            None => Err(Error(CodeGenError::MigrationPath {
+                path: key.to_string(),
+            })),
+        },
+    }
+}
+
+fn not_an_object(field: &str) -> Error {
+    Error(CodeGenError::MigrationPath {
+        path: format!("{} (not in an object)", field),
+    })
+}
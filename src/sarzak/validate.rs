@@ -0,0 +1,619 @@
+//! Model Validation
+//!
+//! A broken model used to show up as an `unwrap()` panic someplace deep inside of
+//! code generation. That's not a lot of fun when all you did was fat finger
+//! something in Cuckoo. This walks a [`SarzakModel`] and collects everything that
+//! looks wrong, naming objects and relationships along the way so that the model
+//! can be fixed without firing up a debugger.
+//!
+//! Run it before code generation. [`SarzakModel::ensure_valid`] does exactly that,
+//! and turns the diagnostics into a [`CodeGenError::InvalidModel`].
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::Path,
+};
+
+use uuid::Uuid;
+
+use crate::codegen::{CodeGenError, Error, Result};
+use crate::sarzak::{Enumeration, Object, Relationship, SarzakModel, Type, AS_IDENT};
+
+/// Something wrong with a model
+///
+/// Everything here has a name attached, and a number, when it's a relationship.
+/// The UUIDs are still included, because sometimes that's all there is to go on.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Diagnostic {
+    /// An [`Object`] refers to an attribute that isn't in the store.
+    DanglingAttribute {
+        object: String,
+        attribute: String,
+        id: Uuid,
+    },
+    /// An attribute is a foreign key to an object that doesn't exist.
+    DanglingForeignKey {
+        object: String,
+        attribute: String,
+        id: Uuid,
+    },
+    /// An [`Object`] thinks it's involved with a relationship that doesn't exist.
+    DanglingRelPointer {
+        object: String,
+        relationship: String,
+        id: Uuid,
+    },
+    /// A [`Relationship`] points at a variant that isn't in the store.
+    DanglingRelationship { id: Uuid },
+    /// One of the sides of a relationship is missing from the store.
+    DanglingRelationshipSide {
+        number: u16,
+        side: &'static str,
+        id: Uuid,
+    },
+    /// One of the sides of a relationship points at an object that doesn't exist.
+    DanglingRelationshipObject {
+        number: u16,
+        side: &'static str,
+        id: Uuid,
+    },
+    /// More than one relationship has the same number.
+    DuplicateRelationshipNumber { number: u16, count: usize },
+    /// More than one object has the same key letters.
+    DuplicateKeyLetter {
+        key_letter: String,
+        objects: Vec<String>,
+    },
+    /// A referential attribute renders to the same identifier as a real attribute.
+    ReferentialAttributeCollision {
+        object: String,
+        attribute: String,
+        number: u16,
+    },
+    /// An attribute is an enumeration without any variants.
+    EmptyEnumeration {
+        object: String,
+        attribute: String,
+        enumeration: String,
+    },
+    /// Attributes don't agree on the variants of an enumeration with the same name.
+    ConflictingEnumeration {
+        enumeration: String,
+        objects: Vec<String>,
+    },
+    /// The configuration marks something as imported that isn't in the model.
+    UnresolvableImport { id: Uuid, domain: String },
+    /// An imported object could not be found in the domain it's imported from.
+    ImportNotFound {
+        object: String,
+        domain: String,
+        model: String,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DanglingAttribute {
+                object,
+                attribute,
+                id,
+            } => write!(
+                f,
+                "object `{}`: attribute `{}` ({}) is missing from the store",
+                object, attribute, id
+            ),
+            Self::DanglingForeignKey {
+                object,
+                attribute,
+                id,
+            } => write!(
+                f,
+                "object `{}`: attribute `{}` is a foreign key to missing object {}",
+                object, attribute, id
+            ),
+            Self::DanglingRelPointer {
+                object,
+                relationship,
+                id,
+            } => write!(
+                f,
+                "object `{}`: relationship {} ({}) is missing from the store",
+                object, relationship, id
+            ),
+            Self::DanglingRelationship { id } => {
+                write!(f, "relationship {} is missing from the store", id)
+            }
+            Self::DanglingRelationshipSide { number, side, id } => write!(
+                f,
+                "R{}: {} side ({}) is missing from the store",
+                number, side, id
+            ),
+            Self::DanglingRelationshipObject { number, side, id } => write!(
+                f,
+                "R{}: {} side points at missing object {}",
+                number, side, id
+            ),
+            Self::DuplicateRelationshipNumber { number, count } => {
+                write!(f, "R{} is used by {} relationships", number, count)
+            }
+            Self::DuplicateKeyLetter {
+                key_letter,
+                objects,
+            } => write!(
+                f,
+                "key letter `{}` is shared by objects {}",
+                key_letter,
+                objects
+                    .iter()
+                    .map(|o| format!("`{}`", o))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::ReferentialAttributeCollision {
+                object,
+                attribute,
+                number,
+            } => write!(
+                f,
+                "object `{}`: referential attribute `{}` from R{} collides with an attribute",
+                object, attribute, number
+            ),
+            Self::EmptyEnumeration {
+                object,
+                attribute,
+                enumeration,
+            } => write!(
+                f,
+                "object `{}`: attribute `{}` is enumeration `{}`, which has no variants",
+                object, attribute, enumeration
+            ),
+            Self::ConflictingEnumeration {
+                enumeration,
+                objects,
+            } => write!(
+                f,
+                "enumeration `{}` has different variants in objects {}",
+                enumeration,
+                objects
+                    .iter()
+                    .map(|o| format!("`{}`", o))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::UnresolvableImport { id, domain } => write!(
+                f,
+                "imported object {} from domain `{}` is not in the model",
+                id, domain
+            ),
+            Self::ImportNotFound {
+                object,
+                domain,
+                model,
+            } => write!(
+                f,
+                "imported object `{}` was not found in domain `{}` ({})",
+                object, domain, model
+            ),
+        }
+    }
+}
+
+impl SarzakModel {
+    /// Validate the model
+    ///
+    /// See [`validate_model`].
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate_model(self)
+    }
+
+    /// Validate the model, failing if there are any diagnostics
+    ///
+    /// This is meant to be called before code generation.
+    pub fn ensure_valid(&self) -> Result<()> {
+        let diagnostics = self.validate();
+
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(Error(CodeGenError::InvalidModel {
+                domain: self.domain.clone(),
+                diagnostics,
+            }))
+        }
+    }
+}
+
+/// Validate a [`SarzakModel`]
+///
+/// The diagnostics are returned in a stable order. An empty `Vec` means that
+/// we didn't find anything to complain about.
+pub fn validate_model(model: &SarzakModel) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut objects: Vec<&Object> = model.sarzak.objects().collect();
+    objects.sort_by(|a, b| a.name.cmp(&b.name));
+
+    check_objects(model, &objects, &mut diagnostics);
+    check_relationships(model, &mut diagnostics);
+    check_key_letters(&objects, &mut diagnostics);
+    check_enumerations(model, &objects, &mut diagnostics);
+    check_imports(model, &mut diagnostics);
+
+    diagnostics
+}
+
+/// Validate imported objects against the models that they are imported from
+///
+/// `models_dir` is the directory that the [`ImportedObject`][io] `model_path` is
+/// relative to. Code generation uses `models` in the current directory.
+///
+/// [io]: crate::codegen::ImportedObject
+pub fn validate_imports<P: AsRef<Path>>(model: &SarzakModel, models_dir: P) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut imports = model.config.get_imported_objects();
+    imports.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (id, io) in imports {
+        let object = match model.sarzak.exhume_object(id) {
+            Some(o) => o,
+            // This is reported by `validate_model`.
+            None => continue,
+        };
+
+        let mut path = models_dir.as_ref().to_path_buf();
+        path.push(&io.model_path);
+        path.push(&io.domain);
+        path.set_extension("json");
+
+        let found = match SarzakModel::load_cuckoo_model(&path) {
+            Ok(imported) => imported.sarzak.exhume_object(id).is_some(),
+            Err(_) => false,
+        };
+
+        if !found {
+            diagnostics.push(Diagnostic::ImportNotFound {
+                object: object.name.inner().clone(),
+                domain: io.domain.clone(),
+                model: path.display().to_string(),
+            });
+        }
+    }
+
+    diagnostics
+}
+
+fn check_objects(model: &SarzakModel, objects: &[&Object], diagnostics: &mut Vec<Diagnostic>) {
+    let store = &model.sarzak;
+
+    for object in objects {
+        let name = object.name.inner();
+
+        let mut attrs: Vec<(&String, &Uuid)> = object.attributes.iter().collect();
+        attrs.sort();
+        for (attr_name, id) in attrs {
+            match store.exhume_attribute(id) {
+                Some(attr) => {
+                    if let Type::ForeignKey(fk) = &attr.attr_t {
+                        if store.exhume_object(fk).is_none() {
+                            diagnostics.push(Diagnostic::DanglingForeignKey {
+                                object: name.clone(),
+                                attribute: attr_name.clone(),
+                                id: *fk,
+                            });
+                        }
+                    }
+                    if let Some(e) = attr.attr_t.enumeration() {
+                        if e.variants.is_empty() {
+                            diagnostics.push(Diagnostic::EmptyEnumeration {
+                                object: name.clone(),
+                                attribute: attr_name.clone(),
+                                enumeration: e.name.inner().clone(),
+                            });
+                        }
+                    }
+                }
+                None => diagnostics.push(Diagnostic::DanglingAttribute {
+                    object: name.clone(),
+                    attribute: attr_name.clone(),
+                    id: *id,
+                }),
+            }
+        }
+
+        let mut rels: Vec<_> = object.rels.iter().collect();
+        rels.sort_by(|a, b| a.0.cmp(b.0));
+        for (rel_name, r_ptr) in rels {
+            let exists = store.exhume_binary(&r_ptr.value).is_some()
+                || store.exhume_isa(&r_ptr.value).is_some()
+                || store.exhume_associative(&r_ptr.value).is_some();
+
+            if !exists {
+                diagnostics.push(Diagnostic::DanglingRelPointer {
+                    object: name.clone(),
+                    relationship: rel_name.clone(),
+                    id: r_ptr.value,
+                });
+            }
+        }
+    }
+}
+
+/// Every attribute with an enumeration carries it's own copy, and they all need to match
+fn check_enumerations(model: &SarzakModel, objects: &[&Object], diagnostics: &mut Vec<Diagnostic>) {
+    let mut uses: BTreeMap<&String, Vec<(&String, &Enumeration)>> = BTreeMap::new();
+    for object in objects {
+        for id in object.attributes.values() {
+            if let Some(e) = model
+                .sarzak
+                .exhume_attribute(id)
+                .and_then(|a| a.attr_t.enumeration())
+            {
+                uses.entry(e.name.inner())
+                    .or_default()
+                    .push((object.name.inner(), e));
+            }
+        }
+    }
+
+    for (name, uses) in uses {
+        if uses.iter().any(|(_, e)| e.variants != uses[0].1.variants) {
+            let mut objects: Vec<String> = uses.iter().map(|(o, _)| (*o).clone()).collect();
+            objects.dedup();
+            diagnostics.push(Diagnostic::ConflictingEnumeration {
+                enumeration: name.clone(),
+                objects,
+            });
+        }
+    }
+}
+
+fn check_relationships(model: &SarzakModel, diagnostics: &mut Vec<Diagnostic>) {
+    let store = &model.sarzak;
+
+    let mut numbers: BTreeMap<u16, usize> = BTreeMap::new();
+    let mut relationships: Vec<&Relationship> = store.relationships().collect();
+    relationships.sort();
+
+    let object_exists = |id: &Uuid| store.exhume_object(id).is_some();
+
+    for rel in relationships {
+        match rel {
+            Relationship::Binary(id) => {
+                let binary = match store.exhume_binary(id) {
+                    Some(b) => b,
+                    None => {
+                        diagnostics.push(Diagnostic::DanglingRelationship { id: *id });
+                        continue;
+                    }
+                };
+                *numbers.entry(binary.number).or_default() += 1;
+
+                match store.exhume_referrer(&binary.from) {
+                    Some(referrer) => {
+                        if !object_exists(&referrer.obj_id) {
+                            diagnostics.push(Diagnostic::DanglingRelationshipObject {
+                                number: binary.number,
+                                side: "referrer",
+                                id: referrer.obj_id,
+                            });
+                        } else {
+                            check_referential_attribute(
+                                model,
+                                &referrer.obj_id,
+                                referrer.referential_attribute.render(AS_IDENT),
+                                binary.number,
+                                diagnostics,
+                            );
+                        }
+                    }
+                    None => diagnostics.push(Diagnostic::DanglingRelationshipSide {
+                        number: binary.number,
+                        side: "referrer",
+                        id: binary.from,
+                    }),
+                }
+
+                match store.exhume_referent(&binary.to) {
+                    Some(referent) => {
+                        if !object_exists(&referent.obj_id) {
+                            diagnostics.push(Diagnostic::DanglingRelationshipObject {
+                                number: binary.number,
+                                side: "referent",
+                                id: referent.obj_id,
+                            });
+                        }
+                    }
+                    None => diagnostics.push(Diagnostic::DanglingRelationshipSide {
+                        number: binary.number,
+                        side: "referent",
+                        id: binary.to,
+                    }),
+                }
+            }
+            Relationship::Isa(id) => {
+                let isa = match store.exhume_isa(id) {
+                    Some(i) => i,
+                    None => {
+                        diagnostics.push(Diagnostic::DanglingRelationship { id: *id });
+                        continue;
+                    }
+                };
+                *numbers.entry(isa.number).or_default() += 1;
+
+                match store.exhume_supertype(&isa.supertype) {
+                    Some(sup) => {
+                        if !object_exists(&sup.obj_id) {
+                            diagnostics.push(Diagnostic::DanglingRelationshipObject {
+                                number: isa.number,
+                                side: "supertype",
+                                id: sup.obj_id,
+                            });
+                        }
+                    }
+                    None => diagnostics.push(Diagnostic::DanglingRelationshipSide {
+                        number: isa.number,
+                        side: "supertype",
+                        id: isa.supertype,
+                    }),
+                }
+
+                for sub_id in &isa.subtypes {
+                    match store.exhume_subtype(sub_id) {
+                        Some(sub) => {
+                            if !object_exists(&sub.obj_id) {
+                                diagnostics.push(Diagnostic::DanglingRelationshipObject {
+                                    number: isa.number,
+                                    side: "subtype",
+                                    id: sub.obj_id,
+                                });
+                            }
+                        }
+                        None => diagnostics.push(Diagnostic::DanglingRelationshipSide {
+                            number: isa.number,
+                            side: "subtype",
+                            id: *sub_id,
+                        }),
+                    }
+                }
+            }
+            Relationship::Associative(id) => {
+                let assoc = match store.exhume_associative(id) {
+                    Some(a) => a,
+                    None => {
+                        diagnostics.push(Diagnostic::DanglingRelationship { id: *id });
+                        continue;
+                    }
+                };
+                *numbers.entry(assoc.number).or_default() += 1;
+
+                match store.exhume_associative_referrer(&assoc.from) {
+                    Some(referrer) => {
+                        if !object_exists(&referrer.obj_id) {
+                            diagnostics.push(Diagnostic::DanglingRelationshipObject {
+                                number: assoc.number,
+                                side: "associative referrer",
+                                id: referrer.obj_id,
+                            });
+                        } else {
+                            check_referential_attribute(
+                                model,
+                                &referrer.obj_id,
+                                referrer.one_referential_attribute.render(AS_IDENT),
+                                assoc.number,
+                                diagnostics,
+                            );
+                            check_referential_attribute(
+                                model,
+                                &referrer.obj_id,
+                                referrer.other_referential_attribute.render(AS_IDENT),
+                                assoc.number,
+                                diagnostics,
+                            );
+                        }
+                    }
+                    None => diagnostics.push(Diagnostic::DanglingRelationshipSide {
+                        number: assoc.number,
+                        side: "associative referrer",
+                        id: assoc.from,
+                    }),
+                }
+
+                for (side, side_id) in [("one", &assoc.one), ("other", &assoc.other)] {
+                    match store.exhume_associative_referent(side_id) {
+                        Some(referent) => {
+                            if !object_exists(&referent.obj_id) {
+                                diagnostics.push(Diagnostic::DanglingRelationshipObject {
+                                    number: assoc.number,
+                                    side,
+                                    id: referent.obj_id,
+                                });
+                            }
+                        }
+                        None => diagnostics.push(Diagnostic::DanglingRelationshipSide {
+                            number: assoc.number,
+                            side,
+                            id: *side_id,
+                        }),
+                    }
+                }
+            }
+        }
+    }
+
+    for (number, count) in numbers {
+        if count > 1 {
+            diagnostics.push(Diagnostic::DuplicateRelationshipNumber { number, count });
+        }
+    }
+}
+
+/// Check that a referential attribute doesn't render the same as a real attribute
+///
+/// They both end up as fields on the same struct, so the compiler would be the
+/// one to find it otherwise.
+fn check_referential_attribute(
+    model: &SarzakModel,
+    obj_id: &Uuid,
+    ident: String,
+    number: u16,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // The caller has already checked that the object exists.
+    let object = model.sarzak.exhume_object(obj_id).unwrap();
+
+    let collides = object
+        .attributes
+        .values()
+        .filter_map(|id| model.sarzak.exhume_attribute(id))
+        .any(|attr| attr.render(AS_IDENT) == ident);
+
+    if collides {
+        diagnostics.push(Diagnostic::ReferentialAttributeCollision {
+            object: object.name.inner().clone(),
+            attribute: ident,
+            number,
+        });
+    }
+}
+
+fn check_key_letters(objects: &[&Object], diagnostics: &mut Vec<Diagnostic>) {
+    let mut key_letters: HashMap<&str, Vec<String>> = HashMap::new();
+    for object in objects {
+        // Cuckoo lets you leave these blank. That's not our problem here.
+        if !object.key_letter.is_empty() {
+            key_letters
+                .entry(object.key_letter.as_str())
+                .or_default()
+                .push(object.name.inner().clone());
+        }
+    }
+
+    let mut duplicates: Vec<(&str, Vec<String>)> = key_letters
+        .into_iter()
+        .filter(|(_, objects)| objects.len() > 1)
+        .collect();
+    duplicates.sort();
+
+    for (key_letter, objects) in duplicates {
+        diagnostics.push(Diagnostic::DuplicateKeyLetter {
+            key_letter: key_letter.to_owned(),
+            objects,
+        });
+    }
+}
+
+fn check_imports(model: &SarzakModel, diagnostics: &mut Vec<Diagnostic>) {
+    let mut imports = model.config.get_imported_objects();
+    imports.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (id, io) in imports {
+        if model.sarzak.exhume_object(id).is_none() {
+            diagnostics.push(Diagnostic::UnresolvableImport {
+                id: *id,
+                domain: io.domain.clone(),
+            });
+        }
+    }
+}
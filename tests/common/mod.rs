@@ -0,0 +1,56 @@
+//! Things the tests share
+//!
+//! Mostly, generating all three files for a model and reading them back in.
+#![allow(dead_code)]
+use std::{fs, path::Path};
+
+use nut::codegen::SarzakModel;
+use nut::domain::{generate_macros, generate_store, generate_types, GenerateOptions};
+
+/// The generated code for a model
+#[derive(Debug, PartialEq)]
+pub struct Generated {
+    pub types: String,
+    pub store: String,
+    pub macros: String,
+}
+
+/// Doc tests and `new`, the way the test models are generated
+pub fn options() -> GenerateOptions {
+    GenerateOptions {
+        doc_tests: true,
+        new_impl: true,
+        ..Default::default()
+    }
+}
+
+/// Generate types, store, and macros into a scratch directory
+pub fn generate(model: &SarzakModel, package: &str) -> Generated {
+    let dir = tempfile::tempdir().unwrap();
+
+    generate_in(model, package, dir.path(), &options())
+}
+
+/// Generate types, store, and macros into `dir`
+///
+/// Anything already in `dir` is regenerated, just like the real thing.
+pub fn generate_in(
+    model: &SarzakModel,
+    package: &str,
+    dir: &Path,
+    options: &GenerateOptions,
+) -> Generated {
+    let types = dir.join("types.rs");
+    let store = dir.join("store.rs");
+    let macros = dir.join("macros.rs");
+
+    generate_types(model, &types, package, options).unwrap();
+    generate_store(model, &store, package, options).unwrap();
+    generate_macros(model, &macros, package, options).unwrap();
+
+    Generated {
+        types: fs::read_to_string(types).unwrap(),
+        store: fs::read_to_string(store).unwrap(),
+        macros: fs::read_to_string(macros).unwrap(),
+    }
+}
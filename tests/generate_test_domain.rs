@@ -12,7 +12,9 @@ use env_logger;
 use log::debug;
 
 use nut::codegen::SarzakModel;
-use nut::domain::{generate_macros, generate_store, generate_types};
+use nut::domain::{generate_macros, generate_store, generate_types, GenerateOptions};
+
+mod common;
 
 const TYPES: &str = "types";
 const MACROS: &str = "macros";
@@ -20,6 +22,7 @@ const STORE: &str = "store";
 
 const RS_EXT: &str = "rs";
 const JSON_EXT: &str = "json";
+const TEXT_EXT: &str = "nut";
 
 #[test]
 fn test_as_much_as_i_can() -> Result<()> {
@@ -32,8 +35,8 @@ fn test_as_much_as_i_can() -> Result<()> {
     for entry in fs::read_dir(&model_dir)? {
         let path = &entry?.path();
         if let Some(ext) = path.extension() {
-            if ext == "json" {
-                generate_domain_code(&package_root, &path, false, false, true, true, false)?;
+            if ext == JSON_EXT || ext == TEXT_EXT {
+                generate_domain_code(&package_root, path, false, &common::options())?;
             }
         }
     }
@@ -49,11 +52,8 @@ fn test_as_much_as_i_can() -> Result<()> {
 fn generate_domain_code(
     root: &PathBuf,
     model_file: &PathBuf,
-    meta: bool,
     test_mode: bool,
-    doc_tests: bool,
-    new_impl: bool,
-    extrude_impl: bool,
+    options: &GenerateOptions,
 ) -> Result<()> {
     // Check that the path exists, and that it's a file. From there we just
     // have to trust...
@@ -65,14 +65,11 @@ fn generate_domain_code(
         model_file.is_file(),
         format!("😱 {:?} is not a model file!", model_file)
     );
-    if let Some(extension) = model_file.extension() {
-        anyhow::ensure!(
-            extension == JSON_EXT,
-            format!("😱 {:?} is not a json file!", model_file)
-        );
-    } else {
-        anyhow::bail!(format!("😱 {:?} is not a json file!", model_file));
-    }
+    let text = match model_file.extension() {
+        Some(extension) if extension == JSON_EXT => false,
+        Some(extension) if extension == TEXT_EXT => true,
+        _ => anyhow::bail!(format!("😱 {:?} is not a model file!", model_file)),
+    };
 
     let module = if let Some(stem) = model_file.file_stem() {
         stem
@@ -83,8 +80,12 @@ fn generate_domain_code(
         ));
     };
 
-    let model = SarzakModel::load_cuckoo_model(&model_file)
-        .context(format!("😱 reading model file {}", model_file.display()))?;
+    let model = if text {
+        SarzakModel::load_text_model(model_file)
+    } else {
+        SarzakModel::load_cuckoo_model(model_file)
+    }
+    .context(format!("😱 reading model file {}", model_file.display()))?;
 
     println!(
         "Generating 🧬 code for domain ✨{:?}✨!",
@@ -111,16 +112,7 @@ fn generate_domain_code(
     module_path.set_extension(RS_EXT);
     debug!("Writing 🖍️ {:?}!", module_path);
     if !test_mode {
-        generate_types(
-            &model,
-            &module_path,
-            &package,
-            meta,
-            doc_tests,
-            new_impl,
-            extrude_impl,
-            false,
-        )?;
+        generate_types(&model, &module_path, &package, options)?;
     }
 
     // generate store.rs
@@ -129,7 +121,7 @@ fn generate_domain_code(
     module_path.set_extension(RS_EXT);
     debug!("Writing ✏️ {:?}!", module_path);
     if !test_mode {
-        generate_store(&model, &module_path, &package, meta, doc_tests)?;
+        generate_store(&model, &module_path, &package, options)?;
     }
 
     // generate macros.rs
@@ -138,7 +130,7 @@ fn generate_domain_code(
     module_path.set_extension(RS_EXT);
     debug!("Writing ✒️ {:?}!", module_path);
     if !test_mode {
-        generate_macros(&model, &module_path, &package, meta, doc_tests, false)?;
+        generate_macros(&model, &module_path, &package, options)?;
     }
 
     Ok(())
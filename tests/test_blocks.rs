@@ -0,0 +1,135 @@
+use std::fs;
+
+use nut::codegen::{CachingContext, CodeGenError, Context, Error};
+
+/// Generate a critical block, wrapped around an ignore block, wrapped around
+/// another critical block
+fn emit(path: &std::path::Path, value: u32) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.writeln("//! A file for nesting");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.begin_critical_block("outer").unwrap();
+    context.writeln(format!("pub const OUTER: u32 = {};", value));
+    context.begin_ignore_block().unwrap();
+    context.writeln("/// Have at it");
+    context.begin_critical_block("inner").unwrap();
+    context.writeln(format!("pub const INNER: u32 = {};", value));
+    context.end_critical_block("inner").unwrap();
+    context.end_ignore_block().unwrap();
+    context.writeln(format!("pub const OUTER_TOO: u32 = {};", value));
+    context.end_critical_block("outer").unwrap();
+    context
+}
+
+#[test]
+fn test_nested_blocks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested.rs");
+
+    emit(&path, 1).commit().unwrap();
+
+    // Edits inside of the ignore block stick, even though it's inside of a
+    // critical block, and has a critical block of it's own. The one after it is
+    // still critical.
+    let edited = fs::read_to_string(&path)
+        .unwrap()
+        .replace("/// Have at it", "/// Had at it")
+        .replace("INNER: u32 = 1;", "INNER: u32 = 42;")
+        .replace("OUTER_TOO: u32 = 1;", "OUTER_TOO: u32 = 42;");
+    fs::write(&path, &edited).unwrap();
+
+    let displaced = emit(&path, 1).commit().unwrap();
+    assert_eq!(displaced.len(), 1);
+    assert_eq!(displaced[0].tag, "outer");
+    assert_eq!(displaced[0].text, "pub const OUTER_TOO: u32 = 42;");
+
+    let after = fs::read_to_string(&path).unwrap();
+    assert!(after.contains("/// Had at it"));
+    assert!(after.contains("pub const INNER: u32 = 42;"));
+    assert!(!after.contains("pub const INNER: u32 = 1;"));
+    assert!(after.contains("// pub const OUTER_TOO: u32 = 42; //⚡️"));
+    assert!(after.contains("pub const OUTER_TOO: u32 = 1;"));
+}
+
+#[test]
+fn test_moved_directive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("moved.rs");
+
+    emit(&path, 1).commit().unwrap();
+
+    // Move the inner block's directives over a bit, like they would be if it
+    // used to be emitted inside of something else.
+    let moved = fs::read_to_string(&path).unwrap().replace(
+        "// {\"magic\":\"\",\"kind\":{\"CriticalBlockBegin\":{\"tag\":\"inner\"}}}",
+        "    // {\"magic\":\"\",\"kind\":{\"CriticalBlockBegin\":{\"tag\":\"inner\"}}}",
+    );
+    fs::write(&path, &moved).unwrap();
+
+    // The old one is just gone. It's not user code, so it's not displaced.
+    let displaced = emit(&path, 1).commit().unwrap();
+    assert!(displaced.is_empty());
+
+    let after = fs::read_to_string(&path).unwrap();
+    assert_eq!(after.matches("\"tag\":\"inner\"").count(), 2);
+    assert!(!after.contains("//⚡️"));
+}
+
+#[test]
+fn test_mismatched_blocks() {
+    let mut context = CachingContext::new();
+    context.begin_critical_block("a").unwrap();
+    context.begin_ignore_block().unwrap();
+
+    match context.end_critical_block("a") {
+        Err(Error(e @ CodeGenError::MismatchedBlock { .. })) => assert_eq!(
+            e.to_string(),
+            "😱 unable to close critical block `a`, ignore block is open"
+        ),
+        r => panic!("expected mismatched block, got {:?}", r),
+    }
+
+    context.end_ignore_block().unwrap();
+    assert!(context.end_critical_block("b").is_err());
+    context.end_critical_block("a").unwrap();
+
+    match context.end_ignore_block() {
+        Err(Error(CodeGenError::MismatchedBlock { open, .. })) => assert_eq!(open, "nothing"),
+        r => panic!("expected mismatched block, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_unclosed_block() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("unclosed.rs");
+
+    // Sub-templates can leave a block open for somebody else to close.
+    let mut sub = CachingContext::new();
+    sub.begin_critical_block("sub").unwrap();
+    sub.writeln("pub const SUB: u32 = 1;");
+
+    let mut context = CachingContext::new();
+    context.begin_ignore_block().unwrap();
+    context += sub;
+    context.end_critical_block("sub").unwrap();
+    context.end_ignore_block().unwrap();
+
+    // But somebody has to.
+    let mut context = Context::new(&path, false).unwrap();
+    context.writeln("//! A file that's missing something");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.begin_critical_block("open").unwrap();
+    context.writeln("pub const OPEN: u32 = 1;");
+    match context.commit() {
+        Err(Error(e @ CodeGenError::UnclosedBlock { .. })) => {
+            assert!(e
+                .to_string()
+                .ends_with("ends with critical block `open` still open"))
+        }
+        r => panic!("expected unclosed block, got {:?}", r),
+    }
+    assert!(!path.exists());
+}
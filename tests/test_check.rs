@@ -0,0 +1,95 @@
+use std::fs;
+
+use nut::codegen::{CodeGenError, Context, Error, SarzakModel};
+use nut::domain::{generate_store, GenerateOptions};
+
+fn emit(path: &std::path::Path, value: u32) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.writeln("//! A file for checking");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.writeln(format!("pub const VALUE: u32 = {};", value));
+    context
+}
+
+#[test]
+fn test_check_context() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("check.rs");
+
+    // Nothing there yet, so it's stale.
+    match emit(&path, 42).check() {
+        Err(Error(CodeGenError::StaleOutput { path: p, diff })) => {
+            assert_eq!(p, path);
+            assert!(diff.contains("+pub const VALUE: u32 = 42;"));
+        }
+        r => panic!("expected stale output, got {:?}", r),
+    }
+    assert!(!path.exists());
+
+    emit(&path, 42).commit().unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+
+    // Same thing again is up to date.
+    emit(&path, 42).check().unwrap();
+    emit(&path, 42).finish(true).unwrap();
+
+    // Something different isn't, and the file is left alone.
+    match emit(&path, 43).check() {
+        Err(Error(CodeGenError::StaleOutput { diff, .. })) => {
+            assert!(diff.starts_with(&format!("--- a/{}", path.display())));
+            assert!(diff.contains("\n@@ -"));
+            // Lines outside of critical blocks belong to the user, so the old
+            // one sticks around.
+            assert!(diff.contains("\n pub const VALUE: u32 = 42;\n"));
+            assert!(diff.contains("\n+pub const VALUE: u32 = 43;\n"));
+        }
+        r => panic!("expected stale output, got {:?}", r),
+    }
+    assert_eq!(written, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn test_check_generated() {
+    let model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/one_to_one.json").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("store.rs");
+
+    generate_store(
+        &model,
+        &path,
+        "test_models",
+        &GenerateOptions {
+            doc_tests: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    generate_store(
+        &model,
+        &path,
+        "test_models",
+        &GenerateOptions {
+            doc_tests: true,
+            check: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // The checked in store should be current. The ignored block with the command
+    // line is different, but that's what ignored blocks are for.
+    fs::copy("crates/test_models/src/one_to_one/store.rs", &path).unwrap();
+    generate_store(
+        &model,
+        &path,
+        "test_models",
+        &GenerateOptions {
+            doc_tests: true,
+            check: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
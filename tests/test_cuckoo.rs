@@ -0,0 +1,118 @@
+use std::fs::File;
+
+use nut::codegen::SarzakModel;
+use nut::model::{JSFormat, ReadModel};
+use nut::sarzak::{diff_models, Attribute, AttributeName, Type};
+
+const MODELS: [&str; 5] = [
+    "models/cat_dog.json",
+    "crates/test_models/models/associative.json",
+    "crates/test_models/models/isa_relationship.json",
+    "crates/test_models/models/everything.json",
+    "crates/test_models/models/imported_object.json",
+];
+
+fn load_json(path: &str) -> JSFormat {
+    File::open(path).unwrap().from_json().unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+    for path in MODELS {
+        let model = SarzakModel::load_cuckoo_model(path).unwrap();
+        let again: SarzakModel = JSFormat::from(&model).try_into().unwrap();
+
+        let diff = diff_models(&model, &again);
+        assert!(diff.is_empty(), "{}:\n{}", path, diff);
+
+        assert_eq!(model.id, again.id);
+        assert_eq!(model.domain, again.domain);
+        assert_eq!(model.description, again.description);
+        assert_eq!(model.extents, again.extents);
+        assert_eq!(model.view, again.view);
+    }
+}
+
+#[test]
+fn test_paper() {
+    for path in MODELS {
+        let original = load_json(path);
+        let model = SarzakModel::load_cuckoo_model(path).unwrap();
+        let exported = JSFormat::from(&model);
+
+        let original = original.paper.entities.values().next().unwrap();
+        let exported = exported.paper.entities.values().next().unwrap();
+
+        assert_eq!(exported.domain_ns, original.domain_ns, "{}", path);
+        assert_eq!(
+            serde_json::to_value(&exported.objects).unwrap(),
+            serde_json::to_value(&original.objects).unwrap(),
+            "{}",
+            path
+        );
+        assert_eq!(
+            serde_json::to_value(&exported.relationships).unwrap(),
+            serde_json::to_value(&original.relationships).unwrap(),
+            "{}",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_config() {
+    let path = "crates/test_models/models/imported_object.json";
+    let model = SarzakModel::load_cuckoo_model(path).unwrap();
+    let exported = JSFormat::from(&model);
+
+    // It's all in the object descriptions, so there's nothing for the paper.
+    let paper = exported.paper.entities.values().next().unwrap();
+    assert!(paper.config.is_none());
+
+    let again: SarzakModel = exported.try_into().unwrap();
+    assert_eq!(
+        model.config.get_imported_objects().len(),
+        again.config.get_imported_objects().len()
+    );
+}
+
+#[test]
+fn test_save_modified() {
+    let mut model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+
+    let dog_id = model.sarzak.get_obj("Dog").unwrap().id;
+    let name = Attribute::new("name", Type::String);
+    let name_id = name.id;
+    model.sarzak.inter_attribute(name);
+
+    let dog = model.sarzak.iter_object().find(|(id, _)| **id == dog_id);
+    let mut dog = dog.unwrap().1.clone();
+    dog.name = AttributeName::new("Hound".to_owned());
+    dog.attributes.insert("name".to_owned(), name_id);
+    model.sarzak.inter_object(dog);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hound.json");
+    model.save_cuckoo_model(&path).unwrap();
+
+    let again = SarzakModel::load_cuckoo_model(&path).unwrap();
+    let hound = again.sarzak.get_obj("Hound").unwrap();
+    assert_eq!(hound.id, dog_id);
+    assert!(again.sarzak.get_obj("Dog").is_none());
+
+    let name = again
+        .sarzak
+        .exhume_attribute(hound.attributes.get("name").unwrap())
+        .unwrap();
+    assert_eq!(name.attr_t, Type::String);
+
+    // Still drawn where it was.
+    let before = load_json("models/cat_dog.json");
+    let after = load_json(path.to_str().unwrap());
+    let before = before.paper.entities.values().next().unwrap();
+    let after = after.paper.entities.values().next().unwrap();
+    assert_eq!(
+        serde_json::to_value(&before.objects[&dog_id]).unwrap(),
+        serde_json::to_value(&after.objects[&dog_id]).unwrap()
+    );
+}
@@ -0,0 +1,110 @@
+use uuid::Uuid;
+
+use nut::codegen::{ConfigValue, SarzakModel, SingletonObject};
+use nut::sarzak::diff::{Change, Element};
+use nut::sarzak::{diff_models, AttributeName, Cardinality, Object, Type};
+
+fn modified(element: Element, property: &str, old: &str, new: &str) -> Change {
+    Change::Modified {
+        element,
+        property: property.to_owned(),
+        old: old.to_owned(),
+        new: new.to_owned(),
+    }
+}
+
+#[test]
+fn test_no_changes() {
+    let old = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let new = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+
+    let diff = diff_models(&old, &new);
+    assert!(diff.is_empty(), "{}", diff);
+}
+
+#[test]
+fn test_changes() {
+    let old = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let mut new = old.clone();
+
+    let mut cat = new.sarzak.get_obj("Cat").unwrap().clone();
+    cat.description = "Meow".to_owned();
+    let mut config = ConfigValue::new();
+    config.singleton_object = Some(SingletonObject(true));
+    new.config.insert(cat.id, config);
+    new.sarzak.inter_object(cat);
+
+    let dog = new.sarzak.get_obj("Dog").unwrap().clone();
+    let mut name = new
+        .sarzak
+        .exhume_attribute(dog.attributes.get("name").unwrap())
+        .unwrap()
+        .clone();
+    name.attr_t = Type::Integer;
+    new.sarzak.inter_attribute(name);
+
+    let cow = Object {
+        id: Uuid::new_v4(),
+        key_letter: "COW".to_owned(),
+        name: AttributeName::new("Cow".to_owned()),
+        ..Default::default()
+    };
+    new.sarzak.inter_object(cow);
+
+    let r2 = new.sarzak.get_rel("R2").unwrap().clone();
+    let binary = new.sarzak.exhume_binary(r2.get_id()).unwrap().clone();
+    let mut referrer = new.sarzak.exhume_referrer(&binary.from).unwrap().clone();
+    assert_eq!(referrer.cardinality, Cardinality::One);
+    referrer.cardinality = Cardinality::Many;
+    new.sarzak.inter_referrer(referrer);
+
+    let diff = diff_models(&old, &new);
+    let cat = Element::Object {
+        name: "Cat".to_owned(),
+    };
+    let cow = Element::Object {
+        name: "Cow".to_owned(),
+    };
+    let old_description = old.sarzak.get_obj("Cat").unwrap().description.clone();
+    assert_eq!(
+        diff.changes,
+        vec![
+            modified(
+                cat.clone(),
+                "config",
+                "(none)",
+                r#"{"imported_object":null,"singleton_object":true}"#
+            ),
+            modified(cat, "description", &old_description, "Meow"),
+            Change::Added { element: cow },
+            modified(
+                Element::Attribute {
+                    object: "Dog".to_owned(),
+                    name: "name".to_owned(),
+                },
+                "type",
+                "std::string::String",
+                "i64"
+            ),
+            modified(
+                Element::Relationship { number: 2 },
+                "referrer cardinality",
+                "One",
+                "Many"
+            ),
+        ]
+    );
+
+    let text = diff.to_string();
+    assert!(
+        text.contains("~ R2 referrer cardinality: One → Many"),
+        "{}",
+        text
+    );
+    assert!(text.contains("+ object `Cow`"), "{}", text);
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["changes"][2]["change"], "added");
+    assert_eq!(json["changes"][2]["element"]["kind"], "object");
+    assert_eq!(json["changes"][2]["element"]["name"], "Cow");
+}
@@ -0,0 +1,119 @@
+use std::{fs, path::Path};
+
+use nut::codegen::{CodeGenError, Context, Displacement, Error};
+
+/// Generate a file with a critical block for each of `blocks`
+fn emit(path: &Path, blocks: &[(&str, u32)]) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.writeln("//! A file for displacing");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    for (tag, value) in blocks {
+        context.begin_critical_block(tag).unwrap();
+        context.writeln(format!(
+            "pub const {}: u32 = {};",
+            tag.to_uppercase(),
+            value
+        ));
+        context.writeln(format!(
+            "pub const {}_TOO: u32 = {};",
+            tag.to_uppercase(),
+            value
+        ));
+        context.end_critical_block(tag).unwrap();
+    }
+    context
+}
+
+fn line_of(text: &str, needle: &str) -> usize {
+    text.lines().position(|l| l.contains(needle)).unwrap() + 1
+}
+
+#[test]
+fn test_commented_out() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("displaced.rs");
+
+    assert!(emit(&path, &[("a", 1), ("b", 2)])
+        .commit()
+        .unwrap()
+        .is_empty());
+
+    // Somebody couldn't help themselves.
+    let edited = fs::read_to_string(&path)
+        .unwrap()
+        .replace("pub const B: u32 = 2;", "pub const B: u32 = 3;")
+        .replace("pub const B_TOO: u32 = 2;", "pub const B_TOO: u32 = 3;");
+    fs::write(&path, &edited).unwrap();
+
+    let displaced = emit(&path, &[("a", 1), ("b", 2)]).commit().unwrap();
+    assert_eq!(displaced.len(), 1);
+
+    let d = &displaced[0];
+    let first = line_of(&edited, "pub const B: u32 = 3;");
+    assert_eq!(d.path, path);
+    assert_eq!(d.tag, "b");
+    assert_eq!(d.kind, Displacement::CommentedOut);
+    assert_eq!(d.lines, first..=first + 1);
+    assert_eq!(d.text, "pub const B: u32 = 3;\npub const B_TOO: u32 = 3;");
+
+    let after = fs::read_to_string(&path).unwrap();
+    assert!(after.contains("// pub const B: u32 = 3; //⚡️"));
+
+    // Once it's commented out, it's old news.
+    assert!(emit(&path, &[("a", 1), ("b", 2)])
+        .commit()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_deleted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("deleted.rs");
+
+    emit(&path, &[("a", 1), ("b", 2)]).commit().unwrap();
+    let before = fs::read_to_string(&path).unwrap();
+
+    let displaced = emit(&path, &[("a", 1)]).commit().unwrap();
+    assert_eq!(displaced.len(), 1);
+
+    let d = &displaced[0];
+    assert_eq!(d.tag, "b");
+    assert_eq!(d.kind, Displacement::Deleted);
+    assert!(d.lines.contains(&line_of(&before, "pub const B: u32 = 2;")));
+    assert!(d
+        .lines
+        .contains(&line_of(&before, "pub const B_TOO: u32 = 2;")));
+
+    assert!(!fs::read_to_string(&path).unwrap().contains("B_TOO"));
+}
+
+#[test]
+fn test_fail_on_displaced() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("strict.rs");
+
+    emit(&path, &[("a", 1)]).commit().unwrap();
+    let edited = fs::read_to_string(&path)
+        .unwrap()
+        .replace("pub const A: u32 = 1;", "pub const A: u32 = 42;");
+    fs::write(&path, &edited).unwrap();
+
+    let mut context = emit(&path, &[("a", 1)]);
+    context.fail_on_displaced(true);
+    match context.commit() {
+        Err(Error(e @ CodeGenError::DisplacedCode { .. })) => {
+            let message = e.to_string();
+            assert!(message.contains("commented out lines in critical block `a`"));
+            if let CodeGenError::DisplacedCode { displaced, .. } = e {
+                assert_eq!(displaced.len(), 1);
+                assert_eq!(displaced[0].text, "pub const A: u32 = 42;");
+            }
+        }
+        r => panic!("expected displaced code, got {:?}", r),
+    }
+
+    // Hands off.
+    assert_eq!(edited, fs::read_to_string(&path).unwrap());
+}
@@ -82,3 +82,24 @@ fn load_drawing() {
     assert_eq!(ui.width, 226);
     assert_eq!(ui.height, 181);
 }
+
+#[test]
+fn test_anchor_offset() {
+    let model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let r2 = model.sarzak.get_rel("R2").unwrap();
+    let (_, ui) = model
+        .drawing
+        .iter_binary_ui()
+        .find(|(_, ui)| &ui.binary == r2.get_id())
+        .unwrap();
+    let anchor = model.drawing.exhume_anchor(&ui.from).unwrap();
+
+    // The anchor is where the line meets the object.
+    let location = model.drawing.exhume_point(&anchor.location).unwrap();
+    assert_eq!((location.x, location.y), (485, 684));
+
+    // And the offset is where the phrase goes, relative to that. It used to be
+    // a second copy of the location.
+    let offset = model.drawing.exhume_point(&anchor.offset).unwrap();
+    assert_eq!((offset.x, offset.y), (10, 41));
+}
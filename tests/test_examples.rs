@@ -0,0 +1,72 @@
+use uuid::Uuid;
+
+use nut::codegen::{ExampleValues, SarzakModel};
+
+mod common;
+use common::{generate_in, options, Generated};
+
+fn model() -> SarzakModel {
+    SarzakModel::load_cuckoo_model("crates/test_models/models/everything.json").unwrap()
+}
+
+#[test]
+fn test_seeded_values() {
+    let model = model();
+    let id = Uuid::new_v4();
+
+    let mut one = ExampleValues::new(&model, &id);
+    let mut two = ExampleValues::new(&model, &id);
+    let mut other = ExampleValues::new(&model, &Uuid::new_v4());
+
+    let names = (0..8).map(|_| one.name()).collect::<Vec<_>>();
+    assert_eq!(names, (0..8).map(|_| two.name()).collect::<Vec<_>>());
+    assert_ne!(names, (0..8).map(|_| other.name()).collect::<Vec<_>>());
+
+    // They are variable names too.
+    for name in (0..1000).map(|_| other.name()) {
+        assert!(
+            name.chars().all(|c| c.is_ascii_lowercase() || c == '_'),
+            "{}",
+            name
+        );
+    }
+
+    let ident = one.ident("dog");
+    assert_eq!(ident, two.ident("dog"));
+    assert!(ident.starts_with("dog_"));
+    assert_eq!(ident.len(), "dog_xyz".len());
+}
+
+#[test]
+fn test_doc_tests_are_stable() {
+    let model = model();
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+
+    let Generated { types, macros, .. } =
+        generate_in(&model, "test_models", first.path(), &options());
+    assert!(types.contains("/// # Example"));
+    assert!(macros.contains("/// # Example"));
+
+    // Nothing to hide anymore.
+    for text in [&types, &macros] {
+        let lines = text.lines().collect::<Vec<_>>();
+        for window in lines.windows(3) {
+            assert!(
+                !(window[0].contains("IgnoreBlockBegin")
+                    && window[1..].join("").contains("# Example"))
+            );
+        }
+    }
+
+    // Different files, same code.
+    let second = generate_in(&model, "test_models", second.path(), &options());
+    assert_eq!((types, macros), (second.types, second.macros));
+
+    // And the same file, regenerated, doesn't change either.
+    let again = generate_in(&model, "test_models", first.path(), &options());
+    assert_eq!(
+        again,
+        generate_in(&model, "test_models", first.path(), &options())
+    );
+}
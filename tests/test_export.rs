@@ -0,0 +1,231 @@
+use nut::codegen::SarzakModel;
+use nut::model::parse_model;
+use nut::sarzak::{export_model, to_dot, to_mermaid, to_plantuml, Format};
+
+#[test]
+fn test_dot() {
+    let model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let dot = to_dot(&model);
+
+    assert!(dot.starts_with("digraph \"cat_dog\" {"), "{}", dot);
+    assert!(
+        dot.contains("Cat [label=\"{Cat|id: Uuid\\ldisposition: String\\l}\"];"),
+        "{}",
+        dot
+    );
+    assert!(
+        dot.contains("Cat -> Animal [label=\"R1\", arrowhead=empty];"),
+        "{}",
+        dot
+    );
+    assert!(
+        dot.contains(
+            "Dog -> Cat [label=\"R2\", taillabel=\"is chased by\\n1\", headlabel=\"chases\\n1\"];"
+        ),
+        "{}",
+        dot
+    );
+    assert!(dot.ends_with("}\n"));
+
+    // Same model, same picture.
+    assert_eq!(dot, export_model(&model, Format::Dot));
+}
+
+#[test]
+fn test_mermaid() {
+    let model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let mermaid = to_mermaid(&model);
+
+    assert!(mermaid.contains("classDiagram\n"), "{}", mermaid);
+    assert!(
+        mermaid.contains("    class Dog {\n        Uuid id\n        String name\n    }\n"),
+        "{}",
+        mermaid
+    );
+    assert!(mermaid.contains("Animal <|-- Dog : R1"), "{}", mermaid);
+    assert!(
+        mermaid.contains("Dog \"1\" --> \"1\" Cat : R2 chases / is chased by"),
+        "{}",
+        mermaid
+    );
+}
+
+#[test]
+fn test_mermaid_escape() {
+    let mut model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let r2 = model.sarzak.get_rel("R2").unwrap().clone();
+    let binary = model.sarzak.exhume_binary(r2.get_id()).unwrap().clone();
+    let mut referrer = model.sarzak.exhume_referrer(&binary.from).unwrap().clone();
+    referrer.description = "chases: \"always\"\n#1".to_owned();
+    model.sarzak.inter_referrer(referrer);
+
+    let mermaid = to_mermaid(&model);
+    assert!(
+        mermaid.contains(
+            "Dog \"1\" --> \"1\" Cat : R2 chases / chases#58; #quot;always#quot; #35;1\n"
+        ),
+        "{}",
+        mermaid
+    );
+}
+
+#[test]
+fn test_names() {
+    let model = parse_model(
+        "domain \"zoo: the \\\"best\\\" #1\"\n\
+         object \"Foo Bar\" (FB) { id: Uuid, }\n\
+         object FooBar (FOO) { id: Uuid, }\n\
+         object \"Say \\\"Hi\\\"\" (HI) { id: Uuid, }\n",
+    )
+    .unwrap();
+
+    let mermaid = to_mermaid(&model);
+    assert!(
+        mermaid.starts_with("---\ntitle: \"zoo: the \\\"best\\\" #1\"\n---\n"),
+        "{}",
+        mermaid
+    );
+
+    let puml = to_plantuml(&model);
+    assert!(puml.contains("title zoo: the 'best' #1\n"), "{}", puml);
+    assert!(puml.contains("class \"Say 'Hi'\" as SayHi {"), "{}", puml);
+
+    // Two names, one identifier. They still have to be two classes.
+    assert!(
+        puml.contains("class \"Foo Bar\" as FooBar_FB {"),
+        "{}",
+        puml
+    );
+    assert!(
+        puml.contains("class \"FooBar\" as FooBar_FOO {"),
+        "{}",
+        puml
+    );
+    assert!(
+        mermaid.contains("class FooBar_FB[\"Foo Bar\"]\n"),
+        "{}",
+        mermaid
+    );
+    assert!(
+        mermaid.contains("class FooBar_FOO[\"FooBar\"]\n"),
+        "{}",
+        mermaid
+    );
+    let dot = to_dot(&model);
+    assert!(dot.contains("FooBar_FB [label=\"{Foo Bar|"), "{}", dot);
+    assert!(dot.contains("FooBar_FOO [label=\"{FooBar|"), "{}", dot);
+}
+
+#[test]
+fn test_associative() {
+    let model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/associative.json").unwrap();
+
+    let puml = to_plantuml(&model);
+    assert!(puml.starts_with("@startuml\n"), "{}", puml);
+    assert!(
+        puml.contains("class \"Acknowledged Event\" as AcknowledgedEvent {"),
+        "{}",
+        puml
+    );
+    assert!(
+        puml.contains(
+            "Event \"may accept\\n0..*\" -- \"may be processed by\\n1..*\" State : R20\n\
+             (Event, State) .. AcknowledgedEvent\n"
+        ),
+        "{}",
+        puml
+    );
+    assert!(puml.ends_with("@enduml\n"));
+
+    let mermaid = to_mermaid(&model);
+    assert!(
+        mermaid.contains("class AcknowledgedEvent[\"Acknowledged Event\"]"),
+        "{}",
+        mermaid
+    );
+    assert!(
+        mermaid.contains("AcknowledgedEvent .. State : R20"),
+        "{}",
+        mermaid
+    );
+
+    let dot = to_dot(&model);
+    assert!(dot.contains("R20 [shape=point, label=\"\"];"), "{}", dot);
+    assert!(
+        dot.contains("R20 -> AcknowledgedEvent [style=dashed, arrowhead=none];"),
+        "{}",
+        dot
+    );
+}
+
+#[test]
+fn test_format() {
+    assert_eq!("dot".parse::<Format>().unwrap(), Format::Dot);
+    assert_eq!("PlantUML".parse::<Format>().unwrap(), Format::PlantUml);
+    assert_eq!("svg".parse::<Format>().unwrap(), Format::Svg);
+    assert!("visio".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_svg() {
+    let model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let svg = export_model(&model, Format::Svg);
+
+    assert!(svg.starts_with("<?xml"), "{}", svg);
+    assert!(svg.ends_with("</svg>\n"), "{}", svg);
+    assert!(svg.contains("<title>cat_dog</title>"), "{}", svg);
+
+    // Cat, by id, and with its attributes.
+    let cat = model.sarzak.get_obj("Cat").unwrap();
+    let group = svg
+        .split("  <g id=")
+        .find(|g| g.starts_with(&format!("\"{}\"", cat.id)))
+        .unwrap();
+    assert!(group.contains(">Cat</text>"), "{}", group);
+    assert!(group.contains(">disposition: String</text>"), "{}", group);
+
+    // R2, with its phrases, multiplicity and number.
+    let r2 = model.sarzak.get_rel("R2").unwrap();
+    let group = svg
+        .split("  <g id=")
+        .find(|g| g.starts_with(&format!("\"{}\"", r2.get_id())))
+        .unwrap();
+    assert!(group.contains("marker-end=\"url(#one)\""), "{}", group);
+    assert!(group.contains(">is chased by</text>"), "{}", group);
+    assert!(group.contains(">chases</text>"), "{}", group);
+    assert!(group.contains(">R2</text>"), "{}", group);
+
+    // R1 fans out to both subtypes.
+    let r1 = model.sarzak.get_rel("R1").unwrap();
+    let group = svg
+        .split("  <g id=")
+        .find(|g| g.starts_with(&format!("\"{}\"", r1.get_id())))
+        .unwrap();
+    assert_eq!(group.matches("<path").count(), 3, "{}", group);
+    assert!(group.contains("marker-end=\"url(#isa)\""), "{}", group);
+}
+
+#[test]
+fn test_associative_svg() {
+    let model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/associative.json").unwrap();
+    let svg = export_model(&model, Format::Svg);
+
+    let r20 = model.sarzak.get_rel("R20").unwrap();
+    let group = svg
+        .split("  <g id=")
+        .find(|g| g.starts_with(&format!("\"{}\"", r20.get_id())))
+        .unwrap();
+    assert!(group.contains("class=\"associative\""), "{}", group);
+    assert!(group.contains(">may accept</text>"), "{}", group);
+    assert!(group.contains(">0..*</text>"), "{}", group);
+    assert!(group.contains("marker-end=\"url(#many)\""), "{}", group);
+
+    // The phrase is where the modeler left it, relative to the anchor.
+    assert!(
+        group.contains("<text x=\"1430\" y=\"2251\">may accept</text>"),
+        "{}",
+        group
+    );
+}
@@ -0,0 +1,192 @@
+use std::fs;
+
+use nut::codegen::{CodeGenError, Context, Error, Formatter, SarzakModel};
+use nut::domain::{generate_types, GenerateOptions};
+
+mod common;
+
+const MESSY: &str = r##"
+
+pub struct Dog {
+pub name: String,
+
+
+      pub legs: u32,
+}
+impl Dog {
+
+    pub fn bark(&self) -> &str {
+  let c = '{';
+        let s = "}{";
+        let r = r#"("#;
+  let lines = "one
+  two
+
+   three";
+        self.name
+            .as_str()
+    }
+
+}
+"##;
+
+const TIDY: &str = r##"pub struct Dog {
+    pub name: String,
+
+    pub legs: u32,
+}
+impl Dog {
+    pub fn bark(&self) -> &str {
+        let c = '{';
+        let s = "}{";
+        let r = r#"("#;
+        let lines = "one
+  two
+
+   three";
+        self.name
+            .as_str()
+    }
+}
+"##;
+
+#[test]
+fn test_builtin() {
+    assert_eq!(Formatter::Builtin.format(MESSY).unwrap(), TIDY);
+    assert_eq!(Formatter::Builtin.format(TIDY).unwrap(), TIDY);
+
+    match Formatter::Builtin.format("fn main() {\n    let x = (1, 2];\n}\n") {
+        Err(Error(CodeGenError::Format {
+            formatter,
+            message,
+            line,
+            snippet,
+        })) => {
+            assert_eq!(formatter, "builtin");
+            assert_eq!(line, Some(2));
+            assert_eq!(message, "`]` doesn't match the `(` at line 2, column 13");
+            assert_eq!(
+                snippet,
+                "  |\n1 | fn main() {\n2>|     let x = (1, 2];\n  |                  ^\n3 | }"
+            );
+        }
+        r => panic!("expected a format error, got {:?}", r),
+    }
+
+    match Formatter::Builtin.format("fn main() {\n") {
+        Err(Error(e @ CodeGenError::Format { .. })) => {
+            assert!(e.to_string().contains("unclosed `{`"))
+        }
+        r => panic!("expected a format error, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_command() {
+    let rustfmt = Formatter::rustfmt();
+    assert_eq!(
+        rustfmt.format("fn  main(){let x=1;}").unwrap(),
+        "fn main() {\n    let x = 1;\n}\n"
+    );
+
+    match rustfmt.format("fn main() {\n    let x = ;\n}\n") {
+        Err(Error(CodeGenError::Format {
+            formatter,
+            message,
+            line,
+            snippet,
+        })) => {
+            assert_eq!(formatter, "rustfmt");
+            assert_eq!(message, "expected expression, found `;`");
+            assert_eq!(line, Some(2));
+            assert!(snippet.contains("\n2>|     let x = ;\n  |             ^\n"));
+            assert!(snippet.ends_with("\n3 | }"));
+        }
+        r => panic!("expected a format error, got {:?}", r),
+    }
+
+    let missing = "no-such-formatter --please".parse::<Formatter>().unwrap();
+    assert!(matches!(
+        missing.format("fn main() {}"),
+        Err(Error(CodeGenError::SpawnFormatter { .. }))
+    ));
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("builtin".parse::<Formatter>().unwrap(), Formatter::Builtin);
+    assert_eq!(" none ".parse::<Formatter>().unwrap(), Formatter::None);
+    assert_eq!(
+        "rustfmt".parse::<Formatter>().unwrap(),
+        Formatter::default()
+    );
+
+    let command = "rustfmt --edition 2021".parse::<Formatter>().unwrap();
+    assert_eq!(
+        command,
+        Formatter::Command {
+            program: "rustfmt".to_owned(),
+            args: vec!["--edition".to_owned(), "2021".to_owned()],
+        }
+    );
+    assert_eq!(command.to_string(), "rustfmt --edition 2021");
+
+    assert!(matches!(
+        "  ".parse::<Formatter>(),
+        Err(Error(CodeGenError::BadFormatter { .. }))
+    ));
+}
+
+#[test]
+fn test_context() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plain.rs");
+
+    let mut context = Context::new(&path, false).unwrap();
+    context.formatter(Formatter::None);
+    context.writeln("//! Left the way it was");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.writeln("pub   const   VALUE: u32 = 42;");
+    context.commit().unwrap();
+
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .ends_with("\npub   const   VALUE: u32 = 42;\n"));
+}
+
+#[test]
+fn test_generate_builtin() {
+    let model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/everything.json").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let builtin = dir.path().join("builtin.rs");
+    let rustfmt = dir.path().join("rustfmt.rs");
+
+    for (path, formatter) in [
+        (&builtin, Formatter::Builtin),
+        (&rustfmt, Formatter::rustfmt()),
+    ] {
+        let options = GenerateOptions {
+            formatter,
+            ..common::options()
+        };
+        generate_types(&model, path, "test_models", &options).unwrap();
+    }
+
+    // Not the same, but close.
+    let builtin = fs::read_to_string(builtin).unwrap();
+    assert!(builtin.contains("\npub struct Everything {\n"));
+    assert!(builtin.contains("\n    pub bool: bool,\n"));
+    assert_eq!(
+        builtin
+            .lines()
+            .filter(|l| l.trim_start().starts_with("pub fn new("))
+            .count(),
+        fs::read_to_string(rustfmt)
+            .unwrap()
+            .lines()
+            .filter(|l| l.trim_start().starts_with("pub fn new("))
+            .count()
+    );
+}
@@ -0,0 +1,224 @@
+use nut::codegen::{SarzakModel, Variant};
+
+mod common;
+use common::{generate, Generated};
+
+/// Subtypes in more than one hierarchy
+fn zoo() -> SarzakModel {
+    SarzakModel::load_text_model("crates/test_models/models/zoo.nut").unwrap()
+}
+
+/// A hierarchy more than one level deep, with every kind of subtype
+fn edges() -> SarzakModel {
+    SarzakModel::load_text_model("crates/test_models/models/edges.nut").unwrap()
+}
+
+fn count(haystack: &str, needle: &str) -> usize {
+    haystack.matches(needle).count()
+}
+
+/// The body of the item that starts with `header`
+fn item<'a>(code: &'a str, header: &str) -> &'a str {
+    let start = code.find(header).unwrap();
+    let end = code[start..].find("\n}\n").unwrap();
+
+    &code[start..start + end]
+}
+
+#[test]
+fn test_multiple_hierarchies() {
+    let model = zoo();
+    let Generated { types, store, .. } = generate(&model, "zoo");
+
+    // One supertype, one struct, and an enum per relationship.
+    assert_eq!(count(&types, "pub struct Animal {"), 1);
+    assert_eq!(count(&types, "pub enum Animal {"), 0);
+    assert_eq!(count(&types, "pub enum AnimalR1 {"), 1);
+    assert_eq!(count(&types, "pub enum AnimalR2 {"), 1);
+    assert!(types.contains("//!    * [`AnimalR1`]\n//!    * [`AnimalR2`]\n"));
+
+    let animal = item(&types, "pub struct Animal {");
+    assert!(animal.contains("pub id: Uuid,"));
+    assert!(animal.contains("pub r1: AnimalR1,"));
+    assert!(animal.contains("pub r2: AnimalR2,"));
+    assert!(
+        types.contains("pub fn new(store: &mut ObjectStore, r1: AnimalR1, r2: AnimalR2) -> Self {")
+    );
+    assert_eq!(count(&types, "pub fn get_id(&self) -> Uuid {"), 4);
+
+    // Only the supertype goes in the store, and it's found by `get_id`, same as an enum.
+    assert_eq!(count(&types, "store.inter_animal("), 1);
+    assert!(!types.contains("inter_animal_r"));
+    assert!(store.contains("self.animal.insert(animal.get_id(), animal);"));
+    assert!(!store.contains("AnimalR1"));
+
+    // The struct has an id of it's own, so a subtype's id finds it in the store.
+    let lookup = item(
+        &store,
+        "pub fn exhume_animal_by_subtype(&self, id: &Uuid) -> Option<&Animal> {",
+    );
+    assert!(lookup.contains("animal.r1.get_id() == *id || animal.r2.get_id() == *id"));
+    assert!(!store.contains("exhume_companion_by_subtype"));
+    assert!(types.contains(
+        "/// assert_eq!(store.exhume_animal_by_subtype(&animal.r2.get_id()), Some(&animal));"
+    ));
+
+    // Critical blocks are tagged like their neighbours.
+    assert_eq!(count(&types, "animal-struct-get-id-impl"), 2);
+    assert_eq!(count(&types, "animal_r1-enum-get-id-impl"), 2);
+    assert!(!types.contains("Animal-struct-get-id-impl"));
+    assert!(!types.contains("AnimalR1-enum-get-id-impl"));
+
+    // Just the one place for their code.
+    assert_eq!(count(&types, "animal-impl-extras"), 2);
+
+    // A subtype in two relationships is a variant in each of them.
+    for header in [
+        "pub enum AnimalR1 {",
+        "pub enum AnimalR2 {",
+        "pub enum Companion {",
+    ] {
+        assert!(item(&types, header).contains("Cat(Uuid),"));
+    }
+
+    // A supertype in only one relationship is still an enum.
+    assert_eq!(count(&types, "pub enum Companion {"), 1);
+    assert!(types.contains("store.inter_companion(test.clone());"));
+}
+
+#[test]
+fn test_multiple_hierarchies_are_stable() {
+    let model = zoo();
+    let first = generate(&model, "zoo");
+    assert!(first.types.find("pub enum AnimalR1 {") < first.types.find("pub enum AnimalR2 {"));
+    assert_eq!(first, generate(&model, "zoo"));
+}
+
+#[test]
+fn test_variants() {
+    let model = edges();
+    let variant = |name: &str| {
+        let (_, object) = model
+            .sarzak
+            .iter_object()
+            .find(|(_, o)| o.name.inner() == name)
+            .unwrap();
+        Variant::of(object, &model)
+    };
+
+    assert_eq!(variant("Object Edge"), Variant::Supertype);
+    assert_eq!(variant("Top"), Variant::Instance);
+    assert_eq!(variant("Left"), Variant::Singleton);
+    assert_eq!(variant("Bottom"), Variant::Unit);
+    assert!(!Variant::Unit.has_id());
+}
+
+#[test]
+fn test_unit_subtype() {
+    let model = edges();
+    let Generated {
+        types,
+        store,
+        macros,
+    } = generate(&model, "edges");
+
+    // It's just a variant, so there's nothing else to it.
+    assert!(types.contains("\n    Bottom,\n"));
+    for code in [&types, &store, &macros] {
+        assert!(!code.contains("[`Bottom`]"));
+        assert!(!code.contains("struct Bottom"));
+        assert!(!code.contains("impl Bottom"));
+        assert!(!code.contains("bottom-"));
+        assert!(!code.contains("_bottom("));
+    }
+}
+
+#[test]
+fn test_multiple_levels() {
+    let model = edges();
+    let Generated { types, store, .. } = generate(&model, "zoo");
+
+    // A supertype without attributes still carries the id from below it.
+    let edge = item(&types, "pub enum Edge {");
+    assert!(edge.contains("ObjectEdge(Uuid),"));
+    let object_edge = item(&types, "pub enum ObjectEdge {");
+    assert!(object_edge.contains("Side(Uuid),"));
+    assert!(object_edge.contains("Left(Uuid),"));
+    assert!(object_edge.contains("Bottom,"));
+    assert!(types.contains("Self::Bottom => Uuid::from_u128(0x"));
+    assert!(
+        types.contains("let test = Self::ObjectEdge(ObjectEdge::test_default(store).get_id());")
+    );
+
+    // Everything at the bottom, from every level.
+    let leaf = item(&types, "pub enum EdgeLeaf<'a> {");
+    for variant in [
+        "Bisection(&'a Bisection),",
+        "Bottom,",
+        "Left(Uuid),",
+        "Top(&'a Top),",
+    ] {
+        assert!(leaf.contains(variant));
+    }
+    assert!(types.contains("pub enum ObjectEdgeLeaf<'a> {"));
+    // One level down is just a lookup.
+    assert!(!types.contains("pub enum SideLeaf"));
+
+    // The doc test builds a `Top`, and wraps it all the way up.
+    for line in [
+        "/// let top = Top::new(&mut store, 42.0);",
+        "/// let side = Side::Top(top.id);",
+        "/// let object_edge = ObjectEdge::Side(side.get_id());",
+        "/// let edge = Edge::ObjectEdge(object_edge.get_id());",
+        "/// assert_eq!(edge.get_id(), top.id);",
+        "/// assert_eq!(store.exhume_edge_leaf(&edge.get_id()), Some(EdgeLeaf::Top(&top)));",
+    ] {
+        assert!(types.contains(line), "missing `{}`", line);
+    }
+
+    // And the store follows it back down.
+    assert!(store.contains("EdgeLeaf, "));
+    let lookup = item(
+        &store,
+        "    pub fn exhume_edge_leaf(&self, id: &Uuid) -> Option<EdgeLeaf<'_>> {",
+    );
+    for line in [
+        "let object_edge = self.exhume_object_edge(id)?;",
+        "let side = self.exhume_side(id)?;",
+        "Side::Top(id) => self.exhume_top(id).map(EdgeLeaf::Top),",
+        "ObjectEdge::Left(id) => Some(EdgeLeaf::Left(*id)),",
+        "ObjectEdge::Bottom => Some(EdgeLeaf::Bottom),",
+        "Edge::Bisection(id) => self.exhume_bisection(id).map(EdgeLeaf::Bisection),",
+    ] {
+        assert!(lookup.contains(line), "missing `{}`", line);
+    }
+}
+
+#[test]
+fn test_conversions() {
+    let model = edges();
+    let types = generate(&model, "zoo").types;
+
+    let conversions = item(&types, "impl Edge {\n    /// Exhume the [`ObjectEdge`]");
+    assert!(conversions.contains(
+        "pub fn as_object_edge<'a>(&self, store: &'a ObjectStore) -> Option<&'a ObjectEdge> {"
+    ));
+    assert!(conversions.contains("Self::Bisection(id) => store.exhume_bisection(id),"));
+
+    // A supertype goes in by it's `get_id`, and everything else by it's `id`.
+    assert!(types.contains("impl From<&ObjectEdge> for Edge {"));
+    assert!(types.contains("Self::ObjectEdge(object_edge.get_id())"));
+    assert!(types.contains("Self::Bisection(bisection.id)"));
+    assert!(types.contains("impl<'a> TryFrom<(&Edge, &'a ObjectStore)> for &'a Bisection {"));
+    assert!(types.contains("edge.as_bisection(store).ok_or_else(|| edge.clone())"));
+
+    // There's nothing to convert a singleton, or a subtype without an id, to.
+    assert!(!types.contains("as_left"));
+    assert!(!types.contains("impl From<&Bottom>"));
+    assert!(types.contains("pub fn as_side<'a>"));
+
+    // The doc test makes one, and gets it back, both ways.
+    assert!(types.contains("/// let edge = Edge::from(&object_edge);"));
+    assert!(types.contains("/// assert_eq!(edge.as_object_edge(&store), Some(&object_edge));"));
+    assert!(types.contains("/// let same: &ObjectEdge = (&edge, &store).try_into().unwrap();"));
+}
@@ -0,0 +1,122 @@
+use std::fs;
+
+use nut::codegen::{CodeGenError, Context, ContextGroup, Error};
+
+fn emit(path: &std::path::Path, value: u32) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.writeln("//! A file in a group");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.begin_critical_block("value").unwrap();
+    context.writeln(format!("pub const VALUE: u32 = {};", value));
+    context.end_critical_block("value").unwrap();
+    context
+}
+
+#[test]
+fn test_all_written() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.rs");
+    let b = dir.path().join("b.rs");
+
+    let mut group = ContextGroup::new();
+    group.add(emit(&a, 1));
+    group.add(emit(&b, 1));
+    assert!(group.commit().unwrap().is_empty());
+
+    assert!(fs::read_to_string(&a).unwrap().contains("VALUE: u32 = 1;"));
+    assert!(fs::read_to_string(&b).unwrap().contains("VALUE: u32 = 1;"));
+
+    // Nothing left behind but what we asked for.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+}
+
+#[test]
+fn test_none_written() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.rs");
+    let b = dir.path().join("b.rs");
+    let c = dir.path().join("c.rs");
+
+    let mut group = ContextGroup::new();
+    group.add(emit(&a, 1));
+    group.add(emit(&b, 1));
+    group.commit().unwrap();
+    let before = fs::read_to_string(&a).unwrap();
+
+    // The last one is broken, so the first two don't get their new values.
+    let mut broken = emit(&c, 2);
+    broken.begin_critical_block("oops").unwrap();
+
+    let mut group = ContextGroup::new();
+    group.add(emit(&a, 2));
+    group.add(emit(&b, 2));
+    group.add(broken);
+    match group.commit() {
+        Err(Error(CodeGenError::UnclosedBlock { path, .. })) => assert_eq!(path, c),
+        r => panic!("expected unclosed block, got {:?}", r),
+    }
+
+    assert_eq!(before, fs::read_to_string(&a).unwrap());
+    assert_eq!(before, fs::read_to_string(&b).unwrap());
+    assert!(!c.exists());
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+}
+
+#[test]
+fn test_all_stale() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.rs");
+    let b = dir.path().join("b.rs");
+
+    let mut group = ContextGroup::new();
+    group.add(emit(&a, 1));
+    group.add(emit(&b, 1));
+    group.commit().unwrap();
+
+    let mut group = ContextGroup::new();
+    group.add(emit(&a, 2));
+    group.add(emit(&b, 2));
+    match group.finish(true) {
+        Err(Error(CodeGenError::StaleOutputs { stale })) => {
+            assert_eq!(stale.len(), 2);
+            let message = CodeGenError::StaleOutputs { stale }.to_string();
+            assert!(message.contains("a.rs is out of date"));
+            assert!(message.contains("b.rs is out of date"));
+        }
+        r => panic!("expected stale outputs, got {:?}", r),
+    }
+
+    // Checking doesn't write anything.
+    assert!(fs::read_to_string(&a).unwrap().contains("VALUE: u32 = 1;"));
+
+    let mut group = ContextGroup::new();
+    group.add(emit(&a, 1));
+    group.add(emit(&b, 1));
+    group.finish(true).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("perms.rs");
+
+    emit(&path, 1).commit().unwrap();
+    assert_eq!(
+        fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+        0o644
+    );
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+    emit(&path, 2).commit().unwrap();
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("VALUE: u32 = 2;"));
+    assert_eq!(
+        fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+        0o600
+    );
+}
@@ -0,0 +1,94 @@
+use std::fs;
+
+use nut::codegen::{CodeGenError, Context, Error, VERSION};
+
+fn emit(path: &std::path::Path) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.writeln("//! A file with provenance");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.writeln("pub const VALUE: u32 = 42;");
+    context
+}
+
+fn headers(text: &str) -> Vec<&str> {
+    text.lines().filter(|l| l.contains("\"version\"")).collect()
+}
+
+/// Swap our header for the ones in `versions`
+fn stamp(path: &std::path::Path, versions: &[&str]) -> String {
+    let text = fs::read_to_string(path).unwrap();
+    let ours = headers(&text)[0].to_owned();
+    let old = versions
+        .iter()
+        .map(|v| ours.replace(VERSION, v))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.replace(&ours, &old);
+    fs::write(path, &text).unwrap();
+    text
+}
+
+#[test]
+fn test_accumulated_headers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("header.rs");
+
+    emit(&path).commit().unwrap();
+    stamp(&path, &["0.2.0", "0.5.0", VERSION]);
+
+    emit(&path).commit().unwrap();
+    let after = fs::read_to_string(&path).unwrap();
+    let after = headers(&after);
+    assert_eq!(after.len(), 1);
+    assert!(after[0].contains(&format!("\"version\":\"{}\"", VERSION)));
+
+    // And it stays that way.
+    emit(&path).check().unwrap();
+}
+
+#[test]
+fn test_older_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("older.rs");
+
+    emit(&path).commit().unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    stamp(&path, &["0.5.0"]);
+
+    // Not up to date, but we'll take care of that.
+    assert!(emit(&path).check().is_err());
+    emit(&path).commit().unwrap();
+    assert_eq!(written, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn test_newer_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("newer.rs");
+
+    emit(&path).commit().unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    let newer = stamp(&path, &["999.0.0"]);
+
+    match emit(&path).commit() {
+        Err(Error(CodeGenError::NewerOutput {
+            path: p,
+            found,
+            supported,
+        })) => {
+            assert_eq!(p, path);
+            assert_eq!(found, "999.0.0");
+            assert_eq!(supported, VERSION);
+        }
+        r => panic!("expected newer output, got {:?}", r),
+    }
+    assert!(emit(&path).check().is_err());
+    assert_eq!(newer, fs::read_to_string(&path).unwrap());
+
+    // Well, if you insist.
+    let mut context = emit(&path);
+    context.allow_newer(true);
+    context.commit().unwrap();
+    assert_eq!(written, fs::read_to_string(&path).unwrap());
+}
@@ -0,0 +1,169 @@
+use nut::codegen::IdentityPolicy;
+use nut::model::parse_model;
+
+mod common;
+use common::{generate, Generated};
+
+const ZOO: &str = r#"
+domain zoo
+
+object Keeper (KPR) {
+    id: Uuid,
+    *1 badge: Integer,
+    *2 first: String,
+    *2 last: String,
+}
+
+/// ❗️ {"identity": "supplied"}
+object Ticket (TIX) {
+    id: Uuid,
+    *1 seat: String,
+}
+
+/// ❗️ {"identity": "random"}
+object Feeding (FED) {
+    id: Uuid,
+    time: Timestamp,
+}
+
+/// ❗️ {"identity": "identifier"}
+object Janitor (JAN) {
+    id: Uuid,
+    name: String,
+}
+
+object Visit (VIS) {
+    id: Uuid,
+    arrived: Timestamp,
+}
+
+object Staff (STF) {
+    id: Uuid,
+}
+
+binary R1 {
+    referrer Visit 1 "is made to" via keeper
+    referent Keeper 1 "is visited by"
+}
+
+binary R2 {
+    referrer Feeding 1 "is paid for with" via ticket
+    referent Ticket 1 "pays for"
+}
+
+isa R3 Staff { Keeper, Janitor }
+"#;
+
+const PLAIN: &str = r#"
+domain plain
+
+object Cat (CAT) {
+    id: Uuid,
+    name: String,
+}
+"#;
+
+/// The body of the `new` for `object`
+fn new_impl<'a>(types: &'a str, object: &str) -> &'a str {
+    let start = types.find(&format!("impl {} {{", object)).unwrap();
+    let end = types[start..].find("\n}\n").unwrap();
+
+    &types[start..start + end]
+}
+
+#[test]
+fn test_policies() {
+    let model = parse_model(ZOO).unwrap();
+    let policy = |name: &str| {
+        model
+            .config
+            .get_identity(&model.sarzak.get_obj(name).unwrap().id)
+    };
+
+    assert_eq!(policy("Ticket"), Some(IdentityPolicy::Supplied));
+    assert_eq!(policy("Feeding"), Some(IdentityPolicy::Random));
+    assert_eq!(policy("Janitor"), Some(IdentityPolicy::Identifier));
+    // Declaring identifiers is enough, no configuration required.
+    assert_eq!(policy("Keeper"), None);
+}
+
+#[test]
+fn test_identifier() {
+    let types = generate(&parse_model(ZOO).unwrap(), "zoo").types;
+
+    assert!(types.contains("//!    * [`DuplicateIdentifier`]\n"));
+    assert_eq!(types.matches("pub struct DuplicateIdentifier {").count(), 1);
+    assert!(types.contains("impl std::error::Error for DuplicateIdentifier {}"));
+
+    // The `id` comes from the first identifier, and the second one is looked for.
+    let keeper = new_impl(&types, "Keeper");
+    assert!(keeper.contains(") -> Result<Self, DuplicateIdentifier> {"));
+    assert!(
+        keeper.contains("let id = Uuid::new_v5(&UUID_NS, format!(\"{}::\", badge,).as_bytes());")
+    );
+    assert!(keeper.contains("if store.exhume_keeper(&id).is_some() {"));
+    assert!(keeper.contains("identifier: 1,"));
+    assert!(keeper.contains(".find(|(_, keeper)| keeper.first == first && keeper.last == last)"));
+    assert!(keeper.contains("identifier: 2,"));
+    assert!(keeper.contains("Ok(new)"));
+    assert!(keeper.contains("/// It's a [`DuplicateIdentifier`] if there's already one"));
+
+    // No identifiers, so it's all of them, but checked this time.
+    let janitor = new_impl(&types, "Janitor");
+    assert!(
+        janitor.contains("let id = Uuid::new_v5(&UUID_NS, format!(\"{}::\", name,).as_bytes());")
+    );
+    assert!(janitor.contains("identifier: 0,"));
+}
+
+#[test]
+fn test_random_and_supplied() {
+    let types = generate(&parse_model(ZOO).unwrap(), "zoo").types;
+
+    let feeding = new_impl(&types, "Feeding");
+    assert!(feeding.contains("let id = Uuid::new_v4();"));
+    assert!(!feeding.contains("Uuid::new_v5(&UUID_NS"));
+
+    // The caller's `id` goes last, and the identifier is still checked.
+    let ticket = new_impl(&types, "Ticket");
+    assert!(ticket.contains(
+        "pub fn new(\n        store: &mut ObjectStore,\n        seat: std::string::String,\n        id: Uuid,\n    ) -> Result<Self, DuplicateIdentifier> {"
+    ));
+    assert!(!ticket.contains("let id ="));
+    assert!(ticket.contains("store.iter_ticket().find(|(_, ticket)| ticket.seat == seat)"));
+    assert!(ticket.contains("uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, b\""));
+}
+
+#[test]
+fn test_callers() {
+    let Generated { types, macros, .. } = generate(&parse_model(ZOO).unwrap(), "zoo");
+
+    // Everybody that calls a checked `new` has to unwrap it.
+    assert!(types.contains("Keeper::new(&mut store, "));
+    for line in types
+        .lines()
+        .chain(macros.lines())
+        .filter(|l| l.contains("Keeper::new(") || l.contains("Ticket::new("))
+    {
+        assert!(line.ends_with(").unwrap();"), "{}", line);
+    }
+    assert!(types.contains("let test = Self::Janitor(Janitor::new(store, "));
+    assert!(types.contains(").unwrap().id);"));
+
+    // And nobody that calls an unchecked one does.
+    let visit = new_impl(&types, "Visit");
+    assert!(visit.contains(") -> Self {"));
+    assert!(visit.contains("\n        new\n"));
+}
+
+#[test]
+fn test_legacy() {
+    let types = generate(&parse_model(PLAIN).unwrap(), "plain").types;
+
+    assert!(!types.contains("DuplicateIdentifier"));
+    assert!(
+        types.contains("pub fn new(store: &mut ObjectStore, name: std::string::String) -> Self {")
+    );
+    assert!(types.contains("/// let cat = Cat::new(&mut store, "));
+    assert!(!types.contains(".unwrap()"));
+}
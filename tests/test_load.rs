@@ -0,0 +1,147 @@
+use std::{collections::HashMap, fs::File, io::Write};
+
+use serde_json::Value;
+use tempfile::NamedTempFile;
+
+use nut::codegen::{CodeGenError, ReadModel, SarzakModel};
+use nut::{ObjectType, ReadCuckooModel, Schema_v0};
+
+type CuckooModel = HashMap<String, Vec<ObjectType>>;
+
+const ISA: &str = "dc116c5e-0b88-5bcc-89b1-648008fafe45";
+
+/// Mangle cat_dog, and load the result.
+fn load_mangled<F: FnOnce(&mut Value)>(mangle: F) -> CodeGenError {
+    let mut json: Value = serde_json::from_reader(File::open("models/cat_dog.json").unwrap())
+        .expect("cat_dog should be valid json");
+    mangle(&mut json);
+
+    let mut file = NamedTempFile::new().unwrap();
+    serde_json::to_writer(&mut file, &json).unwrap();
+
+    match SarzakModel::load_cuckoo_model(file.path()) {
+        Ok(_) => panic!("loaded a mangled model"),
+        Err(e) => match e.0 {
+            CodeGenError::BadCuckooModel { path, source } => {
+                assert_eq!(path, file.path());
+                source.0
+            }
+            e => panic!("unexpected error: {}", e),
+        },
+    }
+}
+
+#[test]
+fn test_missing_file() {
+    let err = SarzakModel::load_cuckoo_model("models/nope.json").unwrap_err();
+    assert!(matches!(err.0, CodeGenError::LoadCuckooModel { .. }));
+}
+
+#[test]
+fn test_bad_json() {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(file, "{{\n  \"paper\": {{\n    \"ids\": [,]\n").unwrap();
+
+    let err = SarzakModel::load_cuckoo_model(file.path()).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("line 3, column 13"), "{}", message);
+
+    let err = ReadModel::from_json(&mut "{\"paper\": 42}".as_bytes()).unwrap_err();
+    assert!(matches!(
+        err.0,
+        CodeGenError::ParseCuckooModel { line: 1, .. }
+    ));
+}
+
+#[test]
+fn test_empty_paper() {
+    let err = load_mangled(|json| json["paper"]["ids"] = Value::Array(vec![]));
+    assert!(matches!(err, CodeGenError::EmptyPaperIds));
+
+    let err = load_mangled(|json| {
+        json["paper"]["entities"].as_object_mut().unwrap().clear();
+    });
+    assert!(matches!(err, CodeGenError::MissingPaper { .. }));
+}
+
+#[test]
+fn test_missing_entity() {
+    let err = load_mangled(|json| {
+        json["relationships"]["entities"]
+            .as_object_mut()
+            .unwrap()
+            .remove(ISA);
+    });
+    match err {
+        CodeGenError::MissingEntity { kind, id } => {
+            assert_eq!(kind, "relationship");
+            assert_eq!(id.to_string(), ISA);
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_wrong_entity_kind() {
+    // Draw the isa relationship as though it were binary.
+    let err = load_mangled(|json| {
+        let paper = json["paper"]["entities"]
+            .as_object_mut()
+            .unwrap()
+            .values_mut()
+            .next()
+            .unwrap();
+        let binary = paper["relationships"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| *id != ISA)
+            .map(|(_, ui)| ui.clone())
+            .unwrap();
+        paper["relationships"][ISA] = binary;
+    });
+    match err {
+        CodeGenError::WrongEntityKind {
+            id,
+            expected,
+            found,
+        } => {
+            assert_eq!(id.to_string(), ISA);
+            assert_eq!(expected, "binary");
+            assert_eq!(found, "isa");
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_cuckoo_schema() {
+    let cuckoo =
+        ReadCuckooModel::from_json(&mut File::open("tests/cat_dog.json").unwrap()).unwrap();
+
+    // Objects in the relationships, and vice versa.
+    let mut swapped: CuckooModel = HashMap::new();
+    let objects = cuckoo.get("Objects").unwrap();
+    let relationships = cuckoo.get("Relationships").unwrap();
+    swapped.insert("Objects".to_owned(), clone_all(relationships));
+    swapped.insert("Relationships".to_owned(), clone_all(objects));
+
+    let err = Schema_v0::try_from(swapped).unwrap_err();
+    assert!(matches!(err.0, CodeGenError::WrongEntityKind { .. }));
+
+    let mut missing: CuckooModel = HashMap::new();
+    missing.insert("Objects".to_owned(), clone_all(objects));
+
+    let err = Schema_v0::try_from(missing).unwrap_err();
+    match err.0 {
+        CodeGenError::MissingSchemaKey { key } => assert_eq!(key, "Relationships"),
+        e => panic!("unexpected error: {}", e),
+    }
+
+    assert!(Schema_v0::try_from(cuckoo).is_ok());
+}
+
+fn clone_all(things: &[ObjectType]) -> Vec<ObjectType> {
+    // ObjectType isn't Clone, so round trip through serde.
+    serde_json::from_value(serde_json::to_value(things).unwrap()).unwrap()
+}
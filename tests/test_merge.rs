@@ -0,0 +1,154 @@
+use std::fs::File;
+
+use serde_json::{json, Value};
+
+use nut::codegen::{JSFormat, ReadModel, SarzakModel};
+use nut::model::{merge::Location, merge_models};
+
+const CAT: &str = "302da172-cfe7-5097-80e2-7aa9abdb6be6";
+const DOG: &str = "e1be2add-a39d-532e-bcb2-c4459717ffe7";
+const DISPOSITION: &str = "35ac9c16-b03e-5986-83ee-4c8db85589f2";
+const R2: &str = "bb8d0f44-40c9-5c3b-bf05-dfa1fdeaba33";
+const PAPER: &str = "88cd70e0-c065-5c4b-b1ce-69194ab4d0cb";
+
+fn cat_dog() -> Value {
+    let model = File::open("models/cat_dog.json")
+        .unwrap()
+        .from_json()
+        .unwrap();
+    serde_json::to_value(model).unwrap()
+}
+
+fn format(value: &Value) -> JSFormat {
+    serde_json::from_value(value.clone()).unwrap()
+}
+
+#[test]
+fn test_clean_merge() {
+    let base = cat_dog();
+
+    let mut ours = base.clone();
+    ours["objects"]["entities"][CAT]["description"] = json!("Meow");
+
+    let mut theirs = base.clone();
+    let collar = "6b4f63a6-0a41-5f2f-a5c5-64a5b1fbb6e9";
+    theirs["objects"]["entities"][DOG]["attributes"][collar] =
+        json!({"id": collar, "name": "collar", "type": "String"});
+    theirs["paper"]["entities"][PAPER]["objects"][DOG]["x"] = json!(42);
+
+    let merge = merge_models(&format(&base), &format(&ours), &format(&theirs)).unwrap();
+    assert!(merge.conflicts.is_empty(), "{:#?}", merge.conflicts);
+    assert!(merge.diagnostics.is_empty(), "{:#?}", merge.diagnostics);
+
+    let model: SarzakModel = merge.merged.try_into().unwrap();
+    let cat = model.sarzak.get_obj("Cat").unwrap();
+    assert_eq!(cat.description, "Meow");
+    let dog = model.sarzak.get_obj("Dog").unwrap();
+    assert!(dog.attributes.contains_key("collar"));
+    let (_, dog_ui) = model
+        .drawing
+        .iter_object_ui()
+        .find(|(_, o)| o.object == dog.id)
+        .unwrap();
+    assert_eq!(model.drawing.exhume_point(&dog_ui.origin).unwrap().x, 42);
+}
+
+#[test]
+fn test_conflicts() {
+    let base = cat_dog();
+
+    let mut ours = base.clone();
+    ours["objects"]["entities"][CAT]["attributes"][DISPOSITION]["type"] = json!("Integer");
+    ours["relationships"]["entities"][R2]["Binary"]["number"] = json!(3);
+
+    let mut theirs = base.clone();
+    theirs["objects"]["entities"][CAT]["attributes"][DISPOSITION]["type"] = json!("Boolean");
+    theirs["relationships"]["entities"][R2]["Binary"]["number"] = json!(4);
+
+    let merge = merge_models(&format(&base), &format(&ours), &format(&theirs)).unwrap();
+    assert_eq!(merge.conflicts.len(), 2, "{:#?}", merge.conflicts);
+
+    let retyped = merge
+        .conflicts
+        .iter()
+        .find(|c| {
+            c.location
+                == Location::Attribute {
+                    object: "Cat".to_owned(),
+                    name: "disposition".to_owned(),
+                }
+        })
+        .unwrap();
+    assert_eq!(retyped.field.as_deref(), Some("type"));
+    assert_eq!(retyped.base, Some(json!("String")));
+    assert_eq!(retyped.ours, Some(json!("Integer")));
+    assert_eq!(retyped.theirs, Some(json!("Boolean")));
+
+    let renumbered = merge
+        .conflicts
+        .iter()
+        .find(|c| {
+            c.location
+                == Location::Relationship {
+                    number: "R2".to_owned(),
+                }
+        })
+        .unwrap();
+    assert_eq!(renumbered.field.as_deref(), Some("number"));
+    assert_eq!(
+        renumbered.to_string(),
+        "R2 number: base 2, ours 3, theirs 4"
+    );
+
+    // Ours wins, and it all still loads.
+    let model: SarzakModel = merge.merged.try_into().unwrap();
+    assert!(model.sarzak.get_rel("R3").is_some());
+}
+
+#[test]
+fn test_delete_and_move() {
+    let base = cat_dog();
+
+    // We moved the cat, and gave the dog something to chase. They got rid of it.
+    let mut ours = base.clone();
+    ours["paper"]["entities"][PAPER]["objects"][CAT]["y"] = json!(0);
+    let chases = "0c6d3f0e-43f5-5d1b-9bb8-2f3d2e2b1f1a";
+    ours["objects"]["entities"][DOG]["attributes"][chases] =
+        json!({"id": chases, "name": "chases", "type": {"foreign_key": CAT}});
+
+    let mut theirs = base.clone();
+    theirs["objects"]["entities"]
+        .as_object_mut()
+        .unwrap()
+        .remove(CAT);
+    theirs["objects"]["ids"] = json!(theirs["objects"]["entities"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect::<Vec<_>>());
+    theirs["paper"]["entities"][PAPER]["objects"]
+        .as_object_mut()
+        .unwrap()
+        .remove(CAT);
+
+    let merge = merge_models(&format(&base), &format(&ours), &format(&theirs)).unwrap();
+    assert_eq!(merge.conflicts.len(), 1, "{:#?}", merge.conflicts);
+    let conflict = &merge.conflicts[0];
+    assert_eq!(
+        conflict.location,
+        Location::Layout {
+            element: "object `Cat`".to_owned()
+        }
+    );
+    assert_eq!(conflict.theirs, None);
+
+    // Neither of us broke the dog, but the merge did.
+    assert_eq!(merge.diagnostics.len(), 1, "{:#?}", merge.diagnostics);
+    assert!(merge.diagnostics[0]
+        .to_string()
+        .contains("`Dog`: attribute `chases`"));
+
+    assert_eq!(merge.merged.objects.ids.len(), 2);
+    let model: SarzakModel = merge.merged.try_into().unwrap();
+    assert!(model.sarzak.get_obj("Cat").is_none());
+}
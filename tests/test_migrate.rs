@@ -0,0 +1,138 @@
+use serde_json::Value;
+
+use std::fs::File;
+
+use nut::codegen::{CodeGenError, ReadSarzakModel, Result, SarzakModel, WriteSarzakModel, VERSION};
+use nut::sarzak::diff_models;
+use nut::sarzak::migrate::{migrate, migrate_with, rename_field, Migration, ModelVersion};
+
+fn cat_dog() -> Value {
+    let model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    let mut bytes = Vec::new();
+    bytes.to_json(&model).unwrap();
+
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[test]
+fn test_current_model() {
+    let mut model = cat_dog();
+    let before = model.clone();
+
+    assert_eq!(migrate(&mut model).unwrap(), ModelVersion::current());
+    assert_eq!(model, before);
+
+    let bytes = serde_json::to_vec(&model).unwrap();
+    let model = bytes.as_slice().from_json().unwrap();
+    assert_eq!(model.version, VERSION);
+    assert!(model.sarzak.get_obj("Cat").is_some());
+}
+
+#[test]
+fn test_archived_model() {
+    // cat_dog, as nut 1.0.0 wrote it.
+    let archived = File::open("models/archive/cat_dog-1.0.0.json")
+        .unwrap()
+        .from_json()
+        .unwrap();
+    let current = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+
+    assert_eq!(archived.version, VERSION);
+    assert!(diff_models(&archived, &current).is_empty());
+
+    // Nothing in it went unread, the drawing included.
+    let mut original: Value =
+        serde_json::from_reader(File::open("models/archive/cat_dog-1.0.0.json").unwrap()).unwrap();
+    original["version"] = Value::String(VERSION.to_owned());
+
+    let mut bytes = Vec::new();
+    bytes.to_json(&archived).unwrap();
+    assert_eq!(serde_json::from_slice::<Value>(&bytes).unwrap(), original);
+}
+
+#[test]
+fn test_newer_model() {
+    let mut model = cat_dog();
+    model["version"] = Value::String("99.0.0".to_owned());
+
+    let bytes = serde_json::to_vec(&model).unwrap();
+    let err = bytes.as_slice().from_json().unwrap_err();
+    match err.0 {
+        CodeGenError::NewerModel { found, supported } => {
+            assert_eq!(found, "99.0.0");
+            assert_eq!(supported, VERSION);
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_bad_version() {
+    let mut model = cat_dog();
+    model["version"] = Value::String("one point oh".to_owned());
+    assert!(matches!(
+        migrate(&mut model).unwrap_err().0,
+        CodeGenError::BadModelVersion { .. }
+    ));
+
+    model.as_object_mut().unwrap().remove("version");
+    assert!(matches!(
+        migrate(&mut model).unwrap_err().0,
+        CodeGenError::BadModelVersion { .. }
+    ));
+
+    assert_eq!(
+        "1.2.3-alpha".parse::<ModelVersion>().unwrap(),
+        ModelVersion::new(1, 2, 3)
+    );
+}
+
+fn unname_objects(model: &mut Value) -> Result<()> {
+    rename_field(model, &["sarzak", "object", "*"], "title", "name")
+}
+
+fn grow_extents(model: &mut Value) -> Result<()> {
+    rename_field(model, &[], "size", "extents")
+}
+
+fn explode(model: &mut Value) -> Result<()> {
+    rename_field(model, &["nope"], "size", "extents")
+}
+
+#[test]
+fn test_migration_steps() {
+    // Pretend that once upon a time objects had titles, and models had a size.
+    let mut model = cat_dog();
+    rename_field(&mut model, &["sarzak", "object", "*"], "name", "title").unwrap();
+    rename_field(&mut model, &[], "extents", "size").unwrap();
+    model["version"] = Value::String("0.9.0".to_owned());
+
+    let steps = [
+        Migration {
+            version: ModelVersion::new(0, 9, 5),
+            description: "objects have names, not titles",
+            upgrade: unname_objects,
+        },
+        Migration {
+            version: ModelVersion::new(1, 0, 0),
+            description: "size is now extents",
+            upgrade: grow_extents,
+        },
+        Migration {
+            version: ModelVersion::new(1, 1, 0),
+            description: "from the future",
+            upgrade: explode,
+        },
+    ];
+
+    let found = migrate_with(&mut model, &steps, ModelVersion::new(1, 0, 0)).unwrap();
+    assert_eq!(found, ModelVersion::new(0, 9, 0));
+    assert_eq!(model, cat_dog());
+
+    // Steps that are out of range don't run. Those that are, and fail, say so.
+    model["version"] = Value::String("1.0.0".to_owned());
+    let err = migrate_with(&mut model, &steps, ModelVersion::new(1, 1, 0)).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("1.1.0 (from the future)"), "{}", message);
+    assert!(message.contains("nope"), "{}", message);
+}
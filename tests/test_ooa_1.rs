@@ -11,7 +11,7 @@ fn test_consistency() {
         .from_json()
         .unwrap();
 
-    let ooa_0: Schema_v0 = cuckoo.into();
+    let ooa_0: Schema_v0 = cuckoo.try_into().unwrap();
     let ooa_1: Schema_v1 = ooa_0.into();
 
     assert!(ooa_1.obj.len() == 3);
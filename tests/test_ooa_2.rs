@@ -1,7 +1,10 @@
 use std::fs::File;
 
-use nut::codegen::{get_referent, get_referrer, get_subtypes, get_supertype, SarzakObjectStore};
-use nut::sarzak::{Object, RelSide};
+use nut::codegen::{
+    get_assoc_from, get_assoc_one, get_assoc_other, get_referent, get_referrer, get_subtypes,
+    get_supertype, SarzakModel, SarzakObjectStore,
+};
+use nut::sarzak::{Object, RelSide, Relationship};
 use nut::{ReadCuckooModel, Schema_v0, Schema_v1};
 
 #[test]
@@ -11,7 +14,7 @@ fn test_drive() {
         .from_json()
         .unwrap();
 
-    let ooa_0: Schema_v0 = cuckoo.into();
+    let ooa_0: Schema_v0 = cuckoo.try_into().unwrap();
     let ooa_1: Schema_v1 = ooa_0.into();
     let store: SarzakObjectStore = ooa_1.into();
 
@@ -101,3 +104,51 @@ fn test_drive() {
     assert_eq!(animals.iter().find(|&&a| a == dog).unwrap(), &dog_2);
     assert_eq!(animals.iter().find(|&&a| a == cat).unwrap(), &cat_2);
 }
+
+#[test]
+fn test_associative() {
+    let model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/associative.json").unwrap();
+    let store = &model.sarzak;
+
+    let r20 = store.get_rel("R20").unwrap();
+    assert!(matches!(r20, Relationship::Associative(_)));
+    assert_eq!(r20.get_number(store), Some(20));
+
+    let numbers: Vec<u16> = store
+        .sorted_relationships()
+        .iter()
+        .map(|r| r.get_number(store).unwrap())
+        .collect();
+    assert_eq!(numbers, vec![10, 20]);
+
+    let ack = store.get_obj("Acknowledged Event").unwrap();
+    let event = store.get_obj("Event").unwrap();
+    let state = store.get_obj("State").unwrap();
+
+    assert_eq!(ack.rels.get("R20").unwrap().side, RelSide::AssocFrom);
+    assert_eq!(event.rels.get("R20").unwrap().side, RelSide::AssocOne);
+    assert_eq!(state.rels.get("R20").unwrap().side, RelSide::AssocOther);
+
+    assert_eq!(get_assoc_from!(event("R20"), store), ack);
+    assert_eq!(get_assoc_one!(state("R20"), store), event);
+    assert_eq!(get_assoc_other!(ack("R20"), store), state);
+
+    let r20 = event.rels.get("R20").unwrap();
+    assert_eq!(get_assoc_from!(r20, store), ack);
+    assert_eq!(get_assoc_one!(r20, store), event);
+    assert_eq!(get_assoc_other!(r20, store), state);
+
+    // Interring by hand indexes by name too.
+    let mut store = SarzakObjectStore::new();
+    let assoc = model
+        .sarzak
+        .iter_associative()
+        .find(|(_, a)| a.number == 10)
+        .map(|(_, a)| a.clone())
+        .unwrap();
+    let id = assoc.id;
+    store.inter_associative(assoc);
+    store.inter_relationship(Relationship::Associative(id));
+    assert_eq!(store.get_rel("R10").unwrap().get_id(), &id);
+}
@@ -0,0 +1,205 @@
+use std::{fs, path::Path};
+
+use uuid::Uuid;
+
+use nut::codegen::{
+    sidecar_path, CodeGenError, Construct, Context, ElementKind, Error, SarzakModel, SourceMap,
+};
+use nut::domain::{generate_macros, generate_types, GenerateOptions};
+
+fn model() -> SarzakModel {
+    SarzakModel::load_cuckoo_model("crates/test_models/models/everything.json").unwrap()
+}
+
+fn types(model: &SarzakModel, path: &Path, source_map: bool) {
+    generate_types(
+        model,
+        &path.to_path_buf(),
+        "test_models",
+        &GenerateOptions {
+            new_impl: true,
+            source_map,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+fn load(path: &Path) -> SourceMap {
+    serde_json::from_str(&fs::read_to_string(sidecar_path(path)).unwrap()).unwrap()
+}
+
+/// The text of `line`, counting from one
+fn line(text: &str, line: usize) -> &str {
+    text.lines().nth(line - 1).unwrap()
+}
+
+#[test]
+fn test_types_map() {
+    let model = model();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("types.rs");
+    let plain = dir.path().join("plain.rs");
+
+    types(&model, &path, true);
+    types(&model, &plain, false);
+
+    // The map doesn't change the code.
+    let text = fs::read_to_string(&path).unwrap();
+    assert_eq!(text, fs::read_to_string(&plain).unwrap());
+    assert!(!text.contains("ElementBegin"));
+    assert!(!sidecar_path(&plain).exists());
+
+    let map = load(&path);
+    assert_eq!(map.file, Path::new("types.rs"));
+
+    let mut structs = 0;
+    for m in &map.elements {
+        let first = line(&text, m.start).trim();
+        match m.element.construct {
+            Construct::Struct => {
+                structs += 1;
+                assert_eq!(m.element.kind, ElementKind::Object);
+                assert!(first.starts_with("#[derive("));
+                assert!(line(&text, m.end).trim() == "}");
+            }
+            Construct::Enum => assert!(first.starts_with("#[derive(")),
+            Construct::Variant => assert_eq!(m.element.kind, ElementKind::Object),
+            Construct::Field => match m.element.kind {
+                ElementKind::Attribute => {
+                    assert_eq!(
+                        first,
+                        format!("/// pub {}: `{}`,", m.element.name, {
+                            let attr = model.sarzak.exhume_attribute(&m.element.id).unwrap();
+                            attr.attr_t.to_string()
+                        })
+                    )
+                }
+                ElementKind::Relationship => assert!(m.element.name.starts_with('R')),
+                ElementKind::Object | ElementKind::Enumeration => {
+                    panic!("{:?}s aren't fields: {:?}", m.element.kind, m)
+                }
+            },
+            Construct::New => assert!(first.starts_with("impl ")),
+            Construct::Macro => panic!("no macros in types.rs: {:?}", m),
+        }
+
+        // Fields are inside of their struct.
+        if m.element.construct == Construct::Field {
+            assert!(map
+                .lookup(m.start)
+                .any(|o| o.element.construct == Construct::Struct));
+        }
+    }
+    assert!(structs > 0);
+
+    // The innermost element for a field line is the field.
+    let field = map
+        .elements
+        .iter()
+        .find(|m| m.element.kind == ElementKind::Attribute)
+        .unwrap();
+    assert_eq!(map.lookup(field.end).last().unwrap(), field);
+}
+
+#[test]
+fn test_macros_map() {
+    let model = model();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("macros.rs");
+
+    generate_macros(
+        &model,
+        &path,
+        "test_models",
+        &GenerateOptions {
+            source_map: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let text = fs::read_to_string(&path).unwrap();
+    let map = load(&path);
+    assert!(!map.elements.is_empty());
+    for m in &map.elements {
+        assert_eq!(m.element.construct, Construct::Macro);
+        assert_eq!(m.element.kind, ElementKind::Relationship);
+        assert!(line(&text, m.start).starts_with("/// Macro to traverse"));
+        assert!(line(&text, m.start).contains(&format!("_{}", m.element.name)));
+        assert!(line(&text, m.end).starts_with("pub use "));
+    }
+}
+
+/// A struct, with room for the user to get in the way
+fn emit(path: &Path, id: Uuid) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.source_map(true);
+    context.writeln("//! A file with a map");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    context.writeln("pub const GENERATED: bool = true;");
+    context.user_region("top", None).unwrap();
+    context.writeln("");
+    context
+        .begin_element(Construct::Struct, ElementKind::Object, id, "dog")
+        .unwrap();
+    context.begin_critical_block("dog").unwrap();
+    context.writeln("pub struct Dog {}");
+    context.end_critical_block("dog").unwrap();
+    context.end_element(id).unwrap();
+    context
+}
+
+#[test]
+fn test_user_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("user.rs");
+    let id = Uuid::new_v4();
+
+    emit(&path, id).commit().unwrap();
+    let before = load(&path).elements[0].start;
+
+    // Their code pushes ours down, and the map follows along.
+    let mut edited = String::new();
+    for l in fs::read_to_string(&path).unwrap().lines() {
+        edited = edited + l + "\n";
+        if l.contains("UserRegionBegin") {
+            edited += "const MINE: u32 = 3;\n";
+        }
+    }
+    fs::write(&path, &edited).unwrap();
+    emit(&path, id).commit().unwrap();
+
+    let text = fs::read_to_string(&path).unwrap();
+    let map = load(&path);
+    assert_eq!(map.elements.len(), 1);
+    assert_eq!(map.elements[0].element.id, id);
+    assert_eq!(map.elements[0].start, before + 1);
+    assert_eq!(line(&text, map.elements[0].start), "pub struct Dog {}");
+
+    emit(&path, id).check().unwrap();
+}
+
+#[test]
+fn test_stale_map() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stale.rs");
+    let id = Uuid::new_v4();
+
+    emit(&path, id).commit().unwrap();
+    let written = fs::read_to_string(sidecar_path(&path)).unwrap();
+    fs::remove_file(sidecar_path(&path)).unwrap();
+
+    // The code is fine, but the map is missing.
+    match emit(&path, id).check() {
+        Err(Error(CodeGenError::StaleOutput { path: p, diff })) => {
+            assert_eq!(p, sidecar_path(&path));
+            assert!(diff.contains(&format!("+      \"id\": \"{}\",", id)));
+        }
+        r => panic!("expected stale output, got {:?}", r),
+    }
+
+    emit(&path, id).commit().unwrap();
+    assert_eq!(written, fs::read_to_string(sidecar_path(&path)).unwrap());
+}
@@ -0,0 +1,211 @@
+use uuid::Uuid;
+
+use nut::codegen::{
+    CachingContext, CodeGenError, Context, Error, SarzakModel, Symbol, SymbolTable,
+};
+use nut::domain::{generate_types, GenerateOptions};
+use nut::sarzak::{Attribute, Type};
+
+fn symbol(value: &str) -> Symbol {
+    Symbol {
+        value: value.to_owned(),
+        value_type: "u32".to_owned(),
+        is_reference: false,
+    }
+}
+
+#[test]
+fn test_scopes() {
+    let mut table = SymbolTable::new();
+    let outer = Uuid::new_v4();
+    let inner = Uuid::new_v4();
+
+    table
+        .declare(outer, "the outer one", symbol("foo"))
+        .unwrap();
+    table.push_scope();
+
+    // Shadowing is fine.
+    table
+        .declare(inner, "the inner one", symbol("foo"))
+        .unwrap();
+    assert_eq!(table.depth(), 2);
+    assert!(table.get(&outer).is_some());
+    assert!(table.get(&inner).is_some());
+
+    // Same name, same scope, something else, is not.
+    match table.declare(Uuid::new_v4(), "another one", symbol("foo")) {
+        Err(Error(e @ CodeGenError::SymbolCollision { .. })) => assert_eq!(
+            e.to_string(),
+            "😱 the inner one and another one both generate `foo`"
+        ),
+        r => panic!("expected a collision, got {:?}", r),
+    }
+
+    // Saying it again is fine though.
+    table
+        .declare(inner, "the inner one", symbol("foo"))
+        .unwrap();
+
+    table.pop_scope().unwrap();
+    assert!(table.get(&inner).is_none());
+    assert!(table.get_by_name("foo").is_some());
+    assert!(matches!(
+        table.pop_scope(),
+        Err(Error(CodeGenError::PoppedGlobalScope))
+    ));
+}
+
+#[test]
+fn test_redeclared() {
+    let mut table = SymbolTable::new();
+    let renamed = Uuid::new_v4();
+    let other = Uuid::new_v4();
+
+    table
+        .declare(renamed, "the renamed one", symbol("foo"))
+        .unwrap();
+    table
+        .declare(renamed, "the renamed one", symbol("bar"))
+        .unwrap();
+
+    // `foo` isn't anyone's anymore, so it's free.
+    assert_eq!(table.get_by_name("bar").unwrap().value, "bar");
+    table.declare(other, "another one", symbol("foo")).unwrap();
+    assert_eq!(table.get(&other).unwrap().value, "foo");
+
+    // Same goes for when the name is replaced with a value.
+    table.insert(other, symbol("42"));
+    assert!(table.get_by_name("foo").is_none());
+    table
+        .declare(Uuid::new_v4(), "yet another", symbol("foo"))
+        .unwrap();
+}
+
+#[test]
+fn test_merged_collision() {
+    let mut fields = CachingContext::new();
+    fields.push_scope();
+    fields
+        .declare_symbol(Uuid::new_v4(), "attribute `type`", symbol("ty"))
+        .unwrap();
+
+    let mut more = CachingContext::new();
+    more.declare_symbol(Uuid::new_v4(), "attribute `ty`", symbol("ty"))
+        .unwrap();
+    more.inter_symbol(Uuid::new_v4(), symbol("42"));
+
+    // Adding can't fail, so it waits until the scope is closed.
+    fields += more;
+    assert!(fields.exhume_symbol_by_name("ty").is_some());
+    match fields.pop_scope() {
+        Err(Error(CodeGenError::SymbolCollision {
+            name,
+            first,
+            second,
+        })) => {
+            assert_eq!(name, "ty");
+            assert_eq!(first, "attribute `type`");
+            assert_eq!(second, "attribute `ty`");
+        }
+        r => panic!("expected a collision, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_rendered_collision() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("collide.rs");
+
+    let mut one = CachingContext::new();
+    one.declare_symbol(Uuid::new_v4(), "one thing", symbol("same"))
+        .unwrap();
+    let mut two = CachingContext::new();
+    two.declare_symbol(Uuid::new_v4(), "another", symbol("same"))
+        .unwrap();
+
+    let mut context = Context::new(&path, false).unwrap();
+    context += one.into();
+    context += two.into();
+    assert!(matches!(
+        context.commit(),
+        Err(Error(CodeGenError::SymbolCollision { .. }))
+    ));
+    assert!(!path.exists());
+}
+
+fn generate(model: &SarzakModel) -> Result<(), CodeGenError> {
+    let dir = tempfile::tempdir().unwrap();
+    generate_types(
+        model,
+        &dir.path().join("types.rs"),
+        "test_models",
+        &GenerateOptions {
+            new_impl: true,
+            ..Default::default()
+        },
+    )
+    .map(|_| ())
+    .map_err(|Error(e)| e)
+}
+
+#[test]
+fn test_model_collisions() {
+    // Dog formalizes R2 with an attribute called `id`, and it already has one.
+    let model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+    match generate(&model) {
+        Err(CodeGenError::SymbolCollision {
+            name,
+            first,
+            second,
+        }) => {
+            assert_eq!(name, "id");
+            assert_eq!(first, "attribute `id`");
+            assert_eq!(second, "referential attribute `id` (R2)");
+        }
+        r => panic!("expected a collision, got {:?}", r),
+    }
+
+    let mut model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/one_to_one.json").unwrap();
+    let (_, object) = model
+        .sarzak
+        .iter_object()
+        .find(|(_, o)| o.name.inner() == "Referent")
+        .unwrap();
+    let mut object = object.clone();
+
+    // `type` is rendered as `ty`, so these two are the same.
+    for name in ["type", "ty"] {
+        let attr = Attribute {
+            id: Uuid::new_v4(),
+            ..Attribute::new(name, Type::Integer)
+        };
+        object.attributes.insert(name.to_owned(), attr.id);
+        model.sarzak.inter_attribute(attr);
+    }
+    model.sarzak.inter_object(object.clone());
+    match generate(&model) {
+        Err(e @ CodeGenError::SymbolCollision { .. }) => {
+            assert!(e.to_string().ends_with("both generate `ty`"));
+            assert!(e.to_string().contains("attribute `ty`"));
+        }
+        r => panic!("expected a collision, got {:?}", r),
+    }
+
+    // A field called `store` is fine in the struct, but not in `new`.
+    object
+        .attributes
+        .retain(|name, _| name != "ty" && name != "type");
+    let attr = Attribute::new("store", Type::Boolean);
+    object.attributes.insert("store".to_owned(), attr.id);
+    model.sarzak.inter_attribute(attr);
+    model.sarzak.inter_object(object);
+    match generate(&model) {
+        Err(CodeGenError::SymbolCollision { name, first, .. }) => {
+            assert_eq!(name, "store");
+            assert_eq!(first, "the `store` parameter to `new`");
+        }
+        r => panic!("expected a collision, got {:?}", r),
+    }
+}
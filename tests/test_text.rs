@@ -0,0 +1,403 @@
+use nut::codegen::{CodeGenError, SarzakModel};
+use nut::model::{parse_model, text::parse, to_text};
+use nut::sarzak::{diff_models, Cardinality, Conditionality, Enumeration, Type};
+
+mod common;
+
+const EXAMPLE: &str = r#"
+/// Cats and dogs, living together.
+domain cat_dog
+
+object Animal (ANI) {
+    id: Uuid,
+}
+
+object Dog (DOG) {
+    id: Uuid,
+    name: String,
+}
+
+object Cat (CAT) {
+    id: Uuid,
+}
+
+// Not this, though.
+object "Dog Fight" (DF) {
+    id: Uuid,
+}
+
+/// Doc comments become descriptions. Dogs howl at it.
+///
+/// ❗️ {"singleton_object": true}
+object Moon (MN) {
+}
+
+isa R1 Animal { Cat, Dog }
+
+binary R2 {
+    referrer Dog 1 "is chased by" via chases
+    referent Cat 1c "chases"
+}
+
+associative R3 {
+    link "Dog Fight" M via dog, cat
+    one Dog Mc "picks on"
+    other Cat M "fights back against"
+}
+"#;
+
+#[test]
+fn test_parse() {
+    let model = parse_model(EXAMPLE).unwrap();
+    let store = &model.sarzak;
+
+    assert_eq!(model.domain, "cat_dog");
+    assert_eq!(model.description, "Cats and dogs, living together.");
+
+    let dog = store.get_obj("Dog").unwrap();
+    assert_eq!(dog.key_letter, "DOG");
+    assert!(!model.config.is_singleton(&dog.id));
+
+    let moon = store.get_obj("Moon").unwrap();
+    assert!(moon
+        .description
+        .starts_with("Doc comments become descriptions. Dogs howl at it.\n\n"));
+    assert!(model.config.is_singleton(&moon.id));
+
+    let cat = store.get_obj("Cat").unwrap();
+    // The referential attribute is the relationship's business.
+    assert!(!dog.attributes.contains_key("chases"));
+
+    let fight = store.get_obj("Dog Fight").unwrap();
+    assert_eq!(fight.key_letter, "DF");
+
+    let r2 = store.get_rel("R2").unwrap();
+    let binary = store.exhume_binary(r2.get_id()).unwrap();
+    let referrer = store.exhume_referrer(&binary.from).unwrap();
+    let referent = store.exhume_referent(&binary.to).unwrap();
+    assert_eq!(referrer.obj_id, dog.id);
+    assert_eq!(referrer.referential_attribute.inner(), "chases");
+    assert_eq!(referent.obj_id, cat.id);
+    assert_eq!(referent.conditionality, Conditionality::Conditional);
+    assert_eq!(referent.description, "chases");
+
+    let r1 = store.get_rel("R1").unwrap();
+    let isa = store.exhume_isa(r1.get_id()).unwrap();
+    assert_eq!(isa.subtypes.len(), 2);
+
+    let r3 = store.get_rel("R3").unwrap();
+    let assoc = store.exhume_associative(r3.get_id()).unwrap();
+    let from = store.exhume_associative_referrer(&assoc.from).unwrap();
+    assert_eq!(from.obj_id, fight.id);
+    assert_eq!(from.cardinality, Cardinality::Many);
+    let one = store.exhume_associative_referent(&assoc.one).unwrap();
+    assert_eq!(one.obj_id, dog.id);
+    assert_eq!(one.description, "picks on");
+
+    // Same text, same ids.
+    let again = parse_model(EXAMPLE).unwrap();
+    assert_eq!(again.sarzak.get_obj("Dog").unwrap().id, dog.id);
+}
+
+/// The example at the top of the `text` module docs
+fn doc_example() -> String {
+    include_str!("../src/model/text.rs")
+        .lines()
+        .skip_while(|l| *l != "//! ```text")
+        .skip(1)
+        .take_while(|l| *l != "//! ```")
+        .map(|l| {
+            l.strip_prefix("//!")
+                .unwrap()
+                .strip_prefix(' ')
+                .unwrap_or("")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_examples_generate() {
+    for src in [EXAMPLE.to_owned(), doc_example()] {
+        let model = parse_model(&src).unwrap();
+        model.ensure_valid().unwrap();
+
+        common::generate(&model, "cat_dog");
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    for path in [
+        "models/cat_dog.json",
+        "crates/test_models/models/associative.json",
+        "crates/test_models/models/one_to_many.json",
+    ] {
+        let model = SarzakModel::load_cuckoo_model(path).unwrap();
+        let text = to_text(&model);
+
+        let parsed = parse_model(&text).unwrap();
+        let diff = diff_models(&model, &parsed);
+        assert!(diff.is_empty(), "{}:\n{}\n{}", path, diff, text);
+
+        // Printing is a fixed point.
+        assert_eq!(to_text(&parsed), text);
+    }
+}
+
+const TYPES: &str = r#"
+domain types
+
+/// How it's feeling
+enum Mood { Aloof, Hungry, "Up To No Good" }
+
+object Cat (CAT) {
+    id: Uuid,
+    lives: Unsigned8,
+    steps: Unsigned64,
+    weight: Float32,
+    born: Timestamp,
+    nap: Duration,
+    photo: Bytes,
+    mood: Mood?,
+    nickname: String?,
+}
+"#;
+
+#[test]
+fn test_attribute_types() {
+    let model = parse_model(TYPES).unwrap();
+    let store = &model.sarzak;
+    let cat = store.get_obj("Cat").unwrap();
+    let ty = |name: &str| {
+        store
+            .exhume_attribute(cat.attributes.get(name).unwrap())
+            .unwrap()
+            .attr_t
+            .clone()
+    };
+
+    assert_eq!(ty("lives"), Type::Unsigned8);
+    assert_eq!(ty("steps"), Type::Unsigned64);
+    assert_eq!(ty("weight"), Type::Float32);
+    assert_eq!(ty("born"), Type::Timestamp);
+    assert_eq!(ty("nap"), Type::Duration);
+    assert_eq!(ty("photo"), Type::Bytes);
+    assert_eq!(ty("nickname"), Type::Optional(Box::new(Type::String)));
+
+    let mut mood = Enumeration::new("Mood", &["Aloof", "Hungry", "Up To No Good"]);
+    mood.description = "How it's feeling".to_owned();
+    assert_eq!(
+        ty("mood"),
+        Type::Optional(Box::new(Type::Enumeration(mood.clone())))
+    );
+    assert_eq!(store.sorted_enumerations(), vec![&mood]);
+
+    // What rustc sees.
+    assert_eq!(ty("mood").to_string(), "Option<Mood>");
+    assert_eq!(ty("born").to_string(), "std::time::SystemTime");
+    assert_eq!(ty("photo").to_string(), "Vec<u8>");
+
+    // And back again, by way of text, and by way of Cuckoo.
+    let text = to_text(&model);
+    assert!(text.contains("enum Mood { Aloof, Hungry, \"Up To No Good\" }"));
+    assert!(text.contains("    mood: Mood?,"));
+    let parsed = parse_model(&text).unwrap();
+    assert!(diff_models(&model, &parsed).is_empty());
+    assert_eq!(to_text(&parsed), text);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("types.json");
+    model.save_cuckoo_model(&path).unwrap();
+    let loaded = SarzakModel::load_cuckoo_model(&path).unwrap();
+    assert_eq!(to_text(&loaded), text);
+}
+
+const SEATS: &str = r#"
+domain seats
+
+object Seat (ST) {
+    id: Uuid,
+    *1 row: Unsigned8,
+    *number: Unsigned8,
+    *2 *3 ticket: String,
+    price: Float,
+}
+"#;
+
+#[test]
+fn test_identifiers() {
+    let model = parse_model(SEATS).unwrap();
+    let store = &model.sarzak;
+    let seat = store.get_obj("Seat").unwrap();
+    let ids = |name: &str| {
+        store
+            .exhume_attribute(seat.attributes.get(name).unwrap())
+            .unwrap()
+            .identifiers
+            .clone()
+    };
+
+    assert_eq!(ids("row"), vec![1]);
+    assert_eq!(ids("number"), vec![1]);
+    assert_eq!(ids("ticket"), vec![2, 3]);
+    assert!(ids("price").is_empty());
+    assert!(ids("id").is_empty());
+
+    let text = to_text(&model);
+    assert!(text.contains("    *1 number: Unsigned8,\n"));
+    assert!(text.contains("    *2 *3 ticket: String,\n"));
+    assert!(text.contains("    price: Float,\n"));
+    assert_eq!(to_text(&parse_model(&text).unwrap()), text);
+
+    // Moving an attribute to another identifier is a change.
+    let moved = parse_model(&SEATS.replace("*2 *3 ticket", "*3 ticket")).unwrap();
+    let diff = diff_models(&model, &moved);
+    assert_eq!(diff.changes.len(), 1);
+    assert!(diff.to_string().contains("*2 *3"), "{}", diff);
+}
+
+fn parse_error(src: &str) -> (usize, usize, String, String) {
+    match parse(src).unwrap_err().0 {
+        CodeGenError::ParseTextModel {
+            line,
+            column,
+            message,
+            snippet,
+        } => (line, column, message, snippet),
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_syntax_errors() {
+    let (line, column, message, snippet) =
+        parse_error("domain oops\n\nobject Cat [CAT] {\n    id: Uuid,\n}\n");
+    assert_eq!((line, column), (3, 12));
+    assert_eq!(message, "unexpected character `[`");
+    assert_eq!(snippet, "  |\n3 | object Cat [CAT] {\n  |            ^");
+
+    let (line, column, message, _) = parse_error("domain oops\nobject Cat (CAT) {\n    id Uuid\n}");
+    assert_eq!((line, column), (3, 8));
+    assert_eq!(message, "expected `:`, found `Uuid`");
+
+    let (_, _, message, _) = parse_error("domain oops\nobject Cat (CAT) {\n    id: Uuid,\n");
+    assert_eq!(
+        message,
+        "expected an attribute name, found the end of the file"
+    );
+
+    let (line, column, message, snippet) = parse_error(
+        "domain oops\nobject A (A) {}\nobject B (B) {}\n\
+         binary R1 {\n    referrer A 2 \"likes\" via b\n    referent B 1 \"is liked by\"\n}\n",
+    );
+    assert_eq!((line, column), (5, 16));
+    assert_eq!(
+        message,
+        "expected a multiplicity: `1`, `1c`, `M` or `Mc`, found `2`"
+    );
+    assert!(snippet.ends_with("               ^"), "{}", snippet);
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { name: \"oops }");
+    assert_eq!(message, "unterminated string");
+
+    // There's only the one way to make a referential attribute.
+    let (line, column, message, _) =
+        parse_error("domain oops\nobject A (A) {}\nobject B (B) { a: &A }\n");
+    assert_eq!((line, column), (3, 19));
+    assert!(message.starts_with("referential attributes come from `via`"));
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { a -> String }\n");
+    assert_eq!(message, "unexpected character `-`");
+}
+
+#[test]
+fn test_semantic_errors() {
+    let (line, column, message, snippet) = parse_error(
+        "domain oops\nobject A (A) {}\n\
+         binary R1 {\n    referrer A 1 \"likes\" via b\n    referent Bee 1 \"is liked by\"\n}\n",
+    );
+    assert_eq!((line, column), (5, 14));
+    assert_eq!(message, "no such object `Bee`");
+    assert!(snippet.ends_with("             ^^^"), "{}", snippet);
+
+    let (line, _, message, _) = parse_error(
+        "domain oops\nobject A (A) {}\nobject B (B) {}\nisa R1 A { B }\nisa R1 B { A }\n",
+    );
+    assert_eq!(line, 5);
+    assert_eq!(message, "R1 is already defined");
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) {}\nobject A (AA) {}\n");
+    assert_eq!(message, "object `A` is already defined");
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { id: Uuid, id: String }\n");
+    assert_eq!(message, "attribute `id` is already defined on `A`");
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { id: Guid }\n");
+    assert_eq!(
+        message,
+        "unknown type `Guid`, expected one of Uuid, Integer, Float, String, Boolean, a sized \
+         number like Unsigned8 or Float32, Timestamp, Duration, Bytes, or an enumeration"
+    );
+
+    let (_, _, message, _) =
+        parse_error("domain oops\nenum E { X, Y, X }\nobject A (A) { e: E }\n");
+    assert_eq!(message, "variant `X` is already defined on `E`");
+
+    let (_, _, message, _) = parse_error("domain oops\nenum A { X }\nobject A (A) { a: A }\n");
+    assert_eq!(message, "`A` is already defined");
+
+    let (_, _, message, _) = parse_error("domain oops\nenum E {}\nobject A (A) { e: E }\n");
+    assert_eq!(message, "enumeration `E` needs at least one variant");
+
+    let (_, _, message, _) = parse_error("domain oops\nenum E { X }\nobject A (A) { id: Uuid }\n");
+    assert!(message.starts_with("enumeration `E` isn't the type of any attribute"));
+
+    let (_, _, message, _) = parse_error(
+        "domain oops\nobject A (A) {}\nobject B (B) {}\nobject C (C) {}\n\
+         associative R1 {\n    link C 1c via a, b\n    one A 1 \"x\"\n    other B 1 \"y\"\n}\n",
+    );
+    assert!(message.starts_with("an associative object can't be conditional"));
+
+    let (line, column, message, _) =
+        parse_error("domain oops\nobject A (A) {\n    *1 id: Uuid,\n}\n");
+    assert_eq!((line, column), (3, 5));
+    assert_eq!(
+        message,
+        "`id` is made out of the identifiers, it can't be one"
+    );
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { *a: String? }\n");
+    assert_eq!(
+        message,
+        "an optional attribute can't be part of an identifier"
+    );
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { *0 a: String }\n");
+    assert_eq!(message, "identifiers start at `*1`");
+
+    let (_, _, message, _) = parse_error("domain oops\nobject A (A) { *1 * a: String }\n");
+    assert_eq!(message, "`a` is already part of identifier `*1`");
+}
+
+#[test]
+fn test_load_text_model() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.nut");
+    std::fs::write(
+        &path,
+        "domain broken\nobject A (A) { id: Uuid }\nisa R1 A { B }\n",
+    )
+    .unwrap();
+
+    let message = SarzakModel::load_text_model(&path).unwrap_err().to_string();
+    assert!(message.contains("broken.nut"), "{}", message);
+    assert!(message.contains("3:12: no such object `B`"), "{}", message);
+
+    assert!(matches!(
+        SarzakModel::load_text_model(dir.path().join("missing.nut"))
+            .unwrap_err()
+            .0,
+        CodeGenError::LoadTextModel { .. }
+    ));
+}
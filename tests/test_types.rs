@@ -0,0 +1,119 @@
+use nut::codegen::CodeGenError;
+use nut::domain::generate_types;
+use nut::model::parse_model;
+use nut::sarzak::Type;
+
+mod common;
+
+const TYPES: &str = r#"
+domain types
+
+/// How it's feeling
+enum Mood { Aloof, Hungry, "Up To No Good" }
+
+object Cat (CAT) {
+    id: Uuid,
+    lives: Unsigned8,
+    age: Integer16,
+    steps: Unsigned64,
+    weight: Float32,
+    nap: Duration,
+    photo: Bytes,
+    mood: Mood,
+    nickname: String?,
+}
+
+object Visit (VIS) {
+    id: Uuid,
+    arrived: Timestamp,
+    left: Timestamp?,
+    mood: Mood?,
+}
+"#;
+
+#[test]
+fn test_enumerations() {
+    let types = common::generate(&parse_model(TYPES).unwrap(), "types").types;
+
+    assert!(types.contains("//!    * [`Mood`]\n//!    * [`Cat`]\n"));
+    assert!(types.contains("/// How it's feeling\n///\n"));
+    assert!(types.contains(
+        "#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]\n\
+         pub enum Mood {\n    #[default]\n    Aloof,\n    Hungry,\n    UpToNoGood,\n}\n"
+    ));
+    // Just the once, no matter how many attributes use it.
+    assert_eq!(types.matches("pub enum Mood {").count(), 1);
+}
+
+#[test]
+fn test_fields() {
+    let types = common::generate(&parse_model(TYPES).unwrap(), "types").types;
+
+    for field in [
+        "pub lives: u8,",
+        "pub age: i16,",
+        "pub steps: u64,",
+        "pub weight: f32,",
+        "pub nap: std::time::Duration,",
+        "pub photo: Vec<u8>,",
+        "pub mood: Mood,",
+        "pub nickname: Option<std::string::String>,",
+        "pub arrived: std::time::SystemTime,",
+        "pub left: Option<std::time::SystemTime>,",
+        "pub mood: Option<Mood>,",
+    ] {
+        assert!(types.contains(field), "missing `{}`", field);
+    }
+
+    // There's no default time.
+    assert!(types.contains(
+        "#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]\npub struct Cat {"
+    ));
+    assert!(types.contains(
+        "#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]\npub struct Visit {"
+    ));
+}
+
+#[test]
+fn test_new() {
+    let types = common::generate(&parse_model(TYPES).unwrap(), "types").types;
+
+    assert!(types.contains("        mood: Option<Mood>,\n"));
+    assert!(types.contains("        arrived: std::time::SystemTime,\n"));
+    // The new types aren't all `Display`.
+    assert!(types.contains("format!(\"{:?}::{:?}::{:?}::\", left, mood, arrived,)"));
+
+    // And the doc tests have something to say about each of them.
+    assert!(types.contains("/// # use types::types::Mood;"));
+    assert!(types.contains(", std::time::Duration::from_secs(42), "));
+    assert!(types.contains(" = b\""));
+    assert!(types.contains(", Mood::Aloof, "));
+    assert!(types.contains(
+        "Some(std::time::SystemTime::UNIX_EPOCH), Some(Mood::Aloof), std::time::SystemTime::UNIX_EPOCH);"
+    ));
+}
+
+#[test]
+fn test_no_example_value() {
+    let mut model = parse_model(TYPES).unwrap();
+    let cat = model.sarzak.get_obj("Cat").unwrap().clone();
+    let visit = model.sarzak.get_obj("Visit").unwrap();
+    let mut lives = model
+        .sarzak
+        .exhume_attribute(cat.attributes.get("lives").unwrap())
+        .unwrap()
+        .clone();
+    lives.attr_t = Type::ForeignKey(visit.id);
+    model.sarzak.inter_attribute(lives);
+    assert!(model.ensure_valid().is_ok());
+
+    // Nothing to put in the doc test, so it's an error, rather than a panic.
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("types.rs");
+    match generate_types(&model, &path, "types", &common::options()).map_err(|e| e.0) {
+        Err(e @ CodeGenError::NoExampleValue { .. }) => {
+            assert!(e.to_string().contains("lives on Cat is a `"), "{}", e)
+        }
+        r => panic!("expected no example value, got {:?}", r),
+    }
+}
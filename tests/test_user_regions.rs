@@ -0,0 +1,126 @@
+use std::fs;
+
+use uuid::Uuid;
+
+use nut::codegen::{Context, SarzakModel};
+use nut::domain::generate_types;
+
+mod common;
+
+/// Generate a struct for each of `objects`, each with a region for extras
+fn emit(path: &std::path::Path, objects: &[(&str, Uuid)]) -> Context {
+    let mut context = Context::new(path, false).unwrap();
+    context.writeln("//! A file for the user");
+    context.writeln("// Generated code, and");
+    context.writeln("// this is where the header goes.");
+    for (name, id) in objects {
+        context
+            .begin_critical_block(format!("{}-struct", name))
+            .unwrap();
+        context.writeln(format!("pub struct {} {{}}", name));
+        context
+            .end_critical_block(format!("{}-struct", name))
+            .unwrap();
+        context.writeln("");
+        context
+            .user_region(format!("{}-impl-extras", name), Some(*id))
+            .unwrap();
+        context.writeln("");
+    }
+    context
+}
+
+const EXTRAS: &str = "impl Dog {\n    pub fn bark(&self) {}\n}\n";
+
+/// Put EXTRAS in the region named `name`
+fn add_extras(path: &std::path::Path, name: &str) {
+    let text = fs::read_to_string(path).unwrap();
+    let mut out = String::new();
+    for line in text.lines() {
+        out = out + line + "\n";
+        if line.contains("UserRegionBegin") && line.contains(&format!("\"{}\"", name)) {
+            out += EXTRAS;
+        }
+    }
+    fs::write(path, out).unwrap();
+}
+
+#[test]
+fn test_reordered() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reordered.rs");
+    let cat = ("Cat", Uuid::new_v4());
+    let dog = ("Dog", Uuid::new_v4());
+
+    emit(&path, &[cat, dog]).commit().unwrap();
+    add_extras(&path, "Dog-impl-extras");
+    let edited = fs::read_to_string(&path).unwrap();
+
+    // Move the dog first. The barking goes with it, and only there.
+    let displaced = emit(&path, &[dog, cat]).commit().unwrap();
+    assert!(displaced.is_empty());
+
+    let after = fs::read_to_string(&path).unwrap();
+    assert_eq!(after.matches("pub fn bark").count(), 1);
+    assert!(after.find("pub struct Dog").unwrap() < after.find("pub fn bark").unwrap());
+    assert!(after.find("pub fn bark").unwrap() < after.find("pub struct Cat").unwrap());
+    assert!(!after.contains("//⚡️"));
+
+    // And back again.
+    emit(&path, &[cat, dog]).commit().unwrap();
+    assert_eq!(edited, fs::read_to_string(&path).unwrap());
+    emit(&path, &[cat, dog]).check().unwrap();
+}
+
+#[test]
+fn test_renamed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("renamed.rs");
+    let id = Uuid::new_v4();
+
+    emit(&path, &[("Dog", id)]).commit().unwrap();
+    add_extras(&path, "Dog-impl-extras");
+
+    // Same object, new name. It's found by it's id.
+    emit(&path, &[("Hound", id)]).commit().unwrap();
+    let after = fs::read_to_string(&path).unwrap();
+    assert!(after.contains("pub struct Hound"));
+    assert!(!after.contains("pub struct Dog"));
+    assert!(!after.contains("Dog-impl-extras"));
+    assert_eq!(after.matches("pub fn bark").count(), 1);
+    assert!(after.find("Hound-impl-extras").unwrap() < after.find("pub fn bark").unwrap());
+}
+
+#[test]
+fn test_orphaned() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orphaned.rs");
+    let cat = ("Cat", Uuid::new_v4());
+    let dog = ("Dog", Uuid::new_v4());
+
+    emit(&path, &[cat, dog]).commit().unwrap();
+    add_extras(&path, "Dog-impl-extras");
+
+    // The dog is gone, but what they wrote is theirs, so it stays.
+    emit(&path, &[cat]).commit().unwrap();
+    let after = fs::read_to_string(&path).unwrap();
+    assert!(!after.contains("pub struct Dog"));
+    assert_eq!(after.matches("pub fn bark").count(), 1);
+}
+
+#[test]
+fn test_generated_extras() {
+    let model =
+        SarzakModel::load_cuckoo_model("crates/test_models/models/one_to_one.json").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("types.rs");
+
+    let generate = || generate_types(&model, &path, "test_models", &common::options()).unwrap();
+
+    generate();
+    add_extras(&path, "referent-impl-extras");
+    let edited = fs::read_to_string(&path).unwrap();
+
+    assert!(generate().is_empty());
+    assert_eq!(edited, fs::read_to_string(&path).unwrap());
+}
@@ -0,0 +1,107 @@
+use std::fs;
+
+use uuid::Uuid;
+
+use nut::codegen::SarzakModel;
+use nut::model::parse_model;
+use nut::sarzak::{validate_model, AttributeName, Diagnostic, Enumeration, Object, Type};
+
+#[test]
+fn test_models_are_valid() {
+    for entry in fs::read_dir("crates/test_models/models").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|e| e == "json") {
+            let model = SarzakModel::load_cuckoo_model(&path).unwrap();
+            let diagnostics = validate_model(&model);
+            assert!(
+                diagnostics.is_empty(),
+                "{}: {:#?}",
+                path.display(),
+                diagnostics
+            );
+        }
+    }
+}
+
+#[test]
+fn test_validate_cat_dog() {
+    let mut model = SarzakModel::load_cuckoo_model("models/cat_dog.json").unwrap();
+
+    // Dog formalizes R2 with an attribute called `id`. Which it already has.
+    assert_eq!(
+        validate_model(&model),
+        vec![Diagnostic::ReferentialAttributeCollision {
+            object: "Dog".to_owned(),
+            attribute: "id".to_owned(),
+            number: 2,
+        }]
+    );
+
+    // Now we break things.
+    let missing = Uuid::new_v4();
+    let mut cow = Object {
+        id: Uuid::new_v4(),
+        key_letter: "C".to_owned(),
+        name: AttributeName::new("Cow".to_owned()),
+        ..Default::default()
+    };
+    cow.attributes.insert("moo".to_owned(), missing);
+    model.sarzak.inter_object(cow);
+
+    let diagnostics = validate_model(&model);
+    assert!(diagnostics.contains(&Diagnostic::DanglingAttribute {
+        object: "Cow".to_owned(),
+        attribute: "moo".to_owned(),
+        id: missing,
+    }));
+    assert!(diagnostics.contains(&Diagnostic::DuplicateKeyLetter {
+        key_letter: "C".to_owned(),
+        objects: vec!["Cat".to_owned(), "Cow".to_owned()],
+    }));
+
+    // Make sure that we are saying something useful.
+    let err = model.ensure_valid().unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("key letter `C` is shared by objects `Cat`, `Cow`"));
+    assert!(message.contains("object `Cow`: attribute `moo`"));
+}
+
+#[test]
+fn test_validate_enumerations() {
+    let mut model = parse_model(
+        "domain moods\nenum Mood { Aloof, Hungry }\n\
+         object Cat (CAT) { mood: Mood }\nobject Dog (DOG) { mood: Mood? }\n",
+    )
+    .unwrap();
+    assert!(validate_model(&model).is_empty());
+
+    // The dog has a different idea about what a mood is.
+    let dog = model.sarzak.get_obj("Dog").unwrap();
+    let mut mood = model
+        .sarzak
+        .exhume_attribute(dog.attributes.get("mood").unwrap())
+        .unwrap()
+        .clone();
+    mood.attr_t = Type::Optional(Box::new(Type::Enumeration(Enumeration::new(
+        "Mood",
+        &["Aloof", "Hungry", "Sleepy"],
+    ))));
+    model.sarzak.inter_attribute(mood.clone());
+
+    assert_eq!(
+        validate_model(&model),
+        vec![Diagnostic::ConflictingEnumeration {
+            enumeration: "Mood".to_owned(),
+            objects: vec!["Cat".to_owned(), "Dog".to_owned()],
+        }]
+    );
+
+    // And then it doesn't have any at all.
+    mood.attr_t = Type::Enumeration(Enumeration::new::<&str>("Mood", &[]));
+    model.sarzak.inter_attribute(mood);
+
+    let message = model.ensure_valid().unwrap_err().to_string();
+    assert!(message
+        .contains("object `Dog`: attribute `mood` is enumeration `Mood`, which has no variants"));
+    assert!(message.contains("enumeration `Mood` has different variants in objects `Cat`, `Dog`"));
+}